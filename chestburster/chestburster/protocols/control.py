@@ -310,6 +310,30 @@ class ControlChannel(Thread):
         return reply
 
 
+    def tracer_attach_process(self, pid):
+        # attach_process pid
+        cmd = 'attach_process ' + str(pid)
+        self._send_message(cmd)
+        message = self._receive_message()
+        reply = str(message)    # wait for ACK string
+        return reply
+
+    def tracer_detach_process(self, pid):
+        # detach_process pid
+        cmd = 'detach_process ' + str(pid)
+        self._send_message(cmd)
+        message = self._receive_message()
+        reply = str(message)    # wait for ACK string
+        return reply
+
+    def tracer_flush_history(self, pid, path):
+        # flush_history pid path
+        cmd = 'flush_history ' + str(pid) + ' ' + str(path)
+        self._send_message(cmd)
+        message = self._receive_message()
+        reply = str(message)    # wait for ACK string
+        return reply
+
     def tracer_start_tracing(self, pid=[]):
         # start_tracing pid1 pid2 ...
         cmd = ['start_tracing'] + list(map(str, pid))
@@ -44,6 +44,21 @@ class TracerTarget(SyscallForwardTarget):
         self.protocols.ctrl_syscall = cc
         self.update_state(TargetStates.INITIALIZED)
 
+    #@watch('TargetInit')
+    #@action_valid_decorator_factory(TargetStates.NOT_RUNNING, 'ctrl_syscall')
+    def attach_process(self, pid):
+        return self.protocols.ctrl_syscall.tracer_attach_process(pid)
+
+    #@watch('TargetInit')
+    #@action_valid_decorator_factory(TargetStates.NOT_RUNNING, 'ctrl_syscall')
+    def detach_process(self, pid):
+        return self.protocols.ctrl_syscall.tracer_detach_process(pid)
+
+    #@watch('TargetInit')
+    #@action_valid_decorator_factory(TargetStates.RUNNING, 'ctrl_syscall')
+    def flush_history(self, pid, path):
+        return self.protocols.ctrl_syscall.tracer_flush_history(pid, path)
+
     #@watch('TargetInit')
     #@action_valid_decorator_factory(TargetStates.NOT_RUNNING, 'ctrl_syscall')
     def start_tracing(self, pid=[]):
@@ -85,8 +85,8 @@ impl ExecutingThread {
         let copy_stop = self.stop.clone();
         let copy_stopped = self.stopped.clone();
         let ptrace_op = targets::ptrace::Ptrace{ };
-        let regs_op = Box::new(ptrace_op.clone());
-        let mem_op = Box::new(ptrace_op);
+        let regs_op = Box::new(ptrace_op);
+        let mem_op = Box::new(targets::process_vm::ProcessVm{ });
         let operator = Box::new(Operation{ register: regs_op, memory: mem_op});
 
         let executor = ExecutorEngine::new(TargetArch::X86_64,
@@ -55,7 +55,9 @@ impl ExecDebugger {
         let ip = Ipv4Addr::new(127, 0, 0, 1);
         let port: u16 = 31001;
 
-        self.control_channel.listen(ip, port);
+        if let Err(err) = self.control_channel.listen(ip, port) {
+            eprintln!("Unable to listen on {}:{}: {}", ip, port, err);
+        }
     }
 }
 
@@ -8,10 +8,12 @@ mod tracing_thread;
 
 use std::{
     collections::HashMap,
-    thread::{ Builder, JoinHandle },
+    os::unix::thread::JoinHandleExt,
+    thread::{ self, Builder, JoinHandle },
+    time::Duration,
     sync::{ 
         Arc, Barrier,
-        mpsc::{ channel, Sender, Receiver },
+        mpsc::{ channel, Sender, Receiver, RecvTimeoutError },
     },
     io::{self, ErrorKind },
     net::Ipv4Addr,
@@ -20,14 +22,20 @@ use std::{
 use nix::{
     sys::{
         ptrace,
-        signal::Signal
+        signal::{ sigaction, SaFlags, SigAction, SigHandler, SigSet, Signal },
     },
     unistd::Pid,
 };
 
 use sysfwd::{
+    memory::unwind::UnwindMethod,
     protocol::control::{ Configuration, ControlChannel },
-    tracer::TracerCallback,
+    syscall::{
+        decoder::DecodeLevel,
+        format::LogFormat,
+    },
+    tracer::{ TracerCallback, history::DEFAULT_HISTORY_CAPACITY },
+    trace::MatchStrictness,
 };
 
 use crate::tracing_thread::TracingThread;
@@ -38,6 +46,16 @@ static IP_ADDRESS: &str = "127.0.0.1";
 //static CONTROL_PORT: u16 = 31000;
 static TRACER_PORT: u16 = 32000;
 static EXECUTOR_PORT: u16 = 32001;
+static LOG_FORMAT: LogFormat = LogFormat::Strace;
+static DECODE_LEVEL: DecodeLevel = DecodeLevel::Full;    // for the syscalls not needed by the rules
+/* Without CAP_SYS_ADMIN, the seccomp filter sets PR_SET_NO_NEW_PRIVS: the setuid programs executed by the tracee are not privileged */
+static USE_SECCOMP: bool = true;      // only the syscalls needed by the rules stop the tracee
+static UNWIND_METHOD: Option<UnwindMethod> = None;     // the call stack of each syscall, e.g. Some(UnwindMethod::EhFrame)
+static HISTORY_CAPACITY: usize = DEFAULT_HISTORY_CAPACITY;     // the last syscalls kept in memory, until flushed
+static HISTORY_RAW_ONLY: bool = false;      // drop the decoded arguments from the history
+static TRACE_PATH: Option<&str> = None;     // record the syscalls, e.g. Some("/tmp/sysfwd-{pid}.trace")
+static REPLAY_PATH: Option<&str> = None;    // answer the forwarded syscalls from a trace, without executor
+static REPLAY_STRICTNESS: MatchStrictness = MatchStrictness::Args;     // how a syscall matches the recorded one
 
 
 
@@ -81,7 +99,9 @@ impl TraceDebugger {
 
         //self.control_channel.connect(ip, port).unwrap();
 
-        self.control_channel.listen(ip, port);
+        if let Err(err) = self.control_channel.listen(ip, port) {
+            eprintln!("Unable to listen on {}:{}: {}", ip, port, err);
+        }
     }
 }
 
@@ -92,7 +112,8 @@ impl TraceDebugger {
 struct ThreadCtrl {
     handler: JoinHandle<()>,
     barrier: Arc<Barrier>,
-    _tx: Sender<String>,
+    tracing: bool,      // the tracing thread passed the boot barrier
+    tx: Sender<String>,
     rx: Receiver<String>,
 
 }
@@ -108,20 +129,18 @@ impl TraceDebuggerCallback {
         }
     }
 
-}
-
-impl TracerCallback for TraceDebuggerCallback {
-
-    fn spawn_process(&mut self, program: String, prog_args: Vec<String>) -> Result<Pid, io::Error>
+    /*
+     * Run a tracing thread until it is ready to trace, and wait for the PID of its tracee.
+     */
+    fn start_thread<F>(&mut self, create: F) -> Result<Pid, io::Error>
+        where F: FnOnce(Sender<String>, Receiver<String>, Arc<Barrier>) -> TracingThread
     {
-        println!("Creating new tracing thread...");
-
         let (tx_ctrl, rx_ctrl) = channel();
         let (tx_thread, rx_thread) = channel();
         let boot_barrier = Arc::new(Barrier::new(2));
         let barrier_copy = boot_barrier.clone();
 
-        let mut tracing_thread = TracingThread::new(program, prog_args, tx_thread, rx_ctrl, boot_barrier);
+        let mut tracing_thread = create(tx_thread, rx_ctrl, boot_barrier);
 
         /* Create thread and start it */
         let builder = Builder::new();
@@ -132,13 +151,19 @@ impl TracerCallback for TraceDebuggerCallback {
         let thread_ctrl = ThreadCtrl {
             handler: handler,
             barrier: barrier_copy,
-            _tx: tx_ctrl,
+            tracing: false,
+            tx: tx_ctrl,
             rx: rx_thread
         };
 
-        let pid = thread_ctrl.rx.recv().unwrap();
-        let pid = pid.parse().unwrap();
-        let pid = Pid::from_raw(pid);
+        let reply = thread_ctrl.rx.recv().map_err(io::Error::other)?;
+        let pid = match reply.parse() {
+            Ok(pid) => Pid::from_raw(pid),
+            Err(_) => {
+                let _ = thread_ctrl.handler.join();
+                return Err(io::Error::other(reply));
+            },
+        };
         self.thread_map.insert(pid, thread_ctrl);
 
         // Notify the executor
@@ -147,6 +172,16 @@ impl TracerCallback for TraceDebuggerCallback {
         Ok(pid)
     }
 
+}
+
+impl TracerCallback for TraceDebuggerCallback {
+
+    fn spawn_process(&mut self, program: String, prog_args: Vec<String>) -> Result<Pid, io::Error>
+    {
+        println!("Creating new tracing thread...");
+        self.start_thread(|tx, rx, barrier| TracingThread::new(program, prog_args, tx, rx, barrier))
+    }
+
     fn kill_process(&mut self, pid: Pid) -> Result<(), io::Error>
     {
         println!("* Kill process {:?} *", pid);
@@ -186,6 +221,82 @@ impl TracerCallback for TraceDebuggerCallback {
         Ok(())
     }
 
+    fn attach_process(&mut self, pid: Pid) -> Result<(), io::Error>
+    {
+        println!("* Attach process {:?} *", pid);
+        if self.thread_map.contains_key(&pid) {
+            return Err(io::Error::other("Process already traced"));
+        }
+
+        self.start_thread(|tx, rx, barrier| TracingThread::attach(pid, tx, rx, barrier))?;
+        Ok(())
+    }
+
+    fn detach_process(&mut self, pid: Pid) -> Result<(), io::Error>
+    {
+        println!("* Detach process {:?} *", pid);
+
+        let mut thread = match self.thread_map.remove(&pid) {
+            Some(thread) => thread,
+            None => {
+                println!("Error: No such process: {}", pid);
+                return Err(io::Error::other("No such pid"))
+            },
+        };
+
+        thread.tx.send(String::from("detach")).map_err(io::Error::other)?;
+        if !thread.tracing {
+            thread.barrier.wait();
+            thread.tracing = true;
+        }
+
+        // Interrupt the waitpid() of the tracing thread until it sees the request
+        while !thread.handler.is_finished() {
+            unsafe {
+                libc::pthread_kill(thread.handler.as_pthread_t(), libc::SIGUSR1);
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        thread.handler.join().map_err(|_| io::Error::other("Couldn't join the thread"))
+    }
+
+    fn flush_history(&mut self, pid: Pid, path: String) -> Result<(), io::Error>
+    {
+        println!("* Flush the syscall history of {:?} to {} *", pid, path);
+
+        let thread = match self.thread_map.get(&pid) {
+            Some(thread) if thread.tracing => thread,
+            Some(_) => return Err(io::Error::other("The tracing has not started")),
+            None => {
+                println!("Error: No such process: {}", pid);
+                return Err(io::Error::other("No such pid"))
+            },
+        };
+
+        thread.tx.send(format!("flush {}", path)).map_err(io::Error::other)?;
+
+        // Interrupt the waitpid() of the tracing thread until it replies
+        let reply = loop {
+            unsafe {
+                libc::pthread_kill(thread.handler.as_pthread_t(), libc::SIGUSR1);
+            }
+            match thread.rx.recv_timeout(Duration::from_millis(10)) {
+                Ok(reply) => break reply,
+                Err(RecvTimeoutError::Timeout) if !thread.handler.is_finished() => continue,
+                Err(err) => return Err(io::Error::other(err)),
+            }
+        };
+
+        match reply.strip_prefix("error: ") {
+            Some(err) => Err(io::Error::other(err.to_string())),
+            None => {
+                println!("{} syscalls flushed", reply);
+                Ok(())
+            },
+        }
+    }
+
     fn start_tracing(&mut self, pid: Pid) -> Result<(), io::Error>
     {
         println!("* Trace process {:?} *", pid);
@@ -194,6 +305,7 @@ impl TracerCallback for TraceDebuggerCallback {
             Some(thread) => {
                 println!("Waiting on boot barrier for {}", pid);
                 thread.barrier.wait();
+                thread.tracing = true;
             },
 
             None => {
@@ -230,6 +342,9 @@ impl TracerCallback for TraceDebuggerCallback {
 
 
 
+extern "C" fn interrupt_handler(_signo: i32) { }
+
+
 fn main()
 {
     /* TODO: add more argument to configure the tracer:
@@ -250,6 +365,12 @@ fn main()
     let prog_args = &args[2..];
      */
 
+    // SIGUSR1 only interrupts the waitpid() of the tracing threads
+    let action = SigAction::new(SigHandler::Handler(interrupt_handler), SaFlags::empty(), SigSet::empty());
+    unsafe {
+        sigaction(Signal::SIGUSR1, &action).expect("Fail to set the SIGUSR1 handler");
+    }
+
     let mut dbg = TraceDebugger::new();
 
 
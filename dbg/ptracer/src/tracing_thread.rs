@@ -3,7 +3,9 @@
  */
 
 use std::{
+    collections::HashSet,
     os::unix::process::CommandExt,
+    path::Path,
     process::{ self, Child, Command, Stdio },
     sync::{ 
         Arc, Barrier,
@@ -13,9 +15,10 @@ use std::{
 };
 
 use nix::{
+    errno::Errno,
     sys::{
         ptrace,
-        wait::{ waitpid, WaitStatus},
+        wait::{ waitpid, WaitPidFlag, WaitStatus },
         signal::Signal,
     },
     unistd::Pid,
@@ -25,16 +28,20 @@ use nix::{
 use libc;
 
 use sysfwd::{
+    Error,
     arch::TargetArch,
-    tracer::TracerEngine,
-    targets::operation::Operation,
-    targets,
+    tracer::{ TracerEngine, SeccompFilter, ENGINE_DECODED_SYSCALLS, filtering::Rule },
+    syscall::decoder::DecodePolicy,
+    targets::{ self, operation::Operation, ptrace::Ptrace },
+    protocol::data::SignalEvent,
+    trace::ReplayTransport,
     memory::{ read_process_memory_maps, print_memory_regions },
 };
 use sysfwd_filter::ForwardFileRule;
 
 use crate::{
-    IP_ADDRESS, TRACER_PORT, EXECUTOR_PORT,
+    IP_ADDRESS, TRACER_PORT, EXECUTOR_PORT, LOG_FORMAT, DECODE_LEVEL, USE_SECCOMP, UNWIND_METHOD,
+    HISTORY_CAPACITY, HISTORY_RAW_ONLY, TRACE_PATH, REPLAY_PATH, REPLAY_STRICTNESS,
 };
 
 
@@ -46,11 +53,15 @@ use crate::{
 pub struct TracingThread {
     pub boot_barrier: Arc<Barrier>,
     tx: Sender<String>,
-    _rx: Receiver<String>,
+    rx: Receiver<String>,
 
     program: String,
     prog_args: Vec<String>,
     tracee: Option<Child>,
+    seccomp: Option<SeccompFilter>,
+    /* The running process to attach to, instead of spawning the program, and its threads */
+    attach_pid: Option<Pid>,
+    attached_tids: Vec<Pid>,
 
     //use_pkexec: bool,
  }
@@ -63,22 +74,64 @@ impl TracingThread {
         TracingThread { 
             boot_barrier: barrier,
             tx: tx,
-            _rx: rx,
+            rx,
             program: program,
             prog_args: prog_args,
             tracee: None,
+            seccomp: None,
+            attach_pid: None,
+            attached_tids: Vec::new(),
             //use_pkexec: true,
         }
     }
+
+    pub fn attach(pid: Pid, tx: Sender<String>, rx: Receiver<String>, barrier: Arc<Barrier>) -> Self
+    {
+        let mut tracing_thread = Self::new(String::new(), Vec::new(), tx, rx, barrier);
+        tracing_thread.attach_pid = Some(pid);
+        tracing_thread
+    }
+
+    fn tracee_pid(&self) -> Pid
+    {
+        match self.attach_pid {
+            Some(pid) => pid,
+            None => Pid::from_raw(self.tracee.as_ref().unwrap().id() as i32),
+        }
+    }
+
+    /* The command line of an attached process is only known from /proc */
+    fn tracee_command(&self, pid: i32) -> Vec<String>
+    {
+        match self.attach_pid {
+            Some(_) => std::fs::read(format!("/proc/{}/cmdline", pid))
+                .map(|cmdline| cmdline.split(|&b| b == 0)
+                    .filter(|arg| !arg.is_empty())
+                    .map(|arg| String::from_utf8_lossy(arg).into_owned())
+                    .collect())
+                .unwrap_or_default(),
+            None => [self.program.clone()].into_iter().chain(self.prog_args.iter().cloned()).collect(),
+        }
+    }
     
     pub fn start(&mut self)
     {
-        let tracer = self.boot_thread().expect("Fail to setup tracing thread");
+        let mut tracer = match self.boot_thread() {
+            Ok(tracer) => tracer,
+            Err(err) => {
+                // The control thread waits for the PID of the tracee
+                eprintln!("Fail to setup tracing thread: {}", err);
+                let _ = self.tx.send(format!("error: {}", err));
+                return;
+            },
+        };
 
         // Wait for the signal to start the tracee execution and syscall tracing from the control thread.
         self.boot_barrier.wait();
 
-        let tracer = self.run_thread(tracer).unwrap();
+        if let Err(err) = self.run_thread(&mut tracer) {
+            eprintln!("The tracing of {} is aborted: {}", self.tracee_pid(), err);
+        }
 
         self.shutdown_thread(tracer).expect("Fail to properly clean tracing thread");
     }
@@ -90,17 +143,29 @@ impl TracingThread {
     {
         println!("Tracing thread {} booting...", process::id());
 
+        /* Load filters */
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(ForwardFileRule::new(String::from("/dev/kbuf"))),
+        ];
+        // a running process cannot be given a seccomp filter
+        if USE_SECCOMP && self.attach_pid.is_none() {
+            self.seccomp = self.compile_seccomp_filter(&rules);
+        }
+
         /* Setup the tracee */
-        self.spawn_tracee(self.program.clone(), self.prog_args.clone())?; 
-        let pid = self.tracee.as_ref().unwrap().id() as i32;
+        match self.attach_pid {
+            Some(pid) => self.attach_tracee(pid)?,
+            None => self.spawn_tracee(self.program.clone(), self.prog_args.clone())?,
+        }
+        let pid = self.tracee_pid().as_raw();
 
         let mem = read_process_memory_maps(pid as u32);
         print_memory_regions(&mem);
 
         /* Setup the tracer */
         let ptrace_op = targets::ptrace::Ptrace{ };
-        let regs_op = Box::new(ptrace_op.clone());
-        let mem_op = Box::new(ptrace_op);
+        let regs_op = Box::new(ptrace_op);
+        let mem_op = Box::new(targets::process_vm::ProcessVm{ });
         let operator = Box::new(Operation{ register: regs_op, memory: mem_op });
 
         let mut tracer = TracerEngine::new(pid,
@@ -110,10 +175,41 @@ impl TracingThread {
                                                          EXECUTOR_PORT,
                                                          operator,
                                                         );
+        tracer.set_log_format(LOG_FORMAT);
+        tracer.set_decode_policy(DecodePolicy::new(DECODE_LEVEL));
+        tracer.set_unwind_method(UNWIND_METHOD);
+        tracer.set_history(HISTORY_CAPACITY, HISTORY_RAW_ONLY);
+        if let Some(path) = REPLAY_PATH {
+            match ReplayTransport::open(Path::new(path), REPLAY_STRICTNESS) {
+                Ok(replay) => {
+                    println!("Replaying the forwarded syscalls from {}", path);
+                    tracer.set_transport(Box::new(replay));
+                },
+                Err(err) => eprintln!("Unable to replay the trace {}: {}", path, err),
+            }
+        }
+        if let Some(path) = TRACE_PATH {
+            let path = path.replace("{pid}", &pid.to_string());
+            match tracer.start_recording(Path::new(&path), self.tracee_command(pid)) {
+                Ok(()) => println!("Recording the syscalls of {} to {}", pid, path),
+                Err(err) => eprintln!("Unable to record the syscalls to {}: {}", path, err),
+            }
+        }
 
-        /* Load filters */
-        let rule = Box::new(ForwardFileRule::new(String::from("/dev/kbuf")));
-        tracer.load_rule(0, rule);
+        for (index, rule) in rules.into_iter().enumerate() {
+            tracer.load_rule(index, rule);
+        }
+
+        /* Rebuild the state of an attached process */
+        for tid in self.attached_tids.iter().filter(|tid| tid.as_raw() != pid) {
+            tracer.add_tracee(tid.as_raw(), pid);
+        }
+        if self.attach_pid.is_some() {
+            match tracer.adopt_open_fds() {
+                Ok(count) => println!("{} open fds of {} are forwarded", count, pid),
+                Err(err) => eprintln!("Unable to forward the open fds of {}: {}", pid, err),
+            }
+        }
         
         // Send the PID of the tracee to the control thread
         self.tx.send(pid.to_string()).unwrap();
@@ -121,6 +217,26 @@ impl TracingThread {
         Ok(tracer)
     }
 
+    /*
+     * Only the syscalls needed by the rules and the engine stop the tracee, unless a rule needs every syscall.
+     */
+    fn compile_seccomp_filter(&self, rules: &[Box<dyn Rule>]) -> Option<SeccompFilter>
+    {
+        let mut syscalls: Vec<&'static str> = rules.iter()
+            .map(|rule| rule.syscalls())
+            .collect::<Option<Vec<_>>>()?
+            .concat();
+        syscalls.extend(ENGINE_DECODED_SYSCALLS);
+
+        match SeccompFilter::new(TargetArch::X86_64, syscalls) {
+            Ok(filter) => Some(filter),
+            Err(err) => {
+                eprintln!("Tracing every syscall, no seccomp filter: {}", err);
+                None
+            },
+        }
+    }
+
     /*
      * Spawn the process where the tracee program will live.
      * Use PTRACE_TRACEME and waits for the tracer thread to initialize.
@@ -140,11 +256,16 @@ impl TracingThread {
         command.stdout(Stdio::inherit());
         command.stderr(Stdio::inherit());
 
+        let seccomp = self.seccomp.clone();
         unsafe {
-            command.pre_exec(|| {
+            command.pre_exec(move || {
                 ptrace::traceme().unwrap();
                 // Disable ASLR for the program
                 libc::personality(libc::ADDR_NO_RANDOMIZE.try_into().unwrap());
+                // Last, the filter would stop the syscalls above
+                if let Some(filter) = &seccomp {
+                    filter.install()?;
+                }
                 Ok(())
             });
         }
@@ -160,103 +281,339 @@ impl TracingThread {
         Ok(())
     }
 
-    fn _attach_tracee(&mut self, _pid: u32) -> Result<(), io::Error>
+    /*
+     * Seize every thread of a running process and interrupt them, so that its state can be rebuilt.
+     * The threads created meanwhile are seized until no new thread shows up.
+     */
+    fn attach_tracee(&mut self, pid: Pid) -> Result<(), io::Error>
+    {
+        println!("Attaching {}", pid);
+        let options = self.ptrace_options();
+
+        loop {
+            let tids: Vec<Pid> = std::fs::read_dir(format!("/proc/{}/task", pid))?
+                .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+                .map(Pid::from_raw)
+                .filter(|tid| !self.attached_tids.contains(tid))
+                .collect();
+            if tids.is_empty() {
+                break;
+            }
+
+            for tid in tids {
+                match ptrace::seize(tid, options) {
+                    Ok(()) => { },
+                    // the thread exited meanwhile
+                    Err(Errno::ESRCH) => continue,
+                    Err(err) => return Err(err.into()),
+                }
+                ptrace::interrupt(tid)?;
+                self.attached_tids.push(tid);
+            }
+        }
+
+        // Wait for the interruption of every thread
+        for tid in self.attached_tids.clone() {
+            match waitpid(tid, Some(WaitPidFlag::__WALL | WaitPidFlag::__WNOTHREAD))? {
+                WaitStatus::PtraceEvent(_, _, libc::PTRACE_EVENT_STOP) => { },
+                status => eprintln!("[{}] Unexpected stop when attaching: {:?}", tid, status),
+            }
+        }
+        Ok(())
+    }
+
+    /*
+     * Detach every thread, the tracee keeps running without the forwarded fds.
+     * The pending signals are delivered.
+     *
+     * A thread between the entry and the exit of a syscall reports its syscall-exit stop
+     * before the interruption: the engine finishes the syscall, e.g. the tracee gets
+     * its forwarded result, and the thread is detached at the syscall boundary.
+     */
+    fn detach_tracees(&mut self, tracer: &mut TracerEngine)
     {
-        /* TODO */
-        panic!("Not implemented");
+        let ptrace_op = Ptrace{ };
+        for tid in tracer.tracees() {
+            let tid = Pid::from_raw(tid);
+            if ptrace::interrupt(tid).is_err() {
+                continue;
+            }
+
+            let signal = match waitpid(tid, Some(WaitPidFlag::__WALL | WaitPidFlag::__WNOTHREAD)) {
+                Ok(WaitStatus::PtraceSyscall(_)) => {
+                    // a syscall entry is not traced, the syscall runs once detached
+                    tracer.select_tracee(tid.as_raw());
+                    if tracer.in_syscall() {
+                        if let Err(err) = self.trace_syscall(tid, &ptrace_op, tracer) {
+                            eprintln!("[{}] Unable to finish the syscall before detaching: {}", tid, err);
+                        }
+                    }
+                    None
+                },
+                Ok(WaitStatus::Stopped(_, signo)) => Some(signo),
+                Ok(WaitStatus::Exited(..)) | Ok(WaitStatus::Signaled(..)) | Err(_) => continue,
+                Ok(_) => None,
+            };
+            if let Err(err) = ptrace::detach(tid, signal) {
+                eprintln!("Fail to detach tracee {}: {}", tid, err);
+            }
+        }
+        tracer.detach();
+        println!("The tracee {} is detached", self.tracee_pid());
+    }
+
+    fn ptrace_options(&self) -> ptrace::Options
+    {
+        let options = ptrace::Options::PTRACE_O_TRACESYSGOOD
+                    | ptrace::Options::PTRACE_O_TRACECLONE
+                    | ptrace::Options::PTRACE_O_TRACEFORK
+                    | ptrace::Options::PTRACE_O_TRACEVFORK
+                    | ptrace::Options::PTRACE_O_TRACEEXEC;
+        match self.seccomp {
+            Some(_) => options | ptrace::Options::PTRACE_O_TRACESECCOMP,
+            None => options,
+        }
     }
 
     fn shutdown_thread(&mut self, mut tracer: TracerEngine) -> Result<(), io::Error>
     {
-        println!("Thread tracing process {} shutdown", self.tracee.as_ref().unwrap().id());
+        println!("Thread tracing process {} shutdown", self.tracee_pid());
         //let status = self.tracee.as_mut().unwrap().wait().expect("Not running");
         //println!("Tracee exits with status {}", status.code().unwrap());
-        tracer.shutdown().unwrap();
+        if let Err(err) = tracer.shutdown() {
+            eprintln!("Unable to shutdown the tracer: {}", err);
+        }
         Ok(())
     }
 
-    fn run_thread(&mut self, mut tracer: TracerEngine) -> Result<TracerEngine, io::Error>
+    fn run_thread(&mut self, tracer: &mut TracerEngine) -> Result<(), io::Error>
     {
-        let pid = Pid::from_raw(self.tracee.as_ref().unwrap().id() as i32);
+        let pid = self.tracee_pid();
+
+        // The first stop of a new thread or process is a PTRACE_EVENT_STOP, and not a group-stop
+        let mut started = HashSet::from([pid]);
+        let ptrace_op = Ptrace{ };
+        let mut restart = Some((pid, None));
+
+        match self.attach_pid {
+            Some(_) => {
+                // the threads are stopped since attach_tracee()
+                started.extend(self.attached_tids.iter().copied());
+                for tid in self.attached_tids.iter().filter(|&&tid| tid != pid) {
+                    self.restart_syscall(*tid, None, tracer);
+                }
+            },
+            None => self.seize_tracee(pid, self.ptrace_options())?,
+        }
+
         /*
          * The main loop
          */
         loop {
-            self.restart_syscall(pid).unwrap();
+            if let Some((tid, signal)) = restart.take() {
+                self.restart_syscall(tid, signal, tracer);
+            }
 
-            match self.wait_for_syscall(pid) {
-                Err(()) => break,
+            // The control thread interrupts waitpid() with SIGUSR1 to detach
+            if let Ok(request) = self.rx.try_recv() {
+                if request == "detach" {
+                    self.detach_tracees(tracer);
+                    break;
+                }
+                if let Some(path) = request.strip_prefix("flush ") {
+                    let reply = match tracer.flush_history(Path::new(path)) {
+                        Ok(count) => count.to_string(),
+                        Err(err) => format!("error: {}", err),
+                    };
+                    let _ = self.tx.send(reply);
+                }
+            }
+
+            // Only the tracees of this thread, the other tracing threads reap their own
+            let status = match waitpid(None, Some(WaitPidFlag::__WALL | WaitPidFlag::__WNOTHREAD)) {
+                Ok(status) => status,
+                Err(Errno::EINTR) => continue,
+                Err(Errno::ECHILD) => break,
+                Err(err) => {
+                    eprintln!("Oops something happens when waiting: {}", err);
+                    break;
+                },
+            };
 
-                Ok(pid) => { 
-                    self.sync_registers(pid, &mut tracer)?;
-                    tracer.trace()?;
+            match status {
+                WaitStatus::PtraceEvent(tid, _, libc::PTRACE_EVENT_STOP) if started.insert(tid) => {
+                    restart = Some((tid, None));
+                },
+                WaitStatus::PtraceSyscall(tid) | WaitStatus::PtraceEvent(tid, _, libc::PTRACE_EVENT_SECCOMP) => {
+                    match self.trace_syscall(tid, &ptrace_op, tracer) {
+                        Ok(()) => restart = Some((tid, None)),
+                        Err(Errno::ESRCH) => self.drop_tracee(tid, &mut started, tracer),
+                        Err(err) => return Err(err.into()),
+                    }
+                },
+                /*
+                 * The group-stop of a seized tracee: it stays stopped until SIGCONT,
+                 * while the tracer keeps being notified.
+                 */
+                WaitStatus::PtraceEvent(tid, signo, libc::PTRACE_EVENT_STOP) if is_stop_signal(signo) => {
+                    self.notify_signal(tid, signo, SignalEvent::GroupStop, tracer);
+                    self.listen_tracee(tid);
+                },
+                // A tracee resumed by SIGCONT or interrupted
+                WaitStatus::PtraceEvent(tid, _, libc::PTRACE_EVENT_STOP) => {
+                    restart = Some((tid, None));
+                },
+                WaitStatus::PtraceEvent(tid, _, event) => {
+                    self.handle_event(tid, event, tracer);
+                    restart = Some((tid, None));
+                },
+                // A signal-delivery-stop: the signal is injected back
+                WaitStatus::Stopped(tid, signo) => {
+                    if signo == Signal::SIGSEGV {
+                        match ptrace::getregs(tid) {
+                            Ok(regs) => println!("Tracee {} segfault at {:#x}", tid, regs.rip),
+                            Err(Errno::ESRCH) => {
+                                self.drop_tracee(tid, &mut started, tracer);
+                                continue;
+                            },
+                            Err(err) => return Err(err.into()),
+                        }
+                    }
+                    self.notify_signal(tid, signo, SignalEvent::Delivered, tracer);
+                    restart = Some((tid, Some(signo)));
+                },
+                WaitStatus::Exited(tid, exit_status) => {
+                    println!("The tracee {} exits with status {}", tid, exit_status);
+                    tracer.remove_tracee(tid.as_raw());
+                    started.remove(&tid);
                 },
+                WaitStatus::Signaled(tid, signo, _) => {
+                    println!("The tracee {} is terminated by signal {}", tid, signo);
+                    self.notify_signal(tid, signo, SignalEvent::Terminated, tracer);
+                    tracer.remove_tracee(tid.as_raw());
+                    started.remove(&tid);
+                },
+                // TODO: add support for other WaitStatus
+                _ => { },
             }
         }
-        Ok(tracer)
+        Ok(())
     }
 
-    fn sync_registers(&self, pid: Pid, tracer: &mut TracerEngine) -> Result<(), io::Error>
+    /*
+     * A tracee killed while stopped (e.g. SIGKILL by another thread) is gone before its stop is handled:
+     * the other tracees keep being traced, and its exit is ignored once reported.
+     */
+    fn drop_tracee(&self, tid: Pid, started: &mut HashSet<Pid>, tracer: &mut TracerEngine)
     {
-        let regs: nix::libc::user_regs_struct = ptrace::getregs(pid)?;
-        tracer.sync_registers(regs);
+        eprintln!("[{}] The tracee is gone", tid);
+        tracer.remove_tracee(tid.as_raw());
+        started.remove(&tid);
+    }
+
+    /*
+     * PTRACE_LISTEN and the PTRACE_EVENT_STOP of group-stops need PTRACE_SEIZE.
+     * The tracee stopped by its execve() since PTRACE_TRACEME is detached into a group-stop,
+     * and seized back before running any instruction.
+     */
+    fn seize_tracee(&self, pid: Pid, options: ptrace::Options) -> Result<(), io::Error>
+    {
+        waitpid(pid, None)?;
+        ptrace::detach(pid, Signal::SIGSTOP)?;
+        waitpid(pid, Some(WaitPidFlag::WUNTRACED))?;
+
+        ptrace::seize(pid, options)?;
+        // The group-stop is reported again to the new tracer
+        waitpid(pid, None)?;
         Ok(())
     }
 
-    fn restart_syscall(&self, pid: Pid) -> Result<Pid, ()>
+    fn listen_tracee(&self, pid: Pid)
     {
-        // Continue execution
-        //ptrace::syscall(pid, None).unwrap();
-        match ptrace::syscall(pid, None) {
-            Ok(()) => { /* continue */ },
-            /*
-            Err(ref err) if err.kind() == nix::errno::Errno::ESRCH => {
-                println!("ESRCH: No such process: {:?}", err);
-                return false;
-            }
-            */
-            Err(err) => {
-                panic!("Fail to restart tracee: {:?}", err);
-            }
+        let ret = unsafe { libc::ptrace(libc::PTRACE_LISTEN, pid.as_raw(), 0, 0) };
+        if ret < 0 {
+            eprintln!("Fail to listen tracee {}: {}", pid, Errno::last());
         }
-        Ok(pid)
     }
 
-    fn wait_for_syscall(&self, pid: Pid) -> Result<Pid, ()>
+    fn notify_signal(&self, pid: Pid, signo: Signal, event: SignalEvent, tracer: &mut TracerEngine)
     {
-        match waitpid(pid, None) {
+        if let Err(err) = tracer.notify_signal(pid.as_raw(), signo, event) {
+            eprintln!("[{}] Unable to notify the signal {}: {}", pid, signo, err);
+        }
+    }
+
+    fn trace_syscall(&self, tid: Pid, ptrace_op: &Ptrace, tracer: &mut TracerEngine) -> nix::Result<()>
+    {
+        tracer.select_tracee(tid.as_raw());
+        self.sync_registers(tid, tracer)?;
+
+        // the engine keeps tracing after an error, the syscall is only reported
+        let result = ptrace_op.get_syscall_info(tid.as_raw())
+            .map_err(Error::from)
+            .and_then(|stop| tracer.trace_stop(stop));
+        if let Err(err) = result {
+            eprintln!("[{}] Tracing error: {}", tid, err);
+        }
+        Ok(())
+    }
+
+    /*
+     * The tracee created a thread or a process, or called execve().
+     */
+    fn handle_event(&self, tid: Pid, event: i32, tracer: &mut TracerEngine)
+    {
+        let message = match ptrace::getevent(tid) {
+            Ok(message) => message as i32,
             Err(err) => {
-                panic!("Oops something happens when waiting: {}", err);
+                eprintln!("[{}] Unable to get the ptrace event message: {}", tid, err);
+                return;
             },
+        };
 
-            Ok(status) => {
-                match status {
-                    WaitStatus::Stopped(pid, signo) => {
-                        match signo {
-                            Signal::SIGTRAP => {
-                                return Ok(pid);
-                            },
-                            Signal::SIGSEGV => {
-                                let regs = ptrace::getregs(pid).unwrap();
-                                println!("Tracee {} segfault at {:#x}", pid, regs.rip);
-                                return Err(());
-                            },
-                            // TODO: add support for other signals
-                            _ => {
-                                panic!("Tracee {} received signal {} which is not handled", pid, signo);
-                            },
-                        }
-                    },
-                    WaitStatus::Exited(pid, exit_status) => {
-                        println!("The tracee {} exits with status {}", pid, exit_status);
-                        return Err(());
-                    },
-                    // TODO: add support for other WaitStatus
-                    _ => {
-                        panic!("WaitStatus not handled");
-                    },
-                }
+        match event {
+            libc::PTRACE_EVENT_CLONE | libc::PTRACE_EVENT_FORK | libc::PTRACE_EVENT_VFORK => {
+                // the options are inherited by the new tracee
+                tracer.add_tracee(message, tid.as_raw());
             },
+            libc::PTRACE_EVENT_EXEC => {
+                tracer.exec_tracee(tid.as_raw(), message);
+            },
+            _ => { },
         }
     }
+
+    fn sync_registers(&self, pid: Pid, tracer: &mut TracerEngine) -> nix::Result<()>
+    {
+        let regs: nix::libc::user_regs_struct = ptrace::getregs(pid)?;
+        tracer.sync_registers(regs);
+        Ok(())
+    }
+
+    /*
+     * With a seccomp filter, the next syscall entry is reported by a seccomp stop:
+     * the syscall-exit stop is only needed for the syscall being traced.
+     */
+    fn restart_syscall(&self, pid: Pid, signal: Option<Signal>, tracer: &mut TracerEngine)
+    {
+        tracer.select_tracee(pid.as_raw());
+        let result = match self.seccomp.is_some() && !tracer.in_syscall() {
+            true => ptrace::cont(pid, signal),
+            false => ptrace::syscall(pid, signal),
+        };
+
+        // Continue execution, a tracee killed meanwhile is reported by waitpid()
+        match result {
+            Ok(()) | Err(Errno::ESRCH) => { /* continue */ },
+            Err(err) => {
+                eprintln!("Fail to restart tracee {}: {:?}", pid, err);
+            }
+        }
+    }
+}
+
+
+fn is_stop_signal(signo: Signal) -> bool
+{
+    matches!(signo, Signal::SIGSTOP | Signal::SIGTSTP | Signal::SIGTTIN | Signal::SIGTTOU)
 }
@@ -6,9 +6,11 @@ mod x86_64;
 
 use std::collections::HashMap;
 
+use serde::{ Serialize, Deserialize };
 
 
-#[derive(Clone, Copy)]
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TargetArch {
     Arm,
     Aarch64,
@@ -22,6 +24,22 @@ pub enum TargetArch {
 }
 
 
+impl TargetArch {
+    /*
+     * The AUDIT_ARCH_* value identifying the syscall convention, as reported by seccomp and ptrace.
+     */
+    pub fn audit_arch(&self) -> Option<u32> {
+        match self {
+            TargetArch::Arm     => Some(0x40000028),
+            TargetArch::Aarch64 => Some(0xc00000b7),
+            TargetArch::X86     => Some(0x40000003),
+            TargetArch::X86_64  => Some(0xc000003e),
+            _ => None,
+        }
+    }
+}
+
+
 pub struct Architecture {
     pub name: TargetArch,
     //register_table: Register,
@@ -99,7 +117,7 @@ impl SyscallTable {
         }
     }
 
-    pub fn _get_syscall_no(&self, name: &str) -> Option<usize> {
+    pub fn get_syscall_no(&self, name: &str) -> Option<usize> {
         self.map.get(name).copied()
     }
 
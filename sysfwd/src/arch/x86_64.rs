@@ -338,9 +338,15 @@ pub fn create_syscall_table() -> HashMap<&'static str, usize> {
         ("preadv", 295),
         ("pwritev", 296),
         ("prlimit64", 302),
+        ("sched_setattr", 314),
+        ("sched_getattr", 315),
         ("getrandom", 318),
         ("statx", 332),
         ("rseq", 334),
+        ("io_uring_setup", 425),
+        ("io_uring_enter", 426),
+        ("io_uring_register", 427),
         ("epoll_pwait2", 441),
+        ("futex_waitv", 449),
     ])
 }
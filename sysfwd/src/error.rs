@@ -0,0 +1,96 @@
+/*
+ * The errors returned by the library.
+ *
+ * Most of them do not prevent the tracing to go on: the debugger decides whether to report them,
+ * to skip the syscall or to stop.
+ */
+use std::{ fmt, io };
+
+use crate::targets::operation::OperationError;
+
+
+
+#[derive(Debug)]
+pub enum Error {
+    /*
+     * The data channel between the tracer and the executor failed (e.g., timeout, socket closed)
+     */
+    Transport(io::Error),
+    /*
+     * The arguments of a syscall could not be decoded
+     */
+    Decode { syscall: String, source: io::Error },
+    /*
+     * A syscall could not be encoded back into registers and memory
+     */
+    Encode { syscall: String, source: io::Error },
+    /*
+     * A local file descriptor is not known on the remote side
+     */
+    FdTranslation { fd: usize },
+    /*
+     * A System V IPC identifier is not known on the remote side
+     */
+    IpcTranslation { id: usize },
+    /*
+     * An operation on the traced or executing process failed (registers, memory, syscall invocation).
+     * The OperationError, if any, is kept as the inner error.
+     */
+    Target(io::Error),
+    /*
+     * A message received does not follow the protocol (e.g., malformed packet or command)
+     */
+    Protocol(String),
+    /*
+     * A feature which is not supported yet (e.g., a decision)
+     */
+    Unsupported(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Transport(err) => write!(f, "transport error: {}", err),
+            Error::Decode { syscall, source } => write!(f, "cannot decode {}: {}", syscall, source),
+            Error::Encode { syscall, source } => write!(f, "cannot encode {}: {}", syscall, source),
+            Error::FdTranslation { fd } => write!(f, "fd {} is not forwarded", fd),
+            Error::IpcTranslation { id } => write!(f, "IPC identifier {} is not forwarded", id),
+            Error::Target(err) => write!(f, "target error: {}", err),
+            Error::Protocol(msg) => write!(f, "protocol error: {}", msg),
+            Error::Unsupported(msg) => write!(f, "not supported: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Transport(err) => Some(err),
+            Error::Decode { source, .. } | Error::Encode { source, .. } => Some(source),
+            Error::Target(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<OperationError> for Error {
+    fn from(err: OperationError) -> Self {
+        Error::Target(err.into())
+    }
+}
+
+/* For the interfaces still returning io::Error (e.g., TracerCallback) */
+impl From<Error> for io::Error {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::Transport(err) | Error::Target(err) => err,
+            Error::FdTranslation { .. } | Error::IpcTranslation { .. } => io::Error::new(io::ErrorKind::NotFound, err),
+            Error::Protocol(_) => io::Error::new(io::ErrorKind::InvalidData, err),
+            Error::Unsupported(_) => io::Error::new(io::ErrorKind::Unsupported, err),
+            _ => io::Error::other(err),
+        }
+    }
+}
@@ -2,18 +2,23 @@
  *
  */
 use std::{
+    collections::HashMap,
     io::{ self },
     sync::Arc,
 };
 
 use crate::{
+    error::{ Error, Result },
     sync::Event,
     arch::{ TargetArch, Architecture },
-    protocol::data::Server,
+    protocol::data::{ Server, DataMessage },
     syscall::{
         Syscall,
-        decoder::DecodeExit,
+        Timestamps,
+        args::{ Buffer, Direction },
+        decoder::{ DecodeArg, DecodedSyscall, DecodeExit },
         encoder::EncodeEntry,
+        syscalls::io_uring::{ self, IoUringRing },
     },
     targets::operation::Operation,
     executor::Invoker,
@@ -30,6 +35,11 @@ pub struct ExecutorEngine {
     operator: Box<Operation>,
     invoker: Box<dyn Invoker>,
 
+    /* The io_uring rings mirrored in the child, by remote fd */
+    uring_mirrors: HashMap<usize, IoUringRing>,
+    /* The buffers to return once their operation completes, by remote fd and user_data */
+    uring_pending: HashMap<(usize, u64), Buffer>,
+
     stop: Arc<Event>,
     stopped: Arc<Event>,
 }
@@ -57,6 +67,8 @@ impl ExecutorEngine {
             stopped: stopped_event,
             invoker: invoker,
             child_pid: child_pid,
+            uring_mirrors: HashMap::new(),
+            uring_pending: HashMap::new(),
         }
     }
 
@@ -78,9 +90,13 @@ impl ExecutorEngine {
              * For now, it needs to be stopped manually or via a signal.
              */
             //self.syscall = self.protocol.receive_syscall();
-            match self.protocol.receive_syscall() {
-                Ok(syscall) => {
-                    self.syscall = syscall;
+            match self.protocol.receive() {
+                Ok(DataMessage::Syscall(syscall)) => {
+                    self.syscall = *syscall;
+                },
+                Ok(DataMessage::Signal(signal)) => {
+                    println!("[{}] Signal {} {:?}", signal.pid, signal.signo, signal.event);
+                    continue;
                 },
                 /* Unix => WouldBlock ; Windows => TimedOut
                 Err(ref err) if err.kind() == io::ErrorKind::TimedOut => {
@@ -89,7 +105,7 @@ impl ExecutorEngine {
                 },
                 */
                 // The socket is set with a timeout of 1sec in order to check if the thread should stop.
-                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+                Err(Error::Transport(ref err)) if err.kind() == io::ErrorKind::WouldBlock => {
                     //eprintln!("Socket timeout: {:?}", err);
                     continue;
                 },
@@ -101,18 +117,27 @@ impl ExecutorEngine {
 
             /* Carry out syscall's decision */
             self.log_entry_syscall();
-            self.invoke_syscall().unwrap();
+            self.syscall.timestamps.executor_start = Timestamps::now();
+            if let Err(err) = self.invoke_syscall() {
+                // the tracer is still waiting for the syscall, it gets an I/O error
+                eprintln!("[{}] Unable to invoke the syscall {}: {}", self.child_pid, self.syscall.name, err);
+                self.syscall.raw.retval = -(nix::libc::EIO as isize) as usize;
+                self.syscall.decoded = None;
+            }
+            self.syscall.timestamps.executor_end = Timestamps::now();
             self.log_exit_syscall();
 
             /* Return syscall */
-            self.protocol.return_syscall_exit(&self.syscall);
+            if let Err(err) = self.protocol.return_syscall_exit(&self.syscall) {
+                eprintln!("Unable to return the syscall {}: {}", self.syscall.name, err);
+            }
         }
 
         self.stopped.set();
 
     }
 
-    fn invoke_syscall(&mut self) -> Result<(), io::Error>
+    fn invoke_syscall(&mut self) -> Result<()>
     {
         /* Encode the Syscall into a RawSyscall */
 
@@ -121,8 +146,10 @@ impl ExecutorEngine {
         //let raw = self.syscall.raw.clone();
         if let Some(decoded_sc) = self.syscall.decoded.as_mut() {
             let raw = self.syscall.raw.clone();
-            self.syscall.raw = decoded_sc.encode_entry(raw, self.child_pid, &self.operator).unwrap();
+            self.syscall.raw = decoded_sc.encode_entry(raw, self.child_pid, &self.operator)
+                .map_err(|source| Error::Encode { syscall: self.syscall.name.clone(), source })?;
         }
+        self.submit_io_uring().map_err(Error::Target)?;
 
         /* Invoke the syscall */
         let (retval, errno)= self.invoker.invoke_syscall(self.syscall.raw.no,
@@ -133,18 +160,103 @@ impl ExecutorEngine {
                                  self.syscall.raw.args[4],
                                  self.syscall.raw.args[5],
                                  self.syscall.raw.args[6])
-                                 .unwrap();
+                                 .map_err(Error::Target)?;
         self.syscall.raw.retval = retval;
         self.syscall.raw.errno = errno;
 
         /* Decode the syscall exit */
         if let Some(decoded_sc) = self.syscall.decoded.as_mut() {
-            decoded_sc.decode_exit(self.syscall.raw.retval, self.child_pid, &self.operator).unwrap();
+            decoded_sc.decode_exit(self.syscall.raw.retval, self.child_pid, &self.operator)
+                .map_err(|source| Error::Decode { syscall: self.syscall.name.clone(), source })?;
         }
+        self.complete_io_uring().map_err(Error::Target)?;
 
         Ok(())
     }
 
+    /*
+     * Push the SQEs sent by the tracer to the mirrored ring before io_uring_enter().
+     */
+    fn submit_io_uring(&mut self) -> io::Result<()>
+    {
+        if let Some(DecodedSyscall::IoUringEnter(sc)) = self.syscall.decoded.as_ref() {
+            if let Some(ring) = self.uring_mirrors.get(&sc.fd.value) {
+                ring.push_submissions(self.child_pid, &self.operator, &sc.sqes)?;
+                for sqe in sc.sqes.iter() {
+                    if let Some(buffer) = io_uring::sqe_buffer(sqe) {
+                        if matches!(buffer.direction, Direction::Out) {
+                            self.uring_pending.insert((sc.fd.value, sqe.user_data), buffer);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /*
+     * Keep the mirrored rings up to date and gather what the tracer needs to synchronize.
+     */
+    fn complete_io_uring(&mut self) -> io::Result<()>
+    {
+        let retval = self.syscall.raw.retval;
+        if (retval as isize) < 0 {
+            return Ok(());
+        }
+
+        match self.syscall.decoded.as_mut() {
+            Some(DecodedSyscall::IoUringSetup(sc)) => {
+                // map the rings in the child, as the process does after io_uring_setup()
+                if let Some(params) = sc.params.content {
+                    let mut ring = IoUringRing::new(params);
+                    for offset in ring.offsets() {
+                        let size = ring.mapping_size(offset);
+                        let prot = (nix::libc::PROT_READ | nix::libc::PROT_WRITE) as usize;
+                        let flags = (nix::libc::MAP_SHARED | nix::libc::MAP_POPULATE) as usize;
+                        let (address, _) = self.invoker.invoke_syscall(9, 0, size, prot, flags, retval, offset, 0)?;
+                        if (address as isize) < 0 {
+                            return Err(io::Error::from_raw_os_error(-(address as isize) as i32));
+                        }
+                        ring.map(offset, address);
+                    }
+                    self.uring_mirrors.insert(retval, ring);
+                }
+            },
+            Some(DecodedSyscall::IoUringEnter(sc)) => {
+                if let Some(ring) = self.uring_mirrors.get(&sc.fd.value) {
+                    sc.cqes = ring.pop_completions(self.child_pid, &self.operator)?;
+                    sc.buffers.clear();
+                    for cqe in sc.cqes.iter() {
+                        if let Some(mut buffer) = self.uring_pending.remove(&(sc.fd.value, cqe.user_data)) {
+                            if cqe.res > 0 {
+                                buffer.size = buffer.size.min(cqe.res as usize);
+                                buffer.decode(self.child_pid, &self.operator)?;
+                                sc.buffers.push(buffer);
+                            }
+                        }
+                    }
+                }
+            },
+            Some(DecodedSyscall::Close(sc)) => {
+                if let Some(ring) = self.uring_mirrors.remove(&sc.fd.value) {
+                    for offset in ring.offsets() {
+                        let address = match offset {
+                            io_uring::IORING_OFF_SQ_RING => ring.sq_ring,
+                            io_uring::IORING_OFF_CQ_RING => ring.cq_ring,
+                            _ => ring.sqes,
+                        };
+                        if let Some(address) = address {
+                            self.invoker.invoke_syscall(11, address, ring.mapping_size(offset), 0, 0, 0, 0, 0)?;
+                        }
+                    }
+                    self.uring_pending.retain(|(fd, _), _| *fd != sc.fd.value);
+                }
+            },
+            _ => (),
+        };
+        Ok(())
+    }
+
     pub fn shutdown(&mut self)
     {
         if ! self.stopped.is_set() {
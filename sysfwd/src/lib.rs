@@ -2,6 +2,7 @@
  * The lib API
  */
 
+pub mod error;
 pub mod sync;
 pub mod arch;
 pub mod protocol; // Should be private?
@@ -12,6 +13,7 @@ pub mod tracer;
 pub mod executor;
 //pub mod migration;
 pub mod targets;
+pub mod trace;
 
 
 /*
@@ -19,4 +21,5 @@ pub mod targets;
  */
 pub use crate::tracer::TracerEngine;
 pub use crate::executor::ExecutorEngine;
+pub use crate::error::{ Error, Result };
 
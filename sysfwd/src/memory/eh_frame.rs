@@ -0,0 +1,691 @@
+/*
+ * The call frame information of .eh_frame (DWARF CFI, as extended by the LSB),
+ * which tells where a function saved the registers of its caller at a given address.
+ *
+ * Only the rules needed to go up the stack are kept: the CFA, the return address and the
+ * registers used as frame pointer. The DWARF expressions are not evaluated.
+ */
+use std::{
+    collections::HashMap,
+    io,
+};
+
+
+
+const DW_EH_PE_OMIT: u8 = 0xff;
+const DW_EH_PE_PCREL: u8 = 0x10;
+
+/* The rules of the registers, by DWARF register number */
+type Rules = HashMap<u16, Rule>;
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rule {
+    /* The register is saved at CFA + offset */
+    Offset(i64),
+    /* The register is CFA + offset */
+    ValOffset(i64),
+    /* The register is in another register */
+    Register(u16),
+    SameValue,
+    Undefined,
+    /* A DWARF expression, not supported */
+    Expression,
+}
+
+/*
+ * The Canonical Frame Address, the value of the stack pointer in the caller before the call,
+ * is computed from a register.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cfa {
+    RegisterOffset { register: u16, offset: i64 },
+    Expression,
+}
+
+#[derive(Clone, Debug)]
+pub struct Row {
+    pub cfa: Cfa,
+    pub rules: Rules,
+    /* The column of the return address */
+    pub return_address: u16,
+}
+
+impl Row {
+    pub fn rule(&self, register: u16) -> Rule {
+        self.rules.get(&register).copied().unwrap_or(Rule::SameValue)
+    }
+}
+
+
+#[derive(Clone, Debug)]
+struct Cie {
+    code_alignment: u64,
+    data_alignment: i64,
+    return_address: u16,
+    fde_encoding: u8,
+    /* The FDEs have augmentation data */
+    augmented: bool,
+    instructions: (usize, usize),
+}
+
+#[derive(Clone, Debug)]
+struct Fde {
+    begin: u64,
+    end: u64,
+    cie: usize,
+    instructions: (usize, usize),
+}
+
+
+pub struct EhFrame {
+    /* The virtual address of the section, for the pc-relative pointers */
+    address: u64,
+    data: Vec<u8>,
+    cies: HashMap<usize, Cie>,
+    /* Sorted by address */
+    fdes: Vec<Fde>,
+}
+
+impl EhFrame {
+
+    pub fn parse(address: u64, data: &[u8]) -> io::Result<Self>
+    {
+        let mut eh_frame = EhFrame {
+            address,
+            data: data.to_vec(),
+            cies: HashMap::new(),
+            fdes: Vec::new(),
+        };
+
+        let mut offset = 0;
+        while offset + 4 <= data.len() {
+            let mut reader = Reader::new(data, address, offset);
+            let mut length = reader.u32()? as u64;
+            if length == 0 {
+                break;
+            }
+            if length == 0xffff_ffff {
+                length = reader.u64()?;
+            }
+            let id_offset = reader.position;
+            let end = id_offset.checked_add(length as usize)
+                .filter(|&end| end <= data.len())
+                .ok_or_else(|| invalid("truncated .eh_frame entry"))?;
+
+            let id = reader.u32()?;
+            match id {
+                0 => {
+                    let cie = Self::parse_cie(&mut reader, end)?;
+                    eh_frame.cies.insert(offset, cie);
+                },
+                // the id of a FDE is the distance back to its CIE
+                _ => {
+                    let cie_offset = id_offset.checked_sub(id as usize)
+                        .ok_or_else(|| invalid("invalid CIE pointer"))?;
+                    // the CIE always comes first
+                    let cie = eh_frame.cies.get(&cie_offset)
+                        .ok_or_else(|| invalid("unknown CIE"))?;
+                    let fde = Self::parse_fde(&mut reader, cie, cie_offset, end)?;
+                    eh_frame.fdes.push(fde);
+                },
+            }
+            offset = end;
+        }
+        eh_frame.fdes.sort_by_key(|fde| fde.begin);
+
+        Ok(eh_frame)
+    }
+
+    fn parse_cie(reader: &mut Reader, end: usize) -> io::Result<Cie>
+    {
+        let version = reader.u8()?;
+        let augmentation = reader.string()?;
+        if augmentation.contains("eh") {
+            reader.u64()?;
+        }
+        let code_alignment = reader.uleb128()?;
+        let data_alignment = reader.sleb128()?;
+        let return_address = match version {
+            1 => reader.u8()? as u16,
+            _ => reader.uleb128()? as u16,
+        };
+
+        let mut fde_encoding = 0;
+        if augmentation.starts_with('z') {
+            let length = reader.uleb128()? as usize;
+            let data_end = reader.position.checked_add(length)
+                .ok_or_else(|| invalid("truncated .eh_frame"))?;
+            for c in augmentation.chars().skip(1) {
+                match c {
+                    'R' => fde_encoding = reader.u8()?,
+                    'L' => { reader.u8()?; },
+                    'P' => {
+                        let encoding = reader.u8()?;
+                        reader.pointer(encoding)?;
+                    },
+                    _ => (),
+                }
+            }
+            reader.position = data_end;
+        }
+
+        Ok(Cie {
+            code_alignment,
+            data_alignment,
+            return_address,
+            fde_encoding,
+            augmented: augmentation.starts_with('z'),
+            instructions: (reader.position, end),
+        })
+    }
+
+    fn parse_fde(reader: &mut Reader, cie: &Cie, cie_offset: usize, end: usize) -> io::Result<Fde>
+    {
+        let begin = reader.pointer(cie.fde_encoding)?;
+        // the range is only a size, it is never relative
+        let range = reader.pointer(cie.fde_encoding & 0x0f)?;
+        // the augmentation data of the FDE (e.g. the LSDA) is not used
+        if cie.augmented {
+            let length = reader.uleb128()? as usize;
+            reader.skip(length)?;
+        }
+
+        Ok(Fde {
+            begin,
+            end: begin.wrapping_add(range),
+            cie: cie_offset,
+            instructions: (reader.position, end),
+        })
+    }
+
+    /*
+     * The rules at the virtual address, if a FDE covers it.
+     */
+    pub fn find_row(&self, vaddr: u64) -> Option<Row>
+    {
+        let fde = self.find_fde(vaddr)?;
+        let cie = self.cies.get(&fde.cie)?;
+
+        let initial = Row {
+            cfa: Cfa::RegisterOffset { register: 0, offset: 0 },
+            rules: Rules::new(),
+            return_address: cie.return_address,
+        };
+        let initial = self.execute(cie, cie.instructions, initial, None, 0, u64::MAX).ok()?;
+        self.execute(cie, fde.instructions, initial.clone(), Some(&initial), fde.begin, vaddr).ok()
+    }
+
+    fn find_fde(&self, vaddr: u64) -> Option<&Fde>
+    {
+        let index = self.fdes.partition_point(|fde| fde.begin <= vaddr);
+        let fde = self.fdes.get(index.checked_sub(1)?)?;
+        (vaddr < fde.end).then_some(fde)
+    }
+
+    /*
+     * Run the CFA instructions until the location goes beyond the target address.
+     */
+    fn execute(&self, cie: &Cie, instructions: (usize, usize), mut row: Row, initial: Option<&Row>,
+               mut location: u64, target: u64) -> io::Result<Row>
+    {
+        let (start, end) = instructions;
+        let mut reader = Reader::new(&self.data[..end], self.address, start);
+        let mut stack: Vec<Row> = Vec::new();
+
+        while reader.position < end {
+            let opcode = reader.u8()?;
+            let operand = opcode & 0x3f;
+
+            let advance = match opcode >> 6 {
+                0x1 => Some(code_offset(operand as u64, cie)?),
+                0x2 => {
+                    let offset = data_offset(reader.uleb128()? as i64, cie)?;
+                    row.rules.insert(operand as u16, Rule::Offset(offset));
+                    None
+                },
+                0x3 => {
+                    Self::restore(&mut row, initial, operand as u16);
+                    None
+                },
+                _ => match opcode {
+                    0x00 => None,
+                    0x01 => {
+                        let address = reader.pointer(cie.fde_encoding)?;
+                        if address > target {
+                            break;
+                        }
+                        location = address;
+                        None
+                    },
+                    0x02 => Some(code_offset(reader.u8()? as u64, cie)?),
+                    0x03 => Some(code_offset(reader.u16()? as u64, cie)?),
+                    0x04 => Some(code_offset(reader.u32()? as u64, cie)?),
+                    0x05 => {
+                        let register = reader.uleb128()? as u16;
+                        let offset = data_offset(reader.uleb128()? as i64, cie)?;
+                        row.rules.insert(register, Rule::Offset(offset));
+                        None
+                    },
+                    0x06 => {
+                        let register = reader.uleb128()? as u16;
+                        Self::restore(&mut row, initial, register);
+                        None
+                    },
+                    0x07 => {
+                        row.rules.insert(reader.uleb128()? as u16, Rule::Undefined);
+                        None
+                    },
+                    0x08 => {
+                        row.rules.insert(reader.uleb128()? as u16, Rule::SameValue);
+                        None
+                    },
+                    0x09 => {
+                        let register = reader.uleb128()? as u16;
+                        let other = reader.uleb128()? as u16;
+                        row.rules.insert(register, Rule::Register(other));
+                        None
+                    },
+                    0x0a => {
+                        stack.push(row.clone());
+                        None
+                    },
+                    // the CFA is part of the saved state, as the compilers expect
+                    0x0b => {
+                        row = stack.pop().ok_or_else(|| invalid("restore without remember"))?;
+                        None
+                    },
+                    0x0c => {
+                        let register = reader.uleb128()? as u16;
+                        let offset = reader.uleb128()? as i64;
+                        row.cfa = Cfa::RegisterOffset { register, offset };
+                        None
+                    },
+                    0x0d => {
+                        let register = reader.uleb128()? as u16;
+                        if let Cfa::RegisterOffset { offset, .. } = row.cfa {
+                            row.cfa = Cfa::RegisterOffset { register, offset };
+                        }
+                        None
+                    },
+                    0x0e => {
+                        let offset = reader.uleb128()? as i64;
+                        if let Cfa::RegisterOffset { register, .. } = row.cfa {
+                            row.cfa = Cfa::RegisterOffset { register, offset };
+                        }
+                        None
+                    },
+                    0x0f => {
+                        let length = reader.uleb128()? as usize;
+                        reader.skip(length)?;
+                        row.cfa = Cfa::Expression;
+                        None
+                    },
+                    0x10 | 0x16 => {
+                        let register = reader.uleb128()? as u16;
+                        let length = reader.uleb128()? as usize;
+                        reader.skip(length)?;
+                        row.rules.insert(register, Rule::Expression);
+                        None
+                    },
+                    0x11 => {
+                        let register = reader.uleb128()? as u16;
+                        let offset = data_offset(reader.sleb128()?, cie)?;
+                        row.rules.insert(register, Rule::Offset(offset));
+                        None
+                    },
+                    0x12 => {
+                        let register = reader.uleb128()? as u16;
+                        let offset = data_offset(reader.sleb128()?, cie)?;
+                        row.cfa = Cfa::RegisterOffset { register, offset };
+                        None
+                    },
+                    0x13 => {
+                        let offset = data_offset(reader.sleb128()?, cie)?;
+                        if let Cfa::RegisterOffset { register, .. } = row.cfa {
+                            row.cfa = Cfa::RegisterOffset { register, offset };
+                        }
+                        None
+                    },
+                    0x14 => {
+                        let register = reader.uleb128()? as u16;
+                        let offset = data_offset(reader.uleb128()? as i64, cie)?;
+                        row.rules.insert(register, Rule::ValOffset(offset));
+                        None
+                    },
+                    0x15 => {
+                        let register = reader.uleb128()? as u16;
+                        let offset = data_offset(reader.sleb128()?, cie)?;
+                        row.rules.insert(register, Rule::ValOffset(offset));
+                        None
+                    },
+                    // DW_CFA_GNU_args_size
+                    0x2e => {
+                        reader.uleb128()?;
+                        None
+                    },
+                    // DW_CFA_GNU_negative_offset_extended
+                    0x2f => {
+                        let register = reader.uleb128()? as u16;
+                        let offset = data_offset((reader.uleb128()? as i64).wrapping_neg(), cie)?;
+                        row.rules.insert(register, Rule::Offset(offset));
+                        None
+                    },
+                    _ => return Err(invalid("unknown CFA instruction")),
+                },
+            };
+
+            if let Some(delta) = advance {
+                // past the end of the address space is past the target too
+                location = match location.checked_add(delta) {
+                    Some(location) if location <= target => location,
+                    _ => break,
+                };
+            }
+        }
+
+        Ok(row)
+    }
+
+    fn restore(row: &mut Row, initial: Option<&Row>, register: u16)
+    {
+        match initial.and_then(|initial| initial.rules.get(&register)) {
+            Some(rule) => { row.rules.insert(register, *rule); },
+            None => { row.rules.remove(&register); },
+        }
+    }
+}
+
+
+/*
+ * A cursor on the section, which knows the address of what it reads
+ * for the pc-relative pointers.
+ */
+struct Reader<'a> {
+    data: &'a [u8],
+    address: u64,
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+
+    fn new(data: &'a [u8], address: u64, position: usize) -> Self
+    {
+        Reader { data, address, position }
+    }
+
+    fn bytes(&mut self, size: usize) -> io::Result<&'a [u8]>
+    {
+        let end = self.position.checked_add(size)
+            .ok_or_else(|| invalid("truncated .eh_frame"))?;
+        let bytes = self.data.get(self.position..end)
+            .ok_or_else(|| invalid("truncated .eh_frame"))?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    /* The end of the data is checked by the next read */
+    fn skip(&mut self, size: usize) -> io::Result<()>
+    {
+        self.position = self.position.checked_add(size)
+            .ok_or_else(|| invalid("truncated .eh_frame"))?;
+        Ok(())
+    }
+
+    fn u8(&mut self) -> io::Result<u8>
+    {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> io::Result<u16>
+    {
+        Ok(u16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> io::Result<u32>
+    {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> io::Result<u64>
+    {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> io::Result<String>
+    {
+        let bytes = &self.data[self.position.min(self.data.len())..];
+        let end = bytes.iter().position(|&b| b == 0)
+            .ok_or_else(|| invalid("unterminated string"))?;
+        self.position += end + 1;
+        Ok(String::from_utf8_lossy(&bytes[..end]).into_owned())
+    }
+
+    fn uleb128(&mut self) -> io::Result<u64>
+    {
+        let mut value = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.u8()?;
+            if shift < 64 {
+                value |= ((byte & 0x7f) as u64) << shift;
+            }
+            shift = (shift + 7).min(64);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn sleb128(&mut self) -> io::Result<i64>
+    {
+        let mut value: i64 = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.u8()?;
+            if shift < 64 {
+                value |= ((byte & 0x7f) as i64) << shift;
+            }
+            shift = (shift + 7).min(64);
+            if byte & 0x80 == 0 {
+                if shift < 64 && byte & 0x40 != 0 {
+                    value |= -1 << shift;
+                }
+                return Ok(value);
+            }
+        }
+    }
+
+    /* A pointer encoded with DW_EH_PE_*, only the absolute and pc-relative ones */
+    fn pointer(&mut self, encoding: u8) -> io::Result<u64>
+    {
+        if encoding == DW_EH_PE_OMIT {
+            return Ok(0);
+        }
+        let address = self.address.wrapping_add(self.position as u64);
+
+        let value = match encoding & 0x0f {
+            0x00 | 0x04 => self.u64()?,
+            0x01 => self.uleb128()?,
+            0x02 => self.u16()? as u64,
+            0x03 => self.u32()? as u64,
+            0x09 => self.sleb128()? as u64,
+            0x0a => self.u16()? as i16 as i64 as u64,
+            0x0b => self.u32()? as i32 as i64 as u64,
+            0x0c => self.u64()?,
+            _ => return Err(invalid("unknown pointer encoding")),
+        };
+
+        match encoding & 0x70 {
+            0 => Ok(value),
+            DW_EH_PE_PCREL => Ok(address.wrapping_add(value)),
+            _ => Err(invalid("unsupported pointer encoding")),
+        }
+    }
+}
+
+
+/* The factored offsets of the instructions */
+fn code_offset(delta: u64, cie: &Cie) -> io::Result<u64>
+{
+    delta.checked_mul(cie.code_alignment).ok_or_else(|| invalid("code offset overflow"))
+}
+
+fn data_offset(offset: i64, cie: &Cie) -> io::Result<i64>
+{
+    offset.checked_mul(cie.data_alignment).ok_or_else(|| invalid("data offset overflow"))
+}
+
+fn invalid(msg: &str) -> io::Error
+{
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECTION_ADDRESS: u64 = 0x2000;
+    const FUNCTION: u64 = 0x1000;
+    const FUNCTION_SIZE: u32 = 0x20;
+
+    const RSP: u16 = 7;
+    const RBP: u16 = 6;
+    const RA: u16 = 16;
+
+    /* Append an entry, its length is patched once its content is known */
+    fn entry(data: &mut Vec<u8>, content: impl FnOnce(&mut Vec<u8>))
+    {
+        let start = data.len();
+        data.extend_from_slice(&[0; 4]);
+        content(data);
+        while !(data.len() - start).is_multiple_of(4) {
+            data.push(0x00);    // DW_CFA_nop
+        }
+        let length = (data.len() - start - 4) as u32;
+        data[start..start + 4].copy_from_slice(&length.to_le_bytes());
+    }
+
+    /*
+     * The usual CIE and FDE of a function with a frame pointer:
+     * push %rbp at +0, mov %rsp,%rbp at +1, the frame is set up at +4.
+     */
+    fn eh_frame() -> (Vec<u8>, usize)
+    {
+        let mut data = Vec::new();
+        entry(&mut data, |data| {
+            data.extend_from_slice(&0u32.to_le_bytes());
+            data.push(1);                               // version
+            data.extend_from_slice(b"zR\0");
+            data.push(1);                               // code alignment
+            data.push(0x78);                            // data alignment, -8
+            data.push(RA as u8);
+            data.push(1);                               // augmentation length
+            data.push(0x1b);                            // DW_EH_PE_pcrel | DW_EH_PE_sdata4
+            data.extend_from_slice(&[0x0c, RSP as u8, 8]);    // DW_CFA_def_cfa rsp+8
+            data.extend_from_slice(&[0x80 | RA as u8, 1]);    // DW_CFA_offset ra at cfa-8
+        });
+        let fde = data.len();
+        entry(&mut data, |data| {
+            // the distance back to the CIE, at the start of the section
+            data.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            let begin = FUNCTION as i64 - (SECTION_ADDRESS + data.len() as u64) as i64;
+            data.extend_from_slice(&(begin as i32).to_le_bytes());
+            data.extend_from_slice(&FUNCTION_SIZE.to_le_bytes());
+            data.push(0);                               // augmentation length
+            data.push(0x41);                            // DW_CFA_advance_loc 1
+            data.extend_from_slice(&[0x0e, 16]);        // DW_CFA_def_cfa_offset 16
+            data.extend_from_slice(&[0x80 | RBP as u8, 2]);   // DW_CFA_offset rbp at cfa-16
+            data.push(0x43);                            // DW_CFA_advance_loc 3
+            data.extend_from_slice(&[0x0d, RBP as u8]); // DW_CFA_def_cfa_register rbp
+        });
+        (data, fde)
+    }
+
+    #[test]
+    fn rows_of_a_function_with_a_frame_pointer()
+    {
+        let eh_frame = EhFrame::parse(SECTION_ADDRESS, &eh_frame().0).unwrap();
+
+        let row = eh_frame.find_row(FUNCTION).unwrap();
+        assert_eq!(row.cfa, Cfa::RegisterOffset { register: RSP, offset: 8 });
+        assert_eq!(row.return_address, RA);
+        assert_eq!(row.rule(RA), Rule::Offset(-8));
+        assert_eq!(row.rule(RBP), Rule::SameValue);
+
+        let row = eh_frame.find_row(FUNCTION + 1).unwrap();
+        assert_eq!(row.cfa, Cfa::RegisterOffset { register: RSP, offset: 16 });
+        assert_eq!(row.rule(RBP), Rule::Offset(-16));
+
+        for vaddr in [FUNCTION + 4, FUNCTION + FUNCTION_SIZE as u64 - 1] {
+            let row = eh_frame.find_row(vaddr).unwrap();
+            assert_eq!(row.cfa, Cfa::RegisterOffset { register: RBP, offset: 16 });
+            assert_eq!(row.rule(RBP), Rule::Offset(-16));
+            assert_eq!(row.rule(RA), Rule::Offset(-8));
+        }
+
+        assert!(eh_frame.find_row(FUNCTION - 1).is_none());
+        assert!(eh_frame.find_row(FUNCTION + FUNCTION_SIZE as u64).is_none());
+    }
+
+    #[test]
+    fn truncated_sections_are_rejected()
+    {
+        let (data, fde) = eh_frame();
+        // the section ends in the middle of an entry
+        for length in (4..fde).chain(fde + 4..data.len()) {
+            assert!(EhFrame::parse(SECTION_ADDRESS, &data[..length]).is_err(), "length {}", length);
+        }
+        // only the CIE is complete
+        let eh_frame = EhFrame::parse(SECTION_ADDRESS, &data[..fde]).unwrap();
+        assert!(eh_frame.find_row(FUNCTION).is_none());
+    }
+
+    #[test]
+    fn overflowing_lengths_are_rejected()
+    {
+        // a 64-bit length up to the end of the address space
+        let mut data = vec![0xff; 4];
+        data.extend_from_slice(&u64::MAX.to_le_bytes());
+        data.extend_from_slice(&[0; 8]);
+        assert!(EhFrame::parse(SECTION_ADDRESS, &data).is_err());
+
+        // an augmentation of the FDE as long as the address space
+        let (mut data, fde) = eh_frame();
+        let augmentation = fde + 16;
+        assert_eq!(data[augmentation], 0);
+        data.splice(augmentation..augmentation + 1, [0xff; 9].into_iter().chain([0x01]));
+        let length = (data.len() - fde - 4) as u32;
+        data[fde..fde + 4].copy_from_slice(&length.to_le_bytes());
+        assert!(EhFrame::parse(SECTION_ADDRESS, &data).is_err());
+    }
+
+    #[test]
+    fn overflowing_offsets_are_not_unwound()
+    {
+        // a data alignment of -2^62, the offset of the return address does not fit
+        let mut data = Vec::new();
+        entry(&mut data, |data| {
+            data.extend_from_slice(&0u32.to_le_bytes());
+            data.push(1);
+            data.extend_from_slice(b"zR\0");
+            data.push(1);
+            data.extend_from_slice(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x40]);
+            data.push(RA as u8);
+            data.push(1);
+            data.push(0x03);                            // DW_EH_PE_udata4
+            data.extend_from_slice(&[0x80 | RA as u8, 4]);
+        });
+        let cie_length = data.len() as u32;
+        entry(&mut data, |data| {
+            data.extend_from_slice(&(cie_length + 4).to_le_bytes());
+            data.extend_from_slice(&(FUNCTION as u32).to_le_bytes());
+            data.extend_from_slice(&FUNCTION_SIZE.to_le_bytes());
+            data.push(0);
+        });
+
+        let eh_frame = EhFrame::parse(SECTION_ADDRESS, &data).unwrap();
+        assert!(eh_frame.find_row(FUNCTION).is_none());
+    }
+}
@@ -0,0 +1,376 @@
+/*
+ * A minimal reader of the ELF64 little-endian files mapped in the tracee:
+ * only what the unwinder needs, the loadable segments, the function symbols and .eh_frame.
+ */
+use std::{
+    fs,
+    io,
+};
+
+use crate::memory::eh_frame::EhFrame;
+
+
+
+const ELF_MAGIC: &[u8] = b"\x7fELF";
+const ELFCLASS64: u8 = 2;
+const ELFDATA2LSB: u8 = 1;
+
+const PT_LOAD: u32 = 1;
+
+const SHT_SYMTAB: u32 = 2;
+const SHT_DYNSYM: u32 = 11;
+const SHT_NOBITS: u32 = 8;
+
+const STT_FUNC: u8 = 2;
+
+const PHDR_SIZE: usize = 56;
+const SHDR_SIZE: usize = 64;
+const SYM_SIZE: usize = 24;
+
+
+#[derive(Clone, Debug)]
+struct Segment {
+    offset: u64,
+    vaddr: u64,
+    filesz: u64,
+}
+
+#[derive(Clone, Debug)]
+struct Symbol {
+    address: u64,
+    size: u64,
+    name: String,
+}
+
+struct Section {
+    name: u32,
+    kind: u32,
+    address: u64,
+    offset: u64,
+    size: u64,
+    link: u32,
+}
+
+
+pub struct ElfFile {
+    segments: Vec<Segment>,
+    /* The function symbols, sorted by address */
+    symbols: Vec<Symbol>,
+    pub eh_frame: Option<EhFrame>,
+}
+
+impl ElfFile {
+
+    pub fn open(path: &str) -> io::Result<Self>
+    {
+        let data = fs::read(path)?;
+        Self::parse(&data)
+    }
+
+    pub fn parse(data: &[u8]) -> io::Result<Self>
+    {
+        if data.len() < 64 || &data[..4] != ELF_MAGIC || data[4] != ELFCLASS64 || data[5] != ELFDATA2LSB {
+            return Err(invalid("not an ELF64 little-endian file"));
+        }
+
+        let phoff = read_u64(data, 0x20)? as usize;
+        let shoff = read_u64(data, 0x28)? as usize;
+        let phnum = read_u16(data, 0x38)? as usize;
+        let shnum = read_u16(data, 0x3c)? as usize;
+        let shstrndx = read_u16(data, 0x3e)? as usize;
+
+        let mut segments = Vec::new();
+        for i in 0..phnum {
+            let phdr = table_entry(phoff, i, PHDR_SIZE)?;
+            if read_u32(data, phdr)? == PT_LOAD {
+                segments.push(Segment {
+                    offset: read_u64(data, phdr + 0x08)?,
+                    vaddr: read_u64(data, phdr + 0x10)?,
+                    filesz: read_u64(data, phdr + 0x20)?,
+                });
+            }
+        }
+
+        let mut sections = Vec::with_capacity(shnum);
+        for i in 0..shnum {
+            let shdr = table_entry(shoff, i, SHDR_SIZE)?;
+            sections.push(Section {
+                name: read_u32(data, shdr)?,
+                kind: read_u32(data, shdr + 0x04)?,
+                address: read_u64(data, shdr + 0x10)?,
+                offset: read_u64(data, shdr + 0x18)?,
+                size: read_u64(data, shdr + 0x20)?,
+                link: read_u32(data, shdr + 0x28)?,
+            });
+        }
+
+        // a stripped file still has .dynsym
+        let mut symbols = Vec::new();
+        for section in sections.iter().filter(|s| s.kind == SHT_SYMTAB || s.kind == SHT_DYNSYM) {
+            let strtab = sections.get(section.link as usize)
+                .ok_or_else(|| invalid("invalid string table"))?;
+            let entries = section_data(data, section)?;
+            for entry in entries.chunks_exact(SYM_SIZE) {
+                let info = entry[4];
+                let address = read_u64(entry, 8)?;
+                if info & 0xf != STT_FUNC || address == 0 {
+                    continue;
+                }
+                symbols.push(Symbol {
+                    address,
+                    size: read_u64(entry, 16)?,
+                    name: read_str(section_data(data, strtab)?, read_u32(entry, 0)? as usize),
+                });
+            }
+        }
+        symbols.sort_by_key(|symbol| symbol.address);
+        symbols.dedup_by_key(|symbol| symbol.address);
+
+        // a bad .eh_frame only prevents the unwinding, the symbols are kept
+        let mut eh_frame = None;
+        if let Some(shstrtab) = sections.get(shstrndx) {
+            let names = section_data(data, shstrtab)?;
+            if let Some(section) = sections.iter().find(|s| read_str(names, s.name as usize) == ".eh_frame") {
+                eh_frame = section_data(data, section)
+                    .and_then(|section_data| EhFrame::parse(section.address, section_data))
+                    .ok();
+            }
+        }
+
+        Ok(ElfFile { segments, symbols, eh_frame })
+    }
+
+    /*
+     * Translate an offset in the file, as given by /proc/pid/maps, to the virtual address
+     * of the file, as used by the symbols and .eh_frame.
+     */
+    pub fn offset_to_vaddr(&self, offset: u64) -> Option<u64>
+    {
+        self.segments.iter()
+            .find(|s| s.offset <= offset && offset - s.offset < s.filesz)
+            .and_then(|s| (offset - s.offset).checked_add(s.vaddr))
+    }
+
+    /* The function containing the virtual address, and the offset in the function */
+    pub fn symbolize(&self, vaddr: u64) -> Option<(&str, u64)>
+    {
+        let index = self.symbols.partition_point(|symbol| symbol.address <= vaddr);
+        let symbol = self.symbols.get(index.checked_sub(1)?)?;
+        // a symbol without size (e.g. in assembly) covers everything up to the next one
+        if symbol.size != 0 && vaddr - symbol.address >= symbol.size {
+            return None;
+        }
+        Some((&symbol.name, vaddr - symbol.address))
+    }
+}
+
+
+fn section_data<'a>(data: &'a [u8], section: &Section) -> io::Result<&'a [u8]>
+{
+    if section.kind == SHT_NOBITS {
+        return Ok(&[]);
+    }
+    let start = section.offset as usize;
+    start.checked_add(section.size as usize)
+        .and_then(|end| data.get(start..end))
+        .ok_or_else(|| invalid("section out of the file"))
+}
+
+/* The offset of the entry of a header table */
+fn table_entry(offset: usize, index: usize, size: usize) -> io::Result<usize>
+{
+    index.checked_mul(size)
+        .and_then(|entry| entry.checked_add(offset))
+        .ok_or_else(|| invalid("header table out of the file"))
+}
+
+fn field(data: &[u8], offset: usize, size: usize) -> io::Result<&[u8]>
+{
+    offset.checked_add(size)
+        .and_then(|end| data.get(offset..end))
+        .ok_or_else(|| invalid("truncated file"))
+}
+
+fn read_str(data: &[u8], offset: usize) -> String
+{
+    let bytes = data.get(offset..).unwrap_or(&[]);
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+fn read_u16(data: &[u8], offset: usize) -> io::Result<u16>
+{
+    let bytes = field(data, offset, 2)?;
+    Ok(u16::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> io::Result<u32>
+{
+    let bytes = field(data, offset, 4)?;
+    Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> io::Result<u64>
+{
+    let bytes = field(data, offset, 8)?;
+    Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn invalid(msg: &str) -> io::Error
+{
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHT_PROGBITS: u32 = 1;
+    const SHT_STRTAB: u32 = 3;
+    const STT_OBJECT: u8 = 1;
+
+    const BASE: u64 = 0x400000;
+    const SEGMENT_SIZE: u64 = 0x2000;
+
+    fn put(data: &mut [u8], offset: usize, bytes: &[u8])
+    {
+        data[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+
+    /* The offset of the string in the table */
+    fn string(table: &mut Vec<u8>, name: &str) -> u32
+    {
+        let offset = table.len() as u32;
+        table.extend_from_slice(name.as_bytes());
+        table.push(0);
+        offset
+    }
+
+    /*
+     * A file with a loadable segment mapping the file at BASE, two functions and a variable,
+     * and the given .eh_frame.
+     */
+    fn elf_file(eh_frame: &[u8]) -> Vec<u8>
+    {
+        let mut shstrtab = vec![0];
+        let mut strtab = vec![0];
+        let mut symtab = vec![0; SYM_SIZE];
+        for (name, info, address, size) in [("main", STT_FUNC, BASE + 0x1000, 0x20),
+                                            ("variable", STT_OBJECT, BASE + 0x1030, 8),
+                                            ("helper", STT_FUNC, BASE + 0x1040, 0)] {
+            let mut symbol = vec![0; SYM_SIZE];
+            put(&mut symbol, 0, &string(&mut strtab, name).to_le_bytes());
+            symbol[4] = info;
+            put(&mut symbol, 8, &u64::to_le_bytes(address));
+            put(&mut symbol, 16, &u64::to_le_bytes(size));
+            symtab.extend(symbol);
+        }
+
+        let mut data = vec![0; 64 + PHDR_SIZE];
+        let mut sections = vec![(0, 0, 0, 0, 0, 0)];
+        for (name, kind, content, link) in [(".symtab", SHT_SYMTAB, &symtab[..], 2),
+                                            (".strtab", SHT_STRTAB, &strtab[..], 0),
+                                            (".eh_frame", SHT_PROGBITS, eh_frame, 0)] {
+            let name = string(&mut shstrtab, name);
+            sections.push((name, kind, BASE + data.len() as u64, data.len(), content.len(), link));
+            data.extend_from_slice(content);
+        }
+        let name = string(&mut shstrtab, ".shstrtab");
+        sections.push((name, SHT_STRTAB, 0, data.len(), shstrtab.len(), 0));
+        data.extend_from_slice(&shstrtab);
+
+        let shoff = data.len();
+        for (name, kind, address, offset, size, link) in sections.iter().copied() {
+            let mut shdr = vec![0; SHDR_SIZE];
+            put(&mut shdr, 0, &u32::to_le_bytes(name));
+            put(&mut shdr, 0x04, &u32::to_le_bytes(kind));
+            put(&mut shdr, 0x10, &u64::to_le_bytes(address));
+            put(&mut shdr, 0x18, &(offset as u64).to_le_bytes());
+            put(&mut shdr, 0x20, &(size as u64).to_le_bytes());
+            put(&mut shdr, 0x28, &u32::to_le_bytes(link));
+            data.extend(shdr);
+        }
+
+        put(&mut data, 0, ELF_MAGIC);
+        data[4] = ELFCLASS64;
+        data[5] = ELFDATA2LSB;
+        put(&mut data, 0x20, &64u64.to_le_bytes());
+        put(&mut data, 0x28, &(shoff as u64).to_le_bytes());
+        put(&mut data, 0x38, &1u16.to_le_bytes());
+        put(&mut data, 0x3c, &(sections.len() as u16).to_le_bytes());
+        put(&mut data, 0x3e, &(sections.len() as u16 - 1).to_le_bytes());
+
+        put(&mut data, 64, &PT_LOAD.to_le_bytes());
+        put(&mut data, 64 + 0x08, &0u64.to_le_bytes());
+        put(&mut data, 64 + 0x10, &BASE.to_le_bytes());
+        put(&mut data, 64 + 0x20, &SEGMENT_SIZE.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn function_symbols()
+    {
+        let elf = ElfFile::parse(&elf_file(&[0; 4])).unwrap();
+
+        assert_eq!(elf.symbolize(BASE + 0x1000), Some(("main", 0)));
+        assert_eq!(elf.symbolize(BASE + 0x101f), Some(("main", 0x1f)));
+        // past the size of the function, the variable is not a function
+        assert_eq!(elf.symbolize(BASE + 0x1020), None);
+        assert_eq!(elf.symbolize(BASE + 0x1030), None);
+        // without size, up to the next function
+        assert_eq!(elf.symbolize(BASE + 0x1fff), Some(("helper", 0xfbf)));
+        assert_eq!(elf.symbolize(BASE + 0xfff), None);
+        assert!(elf.eh_frame.is_some());
+    }
+
+    #[test]
+    fn file_offsets_to_virtual_addresses()
+    {
+        let elf = ElfFile::parse(&elf_file(&[0; 4])).unwrap();
+
+        assert_eq!(elf.offset_to_vaddr(0), Some(BASE));
+        assert_eq!(elf.offset_to_vaddr(0x1234), Some(BASE + 0x1234));
+        assert_eq!(elf.offset_to_vaddr(SEGMENT_SIZE), None);
+        assert_eq!(elf.offset_to_vaddr(u64::MAX), None);
+    }
+
+    #[test]
+    fn symbols_are_kept_without_eh_frame()
+    {
+        // an entry longer than the section
+        let elf = ElfFile::parse(&elf_file(&[0x10, 0, 0, 0, 0, 0, 0, 0])).unwrap();
+        assert!(elf.eh_frame.is_none());
+        assert_eq!(elf.symbolize(BASE + 0x1010), Some(("main", 0x10)));
+    }
+
+    #[test]
+    fn truncated_files_are_rejected()
+    {
+        let data = elf_file(&[0; 4]);
+        assert!(ElfFile::parse(&data[..63]).is_err());
+        // the section headers are at the end, the last one is cut in its size
+        assert!(ElfFile::parse(&data[..data.len() - SHDR_SIZE + 0x24]).is_err());
+    }
+
+    #[test]
+    fn overflowing_offsets_are_rejected()
+    {
+        let data = elf_file(&[0; 4]);
+
+        let mut header_table = data.clone();
+        put(&mut header_table, 0x20, &u64::MAX.to_le_bytes());
+        assert!(ElfFile::parse(&header_table).is_err());
+
+        let mut header_table = data.clone();
+        put(&mut header_table, 0x28, &(u64::MAX - 8).to_le_bytes());
+        assert!(ElfFile::parse(&header_table).is_err());
+
+        // the .symtab section up to the end of the address space
+        let shoff = u64::from_le_bytes(data[0x28..0x30].try_into().unwrap()) as usize;
+        let mut section = data.clone();
+        put(&mut section, shoff + SHDR_SIZE + 0x18, &u64::MAX.to_le_bytes());
+        put(&mut section, shoff + SHDR_SIZE + 0x20, &u64::MAX.to_le_bytes());
+        assert!(ElfFile::parse(&section).is_err());
+    }
+}
@@ -1,6 +1,10 @@
 /*
  * 
  */
+pub mod elf;
+pub mod eh_frame;
+pub mod unwind;
+
 use std::{
     fs::File,
     io::{BufRead, BufReader},
@@ -8,13 +12,13 @@ use std::{
 };
 
 pub struct MemoryRegion {
-    start: u64,
-    end: u64,
-    permissions: String,
-    offset: u64,
-    device: String,
-    inode: u64,
-    pathname: String,
+    pub start: u64,
+    pub end: u64,
+    pub permissions: String,
+    pub offset: u64,
+    pub device: String,
+    pub inode: u64,
+    pub pathname: String,
 }
 
 pub fn read_process_memory_maps(pid: u32) -> Vec<MemoryRegion>
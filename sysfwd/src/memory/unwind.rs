@@ -0,0 +1,248 @@
+/*
+ * Recover the call stack of a tracee stopped at a syscall entry (x86_64 only).
+ *
+ * The stack is read through a MemoryOperation and walked up either with the frame pointers,
+ * or with the CFI of .eh_frame, which also works for the code built without frame pointers.
+ * The frames are symbolized with the ELF symbols of the files mapped by the process.
+ */
+use std::collections::HashMap;
+
+use nix::libc::user_regs_struct;
+use serde::{ Serialize, Deserialize };
+
+use crate::{
+    memory::{
+        read_process_memory_maps,
+        MemoryRegion,
+        elf::ElfFile,
+        eh_frame::{ Cfa, Row, Rule },
+    },
+    targets::operation::MemoryOperation,
+};
+
+
+
+/* The DWARF numbers of the x86_64 registers */
+const DWARF_RBP: u16 = 6;
+const DWARF_RSP: u16 = 7;
+
+const MAX_FRAMES: usize = 64;
+
+
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnwindMethod {
+    /* Follow the chain of saved rbp, the code must keep the frame pointers */
+    FramePointer,
+    /* Apply the CFI of .eh_frame, fall back on the frame pointer when there is none */
+    EhFrame,
+}
+
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Frame {
+    /* The pc of the frame, the return address for the callers */
+    pub address: u64,
+    /* The file mapped at the address */
+    pub module: Option<String>,
+    pub symbol: Option<String>,
+    /* From the symbol if any, from the start of the file otherwise */
+    pub offset: u64,
+}
+
+/* The registers needed to go up the stack */
+#[derive(Clone, Copy, Debug)]
+struct Registers {
+    pc: u64,
+    sp: u64,
+    bp: u64,
+}
+
+
+pub struct Unwinder {
+    method: UnwindMethod,
+    /* The memory maps of each process, read again when an address is unknown */
+    maps: HashMap<i32, Vec<MemoryRegion>>,
+    /* The files which cannot be parsed are kept as None */
+    files: HashMap<String, Option<ElfFile>>,
+}
+
+impl Unwinder {
+
+    pub fn new(method: UnwindMethod) -> Self
+    {
+        Unwinder {
+            method,
+            maps: HashMap::new(),
+            files: HashMap::new(),
+        }
+    }
+
+    /* The memory maps of the process changed (e.g. exec) */
+    pub fn invalidate(&mut self, tgid: i32)
+    {
+        self.maps.remove(&tgid);
+    }
+
+    /*
+     * Walk up the stack of the thread tid, of the process tgid, from its registers.
+     * The unwinding stops at the first frame which cannot be recovered.
+     */
+    pub fn unwind(&mut self, tid: i32, tgid: i32, regs: &user_regs_struct, memory: &dyn MemoryOperation) -> Vec<Frame>
+    {
+        let mut registers = Registers { pc: regs.rip, sp: regs.rsp, bp: regs.rbp };
+        let mut frames = Vec::new();
+
+        self.load_maps(tid, tgid, registers.pc);
+        while frames.len() < MAX_FRAMES && registers.pc != 0 {
+            // a return address is after the call, which may be the last instruction of the function
+            let lookup = match frames.is_empty() {
+                true => registers.pc,
+                false => registers.pc - 1,
+            };
+            self.load_maps(tid, tgid, lookup);
+            frames.push(self.symbolize(tgid, registers.pc, lookup));
+
+            // without CFI for the pc (e.g. JIT code), the frame pointer is the only hint left
+            let row = match self.method {
+                UnwindMethod::EhFrame => self.find_row(tgid, lookup),
+                UnwindMethod::FramePointer => None,
+            };
+            let caller = match row {
+                Some(row) => self.step_eh_frame(tid, tgid, registers, &row, memory),
+                None => self.step_frame_pointer(tid, tgid, registers, memory),
+            };
+            match caller {
+                // the stack grows down, a caller frame is always above
+                Some(caller) if caller.sp > registers.sp && self.is_code(tid, tgid, caller.pc) => registers = caller,
+                _ => break,
+            }
+        }
+        frames
+    }
+
+    /* Read the maps again if the address is not mapped, e.g. after a mmap() */
+    fn load_maps(&mut self, tid: i32, tgid: i32, address: u64)
+    {
+        let known = self.maps.get(&tgid)
+            .is_some_and(|maps| find_region(maps, address).is_some());
+        if !known {
+            self.maps.insert(tgid, read_process_memory_maps(tid as u32));
+        }
+    }
+
+    /* Beyond the outermost frame, the return address is whatever is on the stack */
+    fn is_code(&mut self, tid: i32, tgid: i32, address: u64) -> bool
+    {
+        self.load_maps(tid, tgid, address);
+        self.maps.get(&tgid)
+            .and_then(|maps| find_region(maps, address))
+            .is_some_and(|region| region.permissions.contains('x'))
+    }
+
+    fn load_file(&mut self, path: &str) -> Option<&ElfFile>
+    {
+        if !self.files.contains_key(path) {
+            self.files.insert(path.to_string(), ElfFile::open(path).ok());
+        }
+        self.files.get(path)?.as_ref()
+    }
+
+    /* The file mapped at the address, and the address in the file */
+    fn locate(&mut self, tgid: i32, address: u64) -> Option<(&ElfFile, u64)>
+    {
+        let region = find_region(self.maps.get(&tgid)?, address)?;
+        // the anonymous mappings and the special ones (e.g. [vdso], [stack]) have no file to read
+        if !region.pathname.starts_with('/') {
+            return None;
+        }
+        let offset = address - region.start + region.offset;
+        let path = region.pathname.clone();
+
+        let file = self.load_file(&path)?;
+        let vaddr = file.offset_to_vaddr(offset)?;
+        Some((file, vaddr))
+    }
+
+    fn symbolize(&mut self, tgid: i32, address: u64, lookup: u64) -> Frame
+    {
+        let module = self.maps.get(&tgid)
+            .and_then(|maps| find_region(maps, address))
+            .filter(|region| !region.pathname.is_empty())
+            .map(|region| region.pathname.clone());
+
+        let (symbol, offset) = match self.locate(tgid, lookup) {
+            Some((file, vaddr)) => match file.symbolize(vaddr) {
+                // the offset is the one of the pc, not of the lookup address
+                Some((name, offset)) => (Some(name.to_string()), offset + (address - lookup)),
+                None => (None, vaddr + (address - lookup)),
+            },
+            None => (None, 0),
+        };
+
+        Frame { address, module, symbol, offset }
+    }
+
+    fn find_row(&mut self, tgid: i32, lookup: u64) -> Option<Row>
+    {
+        let (file, vaddr) = self.locate(tgid, lookup)?;
+        file.eh_frame.as_ref()?.find_row(vaddr)
+    }
+
+    /* The outermost frame (e.g. _start) has an undefined return address */
+    fn step_eh_frame(&self, tid: i32, tgid: i32, registers: Registers, row: &Row,
+                     memory: &dyn MemoryOperation) -> Option<Registers>
+    {
+        let cfa = match row.cfa {
+            Cfa::RegisterOffset { register: DWARF_RSP, offset } => registers.sp.wrapping_add(offset as u64),
+            Cfa::RegisterOffset { register: DWARF_RBP, offset } => registers.bp.wrapping_add(offset as u64),
+            _ => return None,
+        };
+
+        let pc = match row.rule(row.return_address) {
+            Rule::Offset(offset) => self.read_word(tid, tgid, cfa.wrapping_add(offset as u64), memory)?,
+            _ => return None,
+        };
+        let bp = match row.rule(DWARF_RBP) {
+            Rule::Offset(offset) => self.read_word(tid, tgid, cfa.wrapping_add(offset as u64), memory)?,
+            Rule::ValOffset(offset) => cfa.wrapping_add(offset as u64),
+            Rule::SameValue => registers.bp,
+            _ => return None,
+        };
+
+        Some(Registers { pc, sp: cfa, bp })
+    }
+
+    /*
+     * With frame pointers, a frame starts with the rbp of the caller followed by the return address:
+     *     push %rbp ; mov %rsp, %rbp
+     */
+    fn step_frame_pointer(&self, tid: i32, tgid: i32, registers: Registers,
+                          memory: &dyn MemoryOperation) -> Option<Registers>
+    {
+        if registers.bp == 0 || registers.bp < registers.sp {
+            return None;
+        }
+        let bp = self.read_word(tid, tgid, registers.bp, memory)?;
+        let pc = self.read_word(tid, tgid, registers.bp + 8, memory)?;
+
+        Some(Registers { pc, sp: registers.bp + 16, bp })
+    }
+
+    /* The address is checked first, a bad frame would have the target operation report an error */
+    fn read_word(&self, tid: i32, tgid: i32, address: u64, memory: &dyn MemoryOperation) -> Option<u64>
+    {
+        let region = find_region(self.maps.get(&tgid)?, address)?;
+        if !region.permissions.starts_with('r') || address + 8 > region.end {
+            return None;
+        }
+        let bytes = memory.read(tid, address as usize, 8).ok()?;
+        Some(u64::from_le_bytes(bytes.try_into().ok()?))
+    }
+}
+
+
+fn find_region(maps: &[MemoryRegion], address: u64) -> Option<&MemoryRegion>
+{
+    maps.iter().find(|region| region.start <= address && address < region.end)
+}
@@ -8,7 +8,7 @@ use std::{
     //os::unix::process::{ CommandExt },
     //process::{ exit, Child, Command },
     //sync::{ Arc, Barrier },
-    io::{ self, BufRead, BufReader, BufWriter, Write },
+    io::{ BufRead, BufReader, BufWriter, Write },
     net::{TcpListener, TcpStream, Ipv4Addr },
 };
 
@@ -18,6 +18,7 @@ use nix::{
 };
 
 use crate::{
+    error::{ Error, Result },
     tracer::TracerCallback,
     executor::ExecutorCallback,
 };
@@ -56,28 +57,31 @@ impl ControlChannel {
         }
     }
 
-    pub fn listen(&mut self, ip: Ipv4Addr, port: u16)
+    pub fn listen(&mut self, ip: Ipv4Addr, port: u16) -> Result<()>
     {
         println!("Listen for connections...");
         let address = (ip, port);
-        let listener = TcpListener::bind(address).unwrap();
+        let listener = TcpListener::bind(address).map_err(Error::Transport)?;
 
         for stream in listener.incoming() {
             match stream {
                 Ok(stream) => {
-                    self.handle_connection(stream);
+                    if let Err(err) = self.handle_connection(stream) {
+                        eprintln!("Connection error: {}", err);
+                    }
                 }
                 Err(e) => {
                     eprintln!("Fail to establish connection: {}", e);
                 }
             }
         }
-        println!("Finish listening")
+        println!("Finish listening");
+        Ok(())
     }
 
-    fn handle_connection(&mut self, stream: TcpStream)
+    fn handle_connection(&mut self, stream: TcpStream) -> Result<()>
     {
-        self.reader = Some(BufReader::new(stream.try_clone().unwrap()));
+        self.reader = Some(BufReader::new(stream.try_clone().map_err(Error::Transport)?));
         self.writer = Some(BufWriter::new(stream));
 
 
@@ -100,16 +104,40 @@ impl ControlChannel {
                 }
             }
         }
+        Ok(())
     }
 
-    fn receive_message(&mut self, buffer: &mut String) -> io::Result<usize>
+    fn receive_message(&mut self, buffer: &mut String) -> Result<usize>
     {
-
-        let result = self.reader.as_mut().unwrap().read_line(buffer);
+        let reader = self.reader.as_mut().ok_or_else(|| Error::Protocol("no connection".to_string()))?;
+        let result = reader.read_line(buffer).map_err(Error::Transport);
         println!("Message received: {:?}", buffer);
         result
     }
 
+    /* Send a reply to avatar2 */
+    fn reply(&mut self, buffer: &[u8]) -> Result<()>
+    {
+        let writer = self.writer.as_mut().ok_or_else(|| Error::Protocol("no connection".to_string()))?;
+        writer.write_all(buffer).map_err(Error::Transport)?;
+        writer.flush().map_err(Error::Transport)
+    }
+
+    /*
+     * Reply with an acknowledgement or an error, depending on the result of the callback.
+     * The error is also returned to be reported.
+     */
+    fn reply_result(&mut self, result: Result<()>) -> Result<()>
+    {
+        match result {
+            Ok(()) => self.reply(b"ACK"),
+            Err(err) => {
+                self.reply(b"ERR")?;
+                Err(err)
+            },
+        }
+    }
+
     fn dispatch_message(&mut self, message: String)
     {
         let command: Vec<&str> = message.split_whitespace().collect();
@@ -118,16 +146,17 @@ impl ControlChannel {
             Ok(_) => {
                 // TODO: Send Ack to avatar2 ?
             },
-            Err(msg) => {
-                eprintln!("{}", msg);
+            Err(err) => {
+                eprintln!("{}", err);
                 // TODO: Send back error message to avatar2
             }
         }
     }
 
-    fn dispatch_command(&mut self, command: Vec<&str>) -> Result<(), String>
+    fn dispatch_command(&mut self, command: Vec<&str>) -> Result<()>
     {
-        match command[0] {
+        let name = *command.first().ok_or_else(|| Error::Protocol("empty command".to_string()))?;
+        match name {
             "switch" => self.switch_configuration(command),
 
             "read_mem" => self.read_memory(command),
@@ -139,8 +168,8 @@ impl ControlChannel {
             "set_breakpoint" => self.set_breakpoint(command),
             "remove_breakpoint" => self.remove_breakpoint(command),
 
-            "get_procmaps" => Err(format!("Not implemented")),
-            //"" => Err(format!("Not implemented")),
+            "get_procmaps" => Err(Error::Unsupported("get_procmaps".to_string())),
+            //"" => Err("Not implemented".to_string()),
             
             // Check for configuration specific commands
             _ => {
@@ -153,31 +182,34 @@ impl ControlChannel {
 
     }
 
-    fn dispatch_tracer(&mut self, command: Vec<&str>) -> Result<(), String>
+    fn dispatch_tracer(&mut self, command: Vec<&str>) -> Result<()>
     {
         match command[0] {
             "spawn_process" => self.tracer_spawn_process(command),
             "kill_process" => self.tracer_kill_process(command),
+            "attach_process" => self.tracer_attach_process(command),
+            "detach_process" => self.tracer_detach_process(command),
+            "flush_history" => self.tracer_flush_history(command),
             "start_tracing" => self.tracer_start_tracing(command),
             "cont_tracing" => self.tracer_cont_tracing(command),
             "stop_tracing" => self.tracer_stop_tracing(command),
-            //"" => Err(format!("Not implemented")),
+            //"" => Err("Not implemented".to_string()),
             _ => {
                 let msg = format!("[TRACER] Command not implemented: {}", command[0]);
-                Err(msg)
+                Err(Error::Protocol(msg))
             }
         }
     }
 
-    fn dispatch_executor(&mut self, command: Vec<&str>) -> Result<(), String>
+    fn dispatch_executor(&mut self, command: Vec<&str>) -> Result<()>
     {
         match command[0] {
             "spawn_process" => self.executor_spawn_process(command),
             "kill_process" => self.executor_kill_process(command),
-            //"" => Err(format!("Not implemented")),
+            //"" => Err("Not implemented".to_string()),
             _ => {
                 let msg = format!("[EXECUTOR] Command not implemented: {}", command[0]);
-                Err(msg)
+                Err(Error::Protocol(msg))
             }
         }
     }
@@ -185,153 +217,139 @@ impl ControlChannel {
 
     /* Function in common */
 
-    fn switch_configuration(&mut self, _command: Vec<&str>) -> Result<(), String>
+    fn switch_configuration(&mut self, _command: Vec<&str>) -> Result<()>
     {
-        let msg = format!("Switching between Tracer and Executor not supported yet :(");
-        Err(msg)
+        let msg = "Switching between Tracer and Executor not supported yet :(".to_string();
+        Err(Error::Unsupported(msg))
     }
 
-    fn read_registers(&mut self, _command: Vec<&str>) -> Result<(), String>
+    fn read_registers(&mut self, _command: Vec<&str>) -> Result<()>
     {
         // TODO
-        let msg = format!("Command read register not implemented yet");
-        Err(msg)
+        let msg = "Command read register not implemented yet".to_string();
+        Err(Error::Unsupported(msg))
     }
 
-    fn write_registers(&mut self, _command: Vec<&str>) -> Result<(), String>
+    fn write_registers(&mut self, _command: Vec<&str>) -> Result<()>
     {
         // TODO
-        let msg = format!("Command write register not implemented yet");
-        Err(msg)
+        let msg = "Command write register not implemented yet".to_string();
+        Err(Error::Unsupported(msg))
     }
 
-    fn read_memory(&mut self, _command: Vec<&str>) -> Result<(), String>
+    fn read_memory(&mut self, _command: Vec<&str>) -> Result<()>
     {
         // TODO
-        let msg = format!("Command read memory not implemented yet");
-        Err(msg)
+        let msg = "Command read memory not implemented yet".to_string();
+        Err(Error::Unsupported(msg))
     }
 
-    fn write_memory(&mut self, _command: Vec<&str>) -> Result<(), String>
+    fn write_memory(&mut self, _command: Vec<&str>) -> Result<()>
     {
         // TODO
-        let msg = format!("Command write memory not implemented yet");
-        Err(msg)
+        let msg = "Command write memory not implemented yet".to_string();
+        Err(Error::Unsupported(msg))
     }
 
-    fn set_breakpoint(&mut self, _command: Vec<&str>) -> Result<(), String>
+    fn set_breakpoint(&mut self, _command: Vec<&str>) -> Result<()>
     {
         // TODO
-        let msg = format!("Command set breakpoint not implemented yet");
-        Err(msg)
+        let msg = "Command set breakpoint not implemented yet".to_string();
+        Err(Error::Unsupported(msg))
     }
 
-    fn remove_breakpoint(&mut self, _command: Vec<&str>) -> Result<(), String>
+    fn remove_breakpoint(&mut self, _command: Vec<&str>) -> Result<()>
     {
         // TODO
-        let msg = format!("Command remove breakpoint not implemented yet");
-        Err(msg)
+        let msg = "Command remove breakpoint not implemented yet".to_string();
+        Err(Error::Unsupported(msg))
     }
     
 
     /* Tracer related functions */
     
-    fn tracer_spawn_process(&mut self, command: Vec<&str>) -> Result<(), String>
+    fn tracer_spawn_process(&mut self, command: Vec<&str>) -> Result<()>
     {
-        let program = command[1].to_string();
+        let program = argument::<String>(&command, 1)?;
         let args_str: Vec<&str> = command[2..].to_vec();
         let args: Vec<String> = args_str.iter().map(|&s| s.to_string()).collect();
 
-        match self.tracer.as_mut() {
-            Some(tracer) => {
-                // TODO: match on the value return by spawn_process
-                let pid = tracer.spawn_process(program, args).unwrap();
+        let result = match self.tracer.as_mut() {
+            Some(tracer) => tracer.spawn_process(program, args).map_err(Error::Target),
+            None => Err(Error::Unsupported("no tracer configured".to_string())),
+        };
 
-                let buffer = pid.as_raw().to_be_bytes();
-                self.writer.as_mut().unwrap().write(&buffer).unwrap();
+        match result {
+            Ok(pid) => self.reply(&pid.as_raw().to_be_bytes()),
+            Err(err) => {
+                self.reply(b"ERR")?;
+                Err(err)
             },
-            None => {
-                let mut ack = String::new();
-                ack.push_str("ERR");
-                let buffer = ack.as_bytes();
-                self.writer.as_mut().unwrap().write(&buffer).unwrap();
-            }
         }
+    }
 
-        self.writer.as_mut().unwrap().flush().unwrap();
+    fn tracer_kill_process(&mut self, command: Vec<&str>) -> Result<()>
+    {
+        let pid = Pid::from_raw(argument(&command, 1)?);
 
-        // when should we return an error ?
-        Ok(())
+        let result = match self.tracer.as_mut() {
+            Some(tracer) => tracer.kill_process(pid).map_err(Error::Target),
+            None => Err(Error::Unsupported("no tracer configured".to_string())),
+        };
+        self.reply_result(result)
     }
 
-    fn tracer_kill_process(&mut self, command: Vec<&str>) -> Result<(), String>
+    fn tracer_attach_process(&mut self, command: Vec<&str>) -> Result<()>
     {
-        let pid = command[1];
-        let pid = FromStr::from_str(pid).unwrap();
-        let pid = Pid::from_raw(pid);
-
-        match self.tracer.as_mut() {
-            Some(tracer) => {
-                // TODO: match on the value return by spawn_process
-                tracer.kill_process(pid).unwrap();
-
-                let mut ack = String::new();
-                ack.push_str("ACK");
-                let buffer = ack.as_bytes();
-                self.writer.as_mut().unwrap().write(&buffer).unwrap();
-            },
-            None => {
-                let mut ack = String::new();
-                ack.push_str("ERR");
-                let buffer = ack.as_bytes();
-                self.writer.as_mut().unwrap().write(&buffer).unwrap();
-            }
-        }
+        let pid = Pid::from_raw(argument(&command, 1)?);
 
-        self.writer.as_mut().unwrap().flush().unwrap();
+        let result = match self.tracer.as_mut() {
+            Some(tracer) => tracer.attach_process(pid).map_err(Error::Target),
+            None => Err(Error::Unsupported("no tracer configured".to_string())),
+        };
+        self.reply_result(result)
+    }
 
-        // when should we return an error ?
-        Ok(())
+    fn tracer_detach_process(&mut self, command: Vec<&str>) -> Result<()>
+    {
+        let pid = Pid::from_raw(argument(&command, 1)?);
+
+        let result = match self.tracer.as_mut() {
+            Some(tracer) => tracer.detach_process(pid).map_err(Error::Target),
+            None => Err(Error::Unsupported("no tracer configured".to_string())),
+        };
+        self.reply_result(result)
     }
 
-    fn tracer_start_tracing(&mut self, command: Vec<&str>) -> Result<(), String>
+    /* The path must not contain spaces */
+    fn tracer_flush_history(&mut self, command: Vec<&str>) -> Result<()>
     {
-        let pid = command[1];
-        let pid = FromStr::from_str(pid).unwrap();
-        let pid = Pid::from_raw(pid);
-
-        match self.tracer.as_mut() {
-            Some(tracer) => {
-                // TODO: match on the value return by spawn_process
-                tracer.start_tracing(pid).unwrap();
-
-                let mut ack = String::new();
-                ack.push_str("ACK");
-                let buffer = ack.as_bytes();
-                self.writer.as_mut().unwrap().write(&buffer).unwrap();
-            },
-            None => {
-                let mut ack = String::new();
-                ack.push_str("ERR");
-                let buffer = ack.as_bytes();
-                self.writer.as_mut().unwrap().write(&buffer).unwrap();
-            }
-        }
+        let pid = Pid::from_raw(argument(&command, 1)?);
+        let path: String = argument(&command, 2)?;
+
+        let result = match self.tracer.as_mut() {
+            Some(tracer) => tracer.flush_history(pid, path).map_err(Error::Target),
+            None => Err(Error::Unsupported("no tracer configured".to_string())),
+        };
+        self.reply_result(result)
+    }
 
-        self.writer.as_mut().unwrap().flush().unwrap();
+    fn tracer_start_tracing(&mut self, command: Vec<&str>) -> Result<()>
+    {
+        let pid = Pid::from_raw(argument(&command, 1)?);
 
-        // when should we return an error ?
-        Ok(())
+        let result = match self.tracer.as_mut() {
+            Some(tracer) => tracer.start_tracing(pid).map_err(Error::Target),
+            None => Err(Error::Unsupported("no tracer configured".to_string())),
+        };
+        self.reply_result(result)
     }
 
-    fn tracer_cont_tracing(&mut self, command: Vec<&str>) -> Result<(), String>
+    fn tracer_cont_tracing(&mut self, command: Vec<&str>) -> Result<()>
     {
-        let pid = command[1];
-        let pid = FromStr::from_str(pid).unwrap();
-        let pid = Pid::from_raw(pid);
+        let pid = Pid::from_raw(argument(&command, 1)?);
 
-        let sig = command[2];
-        let sig = FromStr::from_str(sig).unwrap();
+        let sig: i32 = argument(&command, 2)?;
         let signal: Option<Signal>;
         // Linux signals
         match sig {
@@ -369,115 +387,64 @@ impl ControlChannel {
             _ => signal = None,
         }
 
-        match self.tracer.as_mut() {
-            Some(tracer) => {
-                tracer.cont_tracing(pid, signal).unwrap();
-
-                let mut ack = String::new();
-                ack.push_str("ACK");
-                let buffer = ack.as_bytes();
-                self.writer.as_mut().unwrap().write(&buffer).unwrap();
-            },
-            None => {
-                let mut ack = String::new();
-                ack.push_str("ERR");
-                let buffer = ack.as_bytes();
-                self.writer.as_mut().unwrap().write(&buffer).unwrap();
-            }
-        }
-
-        Ok(())
+        let result = match self.tracer.as_mut() {
+            Some(tracer) => tracer.cont_tracing(pid, signal).map_err(Error::Target),
+            None => Err(Error::Unsupported("no tracer configured".to_string())),
+        };
+        self.reply_result(result)
     }
     
-    fn tracer_stop_tracing(&mut self, command: Vec<&str>) -> Result<(), String>
+    fn tracer_stop_tracing(&mut self, command: Vec<&str>) -> Result<()>
     {
-        let pid = command[1];
-        let pid = FromStr::from_str(pid).unwrap();
-        let pid = Pid::from_raw(pid);
-
-        match self.tracer.as_mut() {
-            Some(tracer) => {
-                // TODO: match on the value return by spawn_process
-                tracer.stop_tracing(pid).unwrap();
-
-                let mut ack = String::new();
-                ack.push_str("ACK");
-                let buffer = ack.as_bytes();
-                self.writer.as_mut().unwrap().write(&buffer).unwrap();
-            },
-            None => {
-                let mut ack = String::new();
-                ack.push_str("ERR");
-                let buffer = ack.as_bytes();
-                self.writer.as_mut().unwrap().write(&buffer).unwrap();
-            }
-        }
-
-        self.writer.as_mut().unwrap().flush().unwrap();
+        let pid = Pid::from_raw(argument(&command, 1)?);
 
-        // when should we return an error ?
-        Ok(())
+        let result = match self.tracer.as_mut() {
+            Some(tracer) => tracer.stop_tracing(pid).map_err(Error::Target),
+            None => Err(Error::Unsupported("no tracer configured".to_string())),
+        };
+        self.reply_result(result)
     }
 
 
     /* Executor related functions */
 
-    fn executor_spawn_process(&mut self, command: Vec<&str>) -> Result<(), String>
+    fn executor_spawn_process(&mut self, command: Vec<&str>) -> Result<()>
     {
-        let program = command[1];
+        let program = *command.get(1).ok_or_else(|| Error::Protocol("missing argument 1".to_string()))?;
         let args = &command[2..];
 
-        match self.executor.as_mut() {
-            Some(executor) => {
-                // TODO: match on the value return by spawn_process
-                let pid = executor.spawn_process(program, args).unwrap();
+        let result = match self.executor.as_mut() {
+            Some(executor) => executor.spawn_process(program, args).map_err(Error::Target),
+            None => Err(Error::Unsupported("no executor configured".to_string())),
+        };
 
-                let buffer = pid.as_raw().to_be_bytes();
-                self.writer.as_mut().unwrap().write(&buffer).unwrap();
+        match result {
+            Ok(pid) => self.reply(&pid.as_raw().to_be_bytes()),
+            Err(err) => {
+                self.reply(b"ERR")?;
+                Err(err)
             },
-            None => {
-                let mut ack = String::new();
-                ack.push_str("ERR");
-                let buffer = ack.as_bytes();
-                self.writer.as_mut().unwrap().write(&buffer).unwrap();
-            }
         }
-
-        self.writer.as_mut().unwrap().flush().unwrap();
-
-        // when should we return an error ?
-        Ok(())
     }
 
-    fn executor_kill_process(&mut self, command: Vec<&str>) -> Result<(), String>
+    fn executor_kill_process(&mut self, command: Vec<&str>) -> Result<()>
     {
-        let pid = command[1];
-        let pid = FromStr::from_str(pid).unwrap();
-        let pid = Pid::from_raw(pid);
-
-        match self.executor.as_mut() {
-            Some(executor) => {
-                // TODO: match on the value return by spawn_process
-                executor.kill_process(pid).unwrap();
-
-                let mut ack = String::new();
-                ack.push_str("ACK");
-                let buffer = ack.as_bytes();
-                self.writer.as_mut().unwrap().write(&buffer).unwrap();
-            },
-            None => {
-                let mut ack = String::new();
-                ack.push_str("ERR");
-                let buffer = ack.as_bytes();
-                self.writer.as_mut().unwrap().write(&buffer).unwrap();
-            }
-        }
+        let pid = Pid::from_raw(argument(&command, 1)?);
 
-        self.writer.as_mut().unwrap().flush().unwrap();
-
-        // when should we return an error ?
-        Ok(())
+        let result = match self.executor.as_mut() {
+            Some(executor) => executor.kill_process(pid).map_err(Error::Target),
+            None => Err(Error::Unsupported("no executor configured".to_string())),
+        };
+        self.reply_result(result)
     }
 
 
-}
\ No newline at end of file
+}
+
+
+/* Parse the argument at the given index of a command */
+fn argument<T: FromStr>(command: &[&str], index: usize) -> Result<T>
+{
+    let arg = command.get(index).ok_or_else(|| Error::Protocol(format!("missing argument {}", index)))?;
+    FromStr::from_str(arg).map_err(|_| Error::Protocol(format!("invalid argument {}: {}", index, arg)))
+}
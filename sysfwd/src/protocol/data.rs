@@ -8,7 +8,12 @@ use std::{
 };
 
 
-use crate::syscall::Syscall;
+use serde::{ Serialize, Deserialize };
+
+use crate::{
+    error::{ Error, Result },
+    syscall::Syscall,
+};
 
 
 /* 
@@ -19,6 +24,38 @@ const HEADER_SIZE: usize = 8;
 
 
 
+/*
+ * The signal events of the tracee, notified to the executor without waiting for a reply
+ * (NotifySignal in sysfwd.proto).
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignalEvent {
+    Delivered,
+    GroupStop,
+    Terminated,
+}
+
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct NotifySignal {
+    pub signo: i32,
+    pub pid: i32,
+    pub event: SignalEvent,
+}
+
+/*
+ * The messages received by the executor: a syscall to invoke, or a notification.
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[serde(untagged)]
+pub enum DataMessage {
+    Syscall(Box<Syscall>),
+    Signal(NotifySignal),
+}
+
+
 /*
  * A Peer represents the endpoint of a connection between a tracer and an executor.
  * It is used to send syscall information through UDP.
@@ -35,7 +72,7 @@ impl Peer {
         Self { local_socket, remote_address }
     }
 
-    pub fn send(&self, data: &[u8]) -> Result<(), std::io::Error> 
+    pub fn send(&self, data: &[u8]) -> Result<()>
     {
         // There is a bug if data > 2^16 which is the maximum payload size for a UDP packet.
         // For example with cat which read 0x20000 bytes
@@ -45,22 +82,34 @@ impl Peer {
         let message: Vec<u8> = [&header[..HEADER_SIZE], data].concat();
 
         // Send the message
-        let _size: usize = self.local_socket.send_to(&message, self.remote_address)?;
+        let _size: usize = self.local_socket.send_to(&message, self.remote_address).map_err(Error::Transport)?;
         //println!("Sent {} bytes", size);
         Ok(())
     }
 
-    pub fn receive(&self) -> Result<(Vec<u8>, usize), std::io::Error>
+    pub fn receive(&self) -> Result<(Vec<u8>, usize)>
     {
         // Read header containing the size of the payload
         let mut header = [0u8; HEADER_SIZE];
-        let (count, _addr): (usize, SocketAddr) = self.local_socket.peek_from(&mut header)?;
-        if count != HEADER_SIZE { panic!("Fail to read header"); }
+        let (count, _addr): (usize, SocketAddr) = self.local_socket.peek_from(&mut header).map_err(Error::Transport)?;
+        if count != HEADER_SIZE {
+            // drop the malformed packet
+            self.local_socket.recv_from(&mut header).map_err(Error::Transport)?;
+            return Err(Error::Protocol(format!("packet of {} bytes without header", count)));
+        }
         let size = usize::from_be_bytes(header);
+        if size > u16::MAX as usize {
+            self.local_socket.recv_from(&mut header).map_err(Error::Transport)?;
+            return Err(Error::Protocol(format!("payload of {} bytes exceeds a UDP packet", size)));
+        }
 
         // Read the payload
         let mut message: Vec<u8> = vec![0u8; HEADER_SIZE + size];
-        let (size, _addr): (usize, SocketAddr) = self.local_socket.recv_from(&mut message)?;
+        let (size, _addr): (usize, SocketAddr) = self.local_socket.recv_from(&mut message).map_err(Error::Transport)?;
+        if size < HEADER_SIZE {
+            return Err(Error::Protocol(format!("packet of {} bytes without header", size)));
+        }
+        message.truncate(size);
         //println!("Received {} bytes", size);
         let payload = message.split_off(HEADER_SIZE);
         Ok((payload, size))
@@ -75,6 +124,20 @@ impl Peer {
 
 
 
+/*
+ * How the tracer reaches the executor: the Client over UDP, or a replay of a recorded trace.
+ */
+pub trait Transport {
+    /* Execute the syscall remotely and return it with its results */
+    fn send_syscall_entry(&mut self, syscall: &Syscall) -> Result<Syscall>;
+    fn notify_signal(&mut self, signal: &NotifySignal) -> Result<()>;
+    /* The tracing is over */
+    fn shutdown(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+
 /*
  * The dispatcher...
  */
@@ -99,24 +162,36 @@ impl Client {
         let connection = Peer::new(local_addr, remote_addr);
         Client { connection }
     }
+}
+
+impl Transport for Client {
 
-    pub fn send_syscall_entry(&self, syscall: &Syscall) -> Result<Syscall, std::io::Error>
+    fn send_syscall_entry(&mut self, syscall: &Syscall) -> Result<Syscall>
     {
         // Craft the message
-        let data: String = serde_json::to_string(syscall).expect("Fail to serialize syscall to JSON");
+        let data: String = serde_json::to_string(syscall)
+            .map_err(|err| Error::Protocol(format!("cannot serialize syscall: {}", err)))?;
         //println!("[TRACER] Send syscall: {:?}", data);
 
         // Send the message
-        self.connection.send(&data.as_bytes()).expect("Fail to send syscall entry");
+        self.connection.send(data.as_bytes())?;
 
         // Wait for the reply
-        let (buffer, _len): (Vec<u8>, usize) = self.connection.receive().expect("Error receiving syscall reply message");
+        let (buffer, _len): (Vec<u8>, usize) = self.connection.receive()?;
         //println!("[TRACER] Received {} bytes: {:?}", len, buffer);
 
-        let remote_syscall: Syscall = serde_json::from_slice(&buffer).expect("Fail to deserialize Syscall from JSON");
+        let remote_syscall: Syscall = serde_json::from_slice(&buffer)
+            .map_err(|err| Error::Protocol(format!("cannot deserialize syscall: {}", err)))?;
         Ok(remote_syscall)
     }
 
+    fn notify_signal(&mut self, signal: &NotifySignal) -> Result<()>
+    {
+        let data: String = serde_json::to_string(signal)
+            .map_err(|err| Error::Protocol(format!("cannot serialize signal: {}", err)))?;
+        self.connection.send(data.as_bytes())
+    }
+
 }
 
 
@@ -151,25 +226,27 @@ impl Server {
     }
 
 
-    pub fn receive_syscall(&self) -> Result<Syscall, std::io::Error>
+    pub fn receive(&self) -> Result<DataMessage>
     {
         // Read socket
         let (buffer, _len): (Vec<u8>, usize)  = self.connection.receive()?;
 
-        // Parse syscall
-        let syscall = serde_json::from_slice(&buffer).expect("Fail to deserialize Syscall from JSON");
-        Ok(syscall)
+        // Parse syscall or notification
+        let message = serde_json::from_slice(&buffer)
+            .map_err(|err| Error::Protocol(format!("cannot deserialize message: {}", err)))?;
+        Ok(message)
     }
 
 
-    pub fn return_syscall_exit(&self, syscall: &Syscall)
+    pub fn return_syscall_exit(&self, syscall: &Syscall) -> Result<()>
     {
         // Craft the message
-        let message: String = serde_json::to_string(syscall).expect("Fail to serialize syscall to JSON");
+        let message: String = serde_json::to_string(syscall)
+            .map_err(|err| Error::Protocol(format!("cannot serialize syscall: {}", err)))?;
         let data: &[u8] = message.as_bytes();
         //println!("[EXECUTOR] Send syscall: {:?}", message);
 
         // Send the message
-        self.connection.send(&data).expect("Failt to return syscall exit");
+        self.connection.send(data)
     }
-}
\ No newline at end of file
+}
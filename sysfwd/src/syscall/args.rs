@@ -12,7 +12,7 @@ use crate::{
         decoder::DecodeArg,
         encoder::EncodeArg,
     },
-    targets::operation::Operation,
+    targets::operation::{ Operation, OperationError },
 };
 
 
@@ -359,6 +359,25 @@ impl EncodeArg for Address {
 
 /* Pointer arguments */
 
+/*
+ * Read the memory pointed to by an argument.
+ * A bad pointer is not a decoding error: the faulting address is kept in the argument, as the
+ * kernel would fail with EFAULT, and nothing is read.
+ */
+pub(crate) fn read_pointed(pid: i32, operation: &Operation, address: usize, size: usize,
+                           fault: &mut Option<usize>) -> Result<Vec<u8>, std::io::Error>
+{
+    *fault = None;
+    match operation.memory.read(pid, address, size) {
+        Ok(mem) => Ok(mem),
+        Err(OperationError::Fault { address, .. }) => {
+            *fault = Some(address);
+            Ok(Vec::new())
+        },
+        Err(err) => Err(err.into()),
+    }
+}
+
 /*
  * A memory block
  */
@@ -369,6 +388,9 @@ pub struct Buffer {
     pub direction: Direction,
     pub size: usize,
     pub content: Vec<u8>,
+    /* The first address which could not be read (EFAULT) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fault: Option<usize>,
 }
 
 impl Buffer {
@@ -378,6 +400,7 @@ impl Buffer {
             direction: direction,
             size: size,
             content: Vec::new(),  // TODO: initialize with a default size?
+            fault: None,
         }
     }
 }
@@ -390,6 +413,7 @@ impl From<usize> for Buffer {
             direction: Direction::InOut,
             size: 0,
             content: Vec::new(),
+            fault: None,
         }
     }
 }
@@ -397,7 +421,7 @@ impl From<usize> for Buffer {
 impl DecodeArg for Buffer {
 
     fn decode(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> { 
-        self.content = operation.memory.read(pid, self.address, self.size);
+        self.content = read_pointed(pid, operation, self.address, self.size, &mut self.fault)?;
         Ok(())
     }
 
@@ -413,7 +437,7 @@ impl EncodeArg for Buffer {
 
     fn encode(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
         let mem = self.content.clone();
-        operation.memory.write(pid, self.address, mem);
+        operation.memory.write(pid, self.address, mem)?;
         Ok(())
     }
 }
@@ -430,6 +454,9 @@ pub struct NullBuffer {
     pub direction: Direction,
     pub size: usize,
     pub content: Vec<u8>,
+    /* The first address which could not be read (EFAULT) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fault: Option<usize>,
 }
 
 impl NullBuffer {
@@ -439,6 +466,7 @@ impl NullBuffer {
             direction: direction,
             size: 0,
             content: Vec::new(),  // TODO: initialize with a default size?
+            fault: None,
         }
     }
 }
@@ -451,6 +479,7 @@ impl From<usize> for NullBuffer {
             direction: Direction::InOut,
             size: 0,
             content: Vec::new(),
+            fault: None,
         }
     }
 }
@@ -461,7 +490,19 @@ impl DecodeArg for NullBuffer {
         //TODO: does not work when the Null terminated buffer is greater than READ_SIZE bytes.
         #[allow(non_snake_case)]
         let READ_SIZE = 1024;
-        let buf = operation.memory.read(pid, self.address, READ_SIZE);
+        self.fault = None;
+        let buf = match operation.memory.read(pid, self.address, READ_SIZE) {
+            Ok(buf) => buf,
+            // the string may end before the fault
+            Err(OperationError::Fault { address, partial }) => {
+                if !partial.contains(&0) {
+                    self.fault = Some(address);
+                    return Ok(());
+                }
+                partial
+            },
+            Err(err) => return Err(err.into()),
+        };
 
         let mut iter = buf.iter();
         loop {
@@ -498,7 +539,7 @@ impl EncodeArg for NullBuffer {
         for _ in 0..count {
             mem.push(0);
         }
-        operation.memory.write(pid, self.address, mem);
+        operation.memory.write(pid, self.address, mem)?;
         Ok(())
     }
 }
@@ -570,6 +611,9 @@ pub struct Struct {
     pub size: usize,
     pub name: String,
     pub content: Vec<u8>,
+    /* The first address which could not be read (EFAULT) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fault: Option<usize>,
 }
 
 impl Struct {
@@ -582,6 +626,7 @@ impl Struct {
             //name: name.to_string(),
             name: String::new(),
             content: Vec::new(),  // TODO: initialize with a default size?
+            fault: None,
         }
     }
 }
@@ -595,6 +640,7 @@ impl From<usize> for Struct {
             size: 0,
             name: String::new(),
             content: Vec::new(),
+            fault: None,
         }
     }
 }
@@ -604,7 +650,17 @@ impl DecodeArg for Struct {
     fn decode(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> { 
        // The best would be to know the structure for each struct and read / parse it.
        // For now read 4kB
-        self.content = operation.memory.read(pid, self.address, 4096);
+        self.fault = None;
+        self.content = match operation.memory.read(pid, self.address, 4096) {
+            Ok(mem) => mem,
+            // the structure may end before the fault
+            Err(OperationError::Fault { partial, .. }) if !partial.is_empty() => partial,
+            Err(OperationError::Fault { address, .. }) => {
+                self.fault = Some(address);
+                Vec::new()
+            },
+            Err(err) => return Err(err.into()),
+        };
         Ok(())
     }
 
@@ -621,7 +677,164 @@ impl EncodeArg for Struct {
 
     fn encode(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
         let mem = self.content.clone();
-        operation.memory.write(pid, self.address, mem);
+        operation.memory.write(pid, self.address, mem)?;
+        Ok(())
+    }
+}
+
+
+
+/*
+ * A kernel structure with a fixed memory layout, which can be parsed from and serialized
+ * back to the tracee memory.
+ */
+pub trait KernelStruct: Sized {
+    /* Size of the structure in the tracee memory */
+    const SIZE: usize;
+
+    fn from_bytes(bytes: &[u8]) -> Self;
+    fn to_bytes(&self) -> Vec<u8>;
+}
+
+
+/*
+ * Represent a pointer to a known structure in memory
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct TypedStruct<T> {
+    pub address: usize,
+    pub direction: Direction,
+    pub content: Option<T>,
+    /* The first address which could not be read (EFAULT) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fault: Option<usize>,
+}
+
+impl<T> TypedStruct<T> {
+    pub fn new(address: usize, direction: Direction) -> Self {
+        Self {
+            address,
+            direction,
+            content: None,
+            fault: None,
+        }
+    }
+}
+
+impl<T> From<usize> for TypedStruct<T> {
+    fn from(value: usize) -> Self
+    {
+        Self {
+            address: value,
+            direction: Direction::InOut,
+            content: None,
+            fault: None,
+        }
+    }
+}
+
+impl<T: KernelStruct> DecodeArg for TypedStruct<T> {
+
+    fn decode(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        // NULL pointers are valid for most optional structures (e.g., timeout)
+        if self.address == 0 {
+            self.content = None;
+            return Ok(());
+        }
+
+        let mem = read_pointed(pid, operation, self.address, T::SIZE, &mut self.fault)?;
+        self.content = match self.fault {
+            None => Some(T::from_bytes(&mem[..T::SIZE])),
+            Some(_) => None,
+        };
+        Ok(())
+    }
+}
+
+impl<T: KernelStruct> EncodeArg for TypedStruct<T> {
+
+    fn encode(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        if let Some(content) = &self.content {
+            operation.memory.write(pid, self.address, content.to_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+
+/*
+ * Represent an array of known structures in memory
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct TypedArray<T> {
+    pub address: usize,
+    pub direction: Direction,
+    pub count: usize,
+    pub content: Vec<T>,
+    /* The first address which could not be read (EFAULT) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fault: Option<usize>,
+}
+
+impl<T> TypedArray<T> {
+    pub fn new(address: usize, direction: Direction, count: usize) -> Self {
+        Self {
+            address,
+            direction,
+            count,
+            content: Vec::new(),
+            fault: None,
+        }
+    }
+}
+
+impl<T> From<usize> for TypedArray<T> {
+    fn from(value: usize) -> Self
+    {
+        Self {
+            address: value,
+            direction: Direction::InOut,
+            count: 0,
+            content: Vec::new(),
+            fault: None,
+        }
+    }
+}
+
+impl<T: KernelStruct> DecodeArg for TypedArray<T> {
+
+    fn decode(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.content.clear();
+        if self.address == 0 || self.count == 0 {
+            return Ok(());
+        }
+
+        // a count given by the tracee may not fit in memory, it cannot be read either
+        let size = match self.count.checked_mul(T::SIZE) {
+            Some(size) => size,
+            None => {
+                self.fault = Some(self.address);
+                return Ok(());
+            },
+        };
+        let mem = read_pointed(pid, operation, self.address, size, &mut self.fault)?;
+        self.content = mem.chunks_exact(T::SIZE)
+                          .take(self.count)
+                          .map(T::from_bytes)
+                          .collect();
+        Ok(())
+    }
+}
+
+impl<T: KernelStruct> EncodeArg for TypedArray<T> {
+
+    fn encode(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        let mem: Vec<u8> = self.content.iter()
+                                       .flat_map(|element| element.to_bytes())
+                                       .collect();
+        operation.memory.write(pid, self.address, mem)?;
         Ok(())
     }
 }
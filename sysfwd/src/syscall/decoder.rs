@@ -1,12 +1,16 @@
 /*
  * Decode syscall arguments.
  */
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+};
 
 use serde::{Serialize, Deserialize};
 
 use crate::{
     arch::Architecture,
+    error::Error,
     syscall::{ Syscall, syscalls },
     targets::operation::Operation,
 };
@@ -25,7 +29,7 @@ impl Decoder {
         }
     }
 
-    pub fn decode_entry(&self, syscall: &mut Syscall, pid: i32, operation: &Box<Operation>) {
+    pub fn decode_entry(&self, syscall: &mut Syscall, pid: i32, operation: &Box<Operation>) -> Result<(), Error> {
 
         // TODO: improve the match by using number instead of strings
         match self.arch.syscall_table.get_syscall_name(&syscall.raw.no) {
@@ -41,14 +45,30 @@ impl Decoder {
         /*
          * Second, iterate over the argument to decode them.
          */
-        self.decode_args(syscall, pid, operation);
+        self.decode_args(syscall, pid, operation)
         //syscall.entry_decoded = true;
     }
 
-    fn decode_args(&self, syscall: &mut Syscall, pid: i32, operation: &Box<Operation>) {
+    /*
+     * Only resolve the syscall name, without assigning types to the arguments nor reading the tracee memory.
+     */
+    pub fn decode_name(&self, syscall: &mut Syscall) {
+        match self.arch.syscall_table.get_syscall_name(&syscall.raw.no) {
+            Some(x) => syscall.name = x,
+            None => println!("No name found for {}", syscall.raw.no),
+        }
+    }
+
+    pub fn get_syscall_no(&self, name: &str) -> Option<usize> {
+        self.arch.syscall_table.get_syscall_no(name)
+    }
+
+    fn decode_args(&self, syscall: &mut Syscall, pid: i32, operation: &Box<Operation>) -> Result<(), Error> {
         if let Some(decoded_sc) = &mut syscall.decoded {
-                decoded_sc.decode_entry(pid, operation);
-            }
+            decoded_sc.decode_entry(pid, operation)
+                      .map_err(|source| Error::Decode { syscall: syscall.name.clone(), source })?;
+        }
+        Ok(())
     }
 
     fn parse_args(&self, syscall: &mut Syscall) {
@@ -145,12 +165,12 @@ impl Decoder {
 
             "getrandom" => { decode_syscall!(Getrandom, getrandom) },
 
-            "epollcreate"   => { decode_syscall!(EpollCreate, epoll) },
-            "epollcreate1"  => { decode_syscall!(EpollCreate1, epoll) },
-            "epollctl"      => { decode_syscall!(EpollCtl, epoll) },
-            "epollwait"     => { decode_syscall!(EpollWait, epoll) },
-            "epollpwait"    => { decode_syscall!(EpollPwait, epoll) },
-            "epollpwait2"   => { decode_syscall!(EpollPwait2, epoll) },
+            "epoll_create"  => { decode_syscall!(EpollCreate, epoll) },
+            "epoll_create1" => { decode_syscall!(EpollCreate1, epoll) },
+            "epoll_ctl"     => { decode_syscall!(EpollCtl, epoll) },
+            "epoll_wait"    => { decode_syscall!(EpollWait, epoll) },
+            "epoll_pwait"   => { decode_syscall!(EpollPwait, epoll) },
+            "epoll_pwait2"  => { decode_syscall!(EpollPwait2, epoll) },
 
             "get_robust_list" => { decode_syscall!(GetRobustList, robust_list) },
             "set_robust_list" => { decode_syscall!(SetRobustList, robust_list) },
@@ -159,18 +179,170 @@ impl Decoder {
 
             "exit_group"    => { decode_syscall!(ExitGroup, exit) },
 
+            "poll"            => { decode_syscall!(Poll, poll) },
+            "ppoll"           => { decode_syscall!(Ppoll, poll) },
+            "select"          => { decode_syscall!(Select, poll) },
+            "pselect6"        => { decode_syscall!(Pselect6, poll) },
+            "eventfd"         => { decode_syscall!(Eventfd, eventfd) },
+            "eventfd2"        => { decode_syscall!(Eventfd2, eventfd) },
+            "timerfd_create"  => { decode_syscall!(TimerfdCreate, timerfd) },
+            "timerfd_settime" => { decode_syscall!(TimerfdSettime, timerfd) },
+            "timerfd_gettime" => { decode_syscall!(TimerfdGettime, timerfd) },
+
+            "mkdir"      => { decode_syscall!(Mkdir, mkdir) },
+            "mkdirat"    => { decode_syscall!(Mkdirat, mkdir) },
+            "rmdir"      => { decode_syscall!(Rmdir, mkdir) },
+            "unlink"     => { decode_syscall!(Unlink, unlink) },
+            "unlinkat"   => { decode_syscall!(Unlinkat, unlink) },
+            "link"       => { decode_syscall!(Link, link) },
+            "linkat"     => { decode_syscall!(Linkat, link) },
+            "symlink"    => { decode_syscall!(Symlink, link) },
+            "symlinkat"  => { decode_syscall!(Symlinkat, link) },
+            "readlink"   => { decode_syscall!(Readlink, link) },
+            "readlinkat" => { decode_syscall!(Readlinkat, link) },
+            "chmod"      => { decode_syscall!(Chmod, chmod) },
+            "fchmod"     => { decode_syscall!(Fchmod, chmod) },
+            "fchmodat"   => { decode_syscall!(Fchmodat, chmod) },
+            "chown"      => { decode_syscall!(Chown, chmod) },
+            "fchown"     => { decode_syscall!(Fchown, chmod) },
+            "lchown"     => { decode_syscall!(Lchown, chmod) },
+            "fchownat"   => { decode_syscall!(Fchownat, chmod) },
+            "utimensat"  => { decode_syscall!(Utimensat, utimensat) },
+            "getcwd"     => { decode_syscall!(Getcwd, cwd) },
+            "chdir"      => { decode_syscall!(Chdir, cwd) },
+            "fchdir"     => { decode_syscall!(Fchdir, cwd) },
+            "statfs"     => { decode_syscall!(Statfs, statfs) },
+            "fstatfs"    => { decode_syscall!(Fstatfs, statfs) },
+            "sync"       => { decode_syscall!(Sync, fsync) },
+            "fsync"      => { decode_syscall!(Fsync, fsync) },
+            "fdatasync"  => { decode_syscall!(Fdatasync, fsync) },
+
+            "setxattr"     => { decode_syscall!(Setxattr, xattr) },
+            "lsetxattr"    => { decode_syscall!(Lsetxattr, xattr) },
+            "fsetxattr"    => { decode_syscall!(Fsetxattr, xattr) },
+            "getxattr"     => { decode_syscall!(Getxattr, xattr) },
+            "lgetxattr"    => { decode_syscall!(Lgetxattr, xattr) },
+            "fgetxattr"    => { decode_syscall!(Fgetxattr, xattr) },
+            "listxattr"    => { decode_syscall!(Listxattr, xattr) },
+            "llistxattr"   => { decode_syscall!(Llistxattr, xattr) },
+            "flistxattr"   => { decode_syscall!(Flistxattr, xattr) },
+            "removexattr"  => { decode_syscall!(Removexattr, xattr) },
+            "lremovexattr" => { decode_syscall!(Lremovexattr, xattr) },
+            "fremovexattr" => { decode_syscall!(Fremovexattr, xattr) },
+
+            "futex"                  => { decode_syscall!(Futex, futex) },
+            "futex_waitv"            => { decode_syscall!(FutexWaitv, futex) },
+            "sched_yield"            => { decode_syscall!(SchedYield, sched) },
+            "sched_setparam"         => { decode_syscall!(SchedSetparam, sched) },
+            "sched_getparam"         => { decode_syscall!(SchedGetparam, sched) },
+            "sched_setscheduler"     => { decode_syscall!(SchedSetscheduler, sched) },
+            "sched_getscheduler"     => { decode_syscall!(SchedGetscheduler, sched) },
+            "sched_get_priority_max" => { decode_syscall!(SchedGetPriorityMax, sched) },
+            "sched_get_priority_min" => { decode_syscall!(SchedGetPriorityMin, sched) },
+            "sched_rr_get_interval"  => { decode_syscall!(SchedRrGetInterval, sched) },
+            "sched_setaffinity"      => { decode_syscall!(SchedSetaffinity, sched) },
+            "sched_getaffinity"      => { decode_syscall!(SchedGetaffinity, sched) },
+            "sched_setattr"          => { decode_syscall!(SchedSetattr, sched) },
+            "sched_getattr"          => { decode_syscall!(SchedGetattr, sched) },
+
+            "shmget"     => { decode_syscall!(Shmget, shm) },
+            "shmat"      => { decode_syscall!(Shmat, shm) },
+            "shmdt"      => { decode_syscall!(Shmdt, shm) },
+            "shmctl"     => { decode_syscall!(Shmctl, shm) },
+            "semget"     => { decode_syscall!(Semget, sem) },
+            "semop"      => { decode_syscall!(Semop, sem) },
+            "semtimedop" => { decode_syscall!(Semtimedop, sem) },
+            "semctl"     => { decode_syscall!(Semctl, sem) },
+            "msgget"     => { decode_syscall!(Msgget, msg) },
+            "msgsnd"     => { decode_syscall!(Msgsnd, msg) },
+            "msgrcv"     => { decode_syscall!(Msgrcv, msg) },
+            "msgctl"     => { decode_syscall!(Msgctl, msg) },
+
+            "time"             => { decode_syscall!(Time, time) },
+            "gettimeofday"     => { decode_syscall!(Gettimeofday, time) },
+            "settimeofday"     => { decode_syscall!(Settimeofday, time) },
+            "times"            => { decode_syscall!(Times, time) },
+            "nanosleep"        => { decode_syscall!(Nanosleep, time) },
+            "clock_settime"    => { decode_syscall!(ClockSettime, time) },
+            "clock_gettime"    => { decode_syscall!(ClockGettime, time) },
+            "clock_getres"     => { decode_syscall!(ClockGetres, time) },
+            "clock_nanosleep"  => { decode_syscall!(ClockNanosleep, time) },
+            "alarm"            => { decode_syscall!(Alarm, timer) },
+            "getitimer"        => { decode_syscall!(Getitimer, timer) },
+            "setitimer"        => { decode_syscall!(Setitimer, timer) },
+            "timer_create"     => { decode_syscall!(TimerCreate, timer) },
+            "timer_settime"    => { decode_syscall!(TimerSettime, timer) },
+            "timer_gettime"    => { decode_syscall!(TimerGettime, timer) },
+            "timer_getoverrun" => { decode_syscall!(TimerGetoverrun, timer) },
+            "timer_delete"     => { decode_syscall!(TimerDelete, timer) },
+
+            "io_uring_setup"    => { decode_syscall!(IoUringSetup, io_uring) },
+            "io_uring_enter"    => { decode_syscall!(IoUringEnter, io_uring) },
+            "io_uring_register" => { decode_syscall!(IoUringRegister, io_uring) },
+
             _ => (),
         }
     }
 
 
-    pub fn decode_exit(&self, syscall: &mut Syscall, pid: i32, operation: &Box<Operation>) {
+    pub fn decode_exit(&self, syscall: &mut Syscall, pid: i32, operation: &Box<Operation>) -> Result<(), Error> {
 
         /* Decode return value */
         if let Some(decoded_sc) = &mut syscall.decoded {
-            decoded_sc.decode_exit(syscall.raw.retval, pid, operation).unwrap();
+            decoded_sc.decode_exit(syscall.raw.retval, pid, operation)
+                      .map_err(|source| Error::Decode { syscall: syscall.name.clone(), source })?;
+        }
+        Ok(())
+    }
+}
+
+
+
+/*
+ * How much of a syscall is decoded.
+ * Decoding the arguments reads the tracee memory for each pointer, which is costly.
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeLevel {
+    /* Only the RawSyscall */
+    Raw,
+    /* The RawSyscall and the syscall name */
+    Name,
+    /* The arguments, including the memory they point to */
+    Full,
+}
+
+/*
+ * The decode level for each syscall number, and the level of the other syscalls.
+ */
+#[derive(Clone, Debug)]
+pub struct DecodePolicy {
+    default: DecodeLevel,
+    levels: HashMap<usize, DecodeLevel>,
+}
+
+impl DecodePolicy {
+    pub fn new(default: DecodeLevel) -> Self {
+        Self {
+            default,
+            levels: HashMap::new(),
         }
+    }
+
+    pub fn set_level(&mut self, no: usize, level: DecodeLevel) {
+        self.levels.insert(no, level);
+    }
 
+    pub fn level(&self, no: usize) -> DecodeLevel {
+        *self.levels.get(&no).unwrap_or(&self.default)
+    }
+}
+
+impl Default for DecodePolicy {
+    /* Decode everything */
+    fn default() -> Self {
+        Self::new(DecodeLevel::Full)
     }
 }
 
@@ -186,7 +358,9 @@ pub trait DecodeArg {
 pub trait DecodeEntry {
     //fn as_any(&self) -> &dyn Any;
     #[allow(unused_variables)]
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) { }
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        Ok(())
+    }
     fn print(&self) { }
 }
 
@@ -297,6 +471,114 @@ pub enum DecodedSyscall {
 
     ExitGroup(syscalls::exit::ExitGroup),
 
+    /* poll, select and event notification fds */
+    Poll(syscalls::poll::Poll),
+    Ppoll(syscalls::poll::Ppoll),
+    Select(syscalls::poll::Select),
+    Pselect6(syscalls::poll::Pselect6),
+    Eventfd(syscalls::eventfd::Eventfd),
+    Eventfd2(syscalls::eventfd::Eventfd2),
+    TimerfdCreate(syscalls::timerfd::TimerfdCreate),
+    TimerfdSettime(syscalls::timerfd::TimerfdSettime),
+    TimerfdGettime(syscalls::timerfd::TimerfdGettime),
+
+    /* filesystem metadata */
+    Mkdir(syscalls::mkdir::Mkdir),
+    Mkdirat(syscalls::mkdir::Mkdirat),
+    Rmdir(syscalls::mkdir::Rmdir),
+    Unlink(syscalls::unlink::Unlink),
+    Unlinkat(syscalls::unlink::Unlinkat),
+    Link(syscalls::link::Link),
+    Linkat(syscalls::link::Linkat),
+    Symlink(syscalls::link::Symlink),
+    Symlinkat(syscalls::link::Symlinkat),
+    Readlink(syscalls::link::Readlink),
+    Readlinkat(syscalls::link::Readlinkat),
+    Chmod(syscalls::chmod::Chmod),
+    Fchmod(syscalls::chmod::Fchmod),
+    Fchmodat(syscalls::chmod::Fchmodat),
+    Chown(syscalls::chmod::Chown),
+    Fchown(syscalls::chmod::Fchown),
+    Lchown(syscalls::chmod::Lchown),
+    Fchownat(syscalls::chmod::Fchownat),
+    Utimensat(syscalls::utimensat::Utimensat),
+    Getcwd(syscalls::cwd::Getcwd),
+    Chdir(syscalls::cwd::Chdir),
+    Fchdir(syscalls::cwd::Fchdir),
+    Statfs(syscalls::statfs::Statfs),
+    Fstatfs(syscalls::statfs::Fstatfs),
+    Sync(syscalls::fsync::Sync),
+    Fsync(syscalls::fsync::Fsync),
+    Fdatasync(syscalls::fsync::Fdatasync),
+
+    /* extended attributes */
+    Setxattr(syscalls::xattr::Setxattr),
+    Lsetxattr(syscalls::xattr::Lsetxattr),
+    Fsetxattr(syscalls::xattr::Fsetxattr),
+    Getxattr(syscalls::xattr::Getxattr),
+    Lgetxattr(syscalls::xattr::Lgetxattr),
+    Fgetxattr(syscalls::xattr::Fgetxattr),
+    Listxattr(syscalls::xattr::Listxattr),
+    Llistxattr(syscalls::xattr::Llistxattr),
+    Flistxattr(syscalls::xattr::Flistxattr),
+    Removexattr(syscalls::xattr::Removexattr),
+    Lremovexattr(syscalls::xattr::Lremovexattr),
+    Fremovexattr(syscalls::xattr::Fremovexattr),
+
+    /* futex and scheduling */
+    Futex(syscalls::futex::Futex),
+    FutexWaitv(syscalls::futex::FutexWaitv),
+    SchedYield(syscalls::sched::SchedYield),
+    SchedSetparam(syscalls::sched::SchedSetparam),
+    SchedGetparam(syscalls::sched::SchedGetparam),
+    SchedSetscheduler(syscalls::sched::SchedSetscheduler),
+    SchedGetscheduler(syscalls::sched::SchedGetscheduler),
+    SchedGetPriorityMax(syscalls::sched::SchedGetPriorityMax),
+    SchedGetPriorityMin(syscalls::sched::SchedGetPriorityMin),
+    SchedRrGetInterval(syscalls::sched::SchedRrGetInterval),
+    SchedSetaffinity(syscalls::sched::SchedSetaffinity),
+    SchedGetaffinity(syscalls::sched::SchedGetaffinity),
+    SchedSetattr(syscalls::sched::SchedSetattr),
+    SchedGetattr(syscalls::sched::SchedGetattr),
+
+    /* System V IPC */
+    Shmget(syscalls::shm::Shmget),
+    Shmat(syscalls::shm::Shmat),
+    Shmdt(syscalls::shm::Shmdt),
+    Shmctl(syscalls::shm::Shmctl),
+    Semget(syscalls::sem::Semget),
+    Semop(syscalls::sem::Semop),
+    Semtimedop(syscalls::sem::Semtimedop),
+    Semctl(syscalls::sem::Semctl),
+    Msgget(syscalls::msg::Msgget),
+    Msgsnd(syscalls::msg::Msgsnd),
+    Msgrcv(syscalls::msg::Msgrcv),
+    Msgctl(syscalls::msg::Msgctl),
+
+    /* time and clocks */
+    Time(syscalls::time::Time),
+    Gettimeofday(syscalls::time::Gettimeofday),
+    Settimeofday(syscalls::time::Settimeofday),
+    Times(syscalls::time::Times),
+    Nanosleep(syscalls::time::Nanosleep),
+    ClockSettime(syscalls::time::ClockSettime),
+    ClockGettime(syscalls::time::ClockGettime),
+    ClockGetres(syscalls::time::ClockGetres),
+    ClockNanosleep(syscalls::time::ClockNanosleep),
+    Alarm(syscalls::timer::Alarm),
+    Getitimer(syscalls::timer::Getitimer),
+    Setitimer(syscalls::timer::Setitimer),
+    TimerCreate(syscalls::timer::TimerCreate),
+    TimerSettime(syscalls::timer::TimerSettime),
+    TimerGettime(syscalls::timer::TimerGettime),
+    TimerGetoverrun(syscalls::timer::TimerGetoverrun),
+    TimerDelete(syscalls::timer::TimerDelete),
+
+    /* io_uring */
+    IoUringSetup(syscalls::io_uring::IoUringSetup),
+    IoUringEnter(syscalls::io_uring::IoUringEnter),
+    IoUringRegister(syscalls::io_uring::IoUringRegister),
+
     /* ... */
 }
 
@@ -304,81 +586,175 @@ impl DecodedSyscall {
     pub fn get_syscall(&self) -> &dyn std::any::Any
     {
         match self {
-            DecodedSyscall::Close(sc) => return sc,
-            DecodedSyscall::Creat(sc) => return sc,
-            DecodedSyscall::Open(sc) => return sc,
-            DecodedSyscall::Openat(sc) => return sc,
-            DecodedSyscall::Openat2(sc) => return sc,
-            DecodedSyscall::Read(sc) => return sc,
-            DecodedSyscall::Write(sc) => return sc,
-            DecodedSyscall::Readv(sc) => return sc,
-            DecodedSyscall::Writev(sc) => return sc,
-            DecodedSyscall::Pread(sc) => return sc,
-            DecodedSyscall::Pwrite(sc) => return sc,
-            DecodedSyscall::Preadv(sc) => return sc,
-            DecodedSyscall::Pwritev(sc) => return sc,
-            DecodedSyscall::Preadv2(sc) => return sc,
-            DecodedSyscall::Pwritev2(sc) => return sc,
-            DecodedSyscall::Ioctl(sc) => return sc,
-            DecodedSyscall::Brk(sc) => return sc,
-            DecodedSyscall::Sbrk(sc) => return sc,
-            DecodedSyscall::Mmap(sc) => return sc,
-            DecodedSyscall::Mremap(sc) => return sc,
-            DecodedSyscall::Munmap(sc) => return sc,
-            DecodedSyscall::Mprotect(sc) => return sc,
-            DecodedSyscall::Madvise(sc) => return sc,
-            DecodedSyscall::Execve(sc) => return sc,
-            DecodedSyscall::Execveat(sc) => return sc,
-            DecodedSyscall::Fallocate(sc) => return sc,
-            DecodedSyscall::NameToHandleAt(sc) => return sc,
-            DecodedSyscall::OpenByHandleAt(sc) => return sc,
-            DecodedSyscall::MemfdCreate(sc) => return sc,
-            DecodedSyscall::Mknod(sc) => return sc,
-            DecodedSyscall::Mknodat(sc) => return sc,
-            DecodedSyscall::Rename(sc) => return sc,
-            DecodedSyscall::Renameat(sc) => return sc,
-            DecodedSyscall::Renameat2(sc) => return sc,
-            DecodedSyscall::Truncate(sc) => return sc,
-            DecodedSyscall::Ftruncate(sc) => return sc,
-            DecodedSyscall::Access(sc) => return sc,
-            DecodedSyscall::Faccessat(sc) => return sc,
-            DecodedSyscall::Faccessat2(sc) => return sc,
-            DecodedSyscall::Prctl(sc) => return sc,
-            DecodedSyscall::ArchPrctl(sc) => return sc,
-            DecodedSyscall::Getdents(sc) => return sc,
-            DecodedSyscall::Getdents64(sc) => return sc,
-            DecodedSyscall::Readdir(sc) => return sc,
-            DecodedSyscall::Stat(sc) => return sc,
-            DecodedSyscall::Fstat(sc) => return sc,
-            DecodedSyscall::Lstat(sc) => return sc,
-            DecodedSyscall::Fstatat(sc) => return sc,
-            DecodedSyscall::Statx(sc) => return sc,
-            DecodedSyscall::Getrlimit(sc) => return sc,
-            DecodedSyscall::Setrlimit(sc) => return sc,
-            DecodedSyscall::Prlimit(sc) => return sc,
-            DecodedSyscall::Prlimit64(sc) => return sc,
-            DecodedSyscall::Getrusage(sc) => return sc,
-            DecodedSyscall::Rseq(sc) => return sc,
-            DecodedSyscall::Getrandom(sc) => return sc,
-            DecodedSyscall::EpollCreate(sc) => return sc,
-            DecodedSyscall::EpollCreate1(sc) => return sc,
-            DecodedSyscall::EpollCtl(sc) => return sc,
-            DecodedSyscall::EpollWait(sc) => return sc,
-            DecodedSyscall::EpollPwait(sc) => return sc,
-            DecodedSyscall::EpollPwait2(sc) => return sc,
-            DecodedSyscall::SetTidAddress(sc) => return sc,
-            DecodedSyscall::GetRobustList(sc) => return sc,
-            DecodedSyscall::SetRobustList(sc) => return sc,
-            DecodedSyscall::Lseek(sc) => return sc,
-            DecodedSyscall::Llseek(sc) => return sc,
-            DecodedSyscall::ExitGroup(sc) => return sc,
+            DecodedSyscall::Close(sc) => sc,
+            DecodedSyscall::Creat(sc) => sc,
+            DecodedSyscall::Open(sc) => sc,
+            DecodedSyscall::Openat(sc) => sc,
+            DecodedSyscall::Openat2(sc) => sc,
+            DecodedSyscall::Read(sc) => sc,
+            DecodedSyscall::Write(sc) => sc,
+            DecodedSyscall::Readv(sc) => sc,
+            DecodedSyscall::Writev(sc) => sc,
+            DecodedSyscall::Pread(sc) => sc,
+            DecodedSyscall::Pwrite(sc) => sc,
+            DecodedSyscall::Preadv(sc) => sc,
+            DecodedSyscall::Pwritev(sc) => sc,
+            DecodedSyscall::Preadv2(sc) => sc,
+            DecodedSyscall::Pwritev2(sc) => sc,
+            DecodedSyscall::Ioctl(sc) => sc,
+            DecodedSyscall::Brk(sc) => sc,
+            DecodedSyscall::Sbrk(sc) => sc,
+            DecodedSyscall::Mmap(sc) => sc,
+            DecodedSyscall::Mremap(sc) => sc,
+            DecodedSyscall::Munmap(sc) => sc,
+            DecodedSyscall::Mprotect(sc) => sc,
+            DecodedSyscall::Madvise(sc) => sc,
+            DecodedSyscall::Execve(sc) => sc,
+            DecodedSyscall::Execveat(sc) => sc,
+            DecodedSyscall::Fallocate(sc) => sc,
+            DecodedSyscall::NameToHandleAt(sc) => sc,
+            DecodedSyscall::OpenByHandleAt(sc) => sc,
+            DecodedSyscall::MemfdCreate(sc) => sc,
+            DecodedSyscall::Mknod(sc) => sc,
+            DecodedSyscall::Mknodat(sc) => sc,
+            DecodedSyscall::Rename(sc) => sc,
+            DecodedSyscall::Renameat(sc) => sc,
+            DecodedSyscall::Renameat2(sc) => sc,
+            DecodedSyscall::Truncate(sc) => sc,
+            DecodedSyscall::Ftruncate(sc) => sc,
+            DecodedSyscall::Access(sc) => sc,
+            DecodedSyscall::Faccessat(sc) => sc,
+            DecodedSyscall::Faccessat2(sc) => sc,
+            DecodedSyscall::Prctl(sc) => sc,
+            DecodedSyscall::ArchPrctl(sc) => sc,
+            DecodedSyscall::Getdents(sc) => sc,
+            DecodedSyscall::Getdents64(sc) => sc,
+            DecodedSyscall::Readdir(sc) => sc,
+            DecodedSyscall::Stat(sc) => sc,
+            DecodedSyscall::Fstat(sc) => sc,
+            DecodedSyscall::Lstat(sc) => sc,
+            DecodedSyscall::Fstatat(sc) => sc,
+            DecodedSyscall::Statx(sc) => sc,
+            DecodedSyscall::Getrlimit(sc) => sc,
+            DecodedSyscall::Setrlimit(sc) => sc,
+            DecodedSyscall::Prlimit(sc) => sc,
+            DecodedSyscall::Prlimit64(sc) => sc,
+            DecodedSyscall::Getrusage(sc) => sc,
+            DecodedSyscall::Rseq(sc) => sc,
+            DecodedSyscall::Getrandom(sc) => sc,
+            DecodedSyscall::EpollCreate(sc) => sc,
+            DecodedSyscall::EpollCreate1(sc) => sc,
+            DecodedSyscall::EpollCtl(sc) => sc,
+            DecodedSyscall::EpollWait(sc) => sc,
+            DecodedSyscall::EpollPwait(sc) => sc,
+            DecodedSyscall::EpollPwait2(sc) => sc,
+            DecodedSyscall::SetTidAddress(sc) => sc,
+            DecodedSyscall::GetRobustList(sc) => sc,
+            DecodedSyscall::SetRobustList(sc) => sc,
+            DecodedSyscall::Lseek(sc) => sc,
+            DecodedSyscall::Llseek(sc) => sc,
+            DecodedSyscall::ExitGroup(sc) => sc,
+            DecodedSyscall::IoUringSetup(sc) => sc,
+            DecodedSyscall::IoUringEnter(sc) => sc,
+            DecodedSyscall::IoUringRegister(sc) => sc,
+            DecodedSyscall::Time(sc) => sc,
+            DecodedSyscall::Gettimeofday(sc) => sc,
+            DecodedSyscall::Settimeofday(sc) => sc,
+            DecodedSyscall::Times(sc) => sc,
+            DecodedSyscall::Nanosleep(sc) => sc,
+            DecodedSyscall::ClockSettime(sc) => sc,
+            DecodedSyscall::ClockGettime(sc) => sc,
+            DecodedSyscall::ClockGetres(sc) => sc,
+            DecodedSyscall::ClockNanosleep(sc) => sc,
+            DecodedSyscall::Alarm(sc) => sc,
+            DecodedSyscall::Getitimer(sc) => sc,
+            DecodedSyscall::Setitimer(sc) => sc,
+            DecodedSyscall::TimerCreate(sc) => sc,
+            DecodedSyscall::TimerSettime(sc) => sc,
+            DecodedSyscall::TimerGettime(sc) => sc,
+            DecodedSyscall::TimerGetoverrun(sc) => sc,
+            DecodedSyscall::TimerDelete(sc) => sc,
+            DecodedSyscall::Shmget(sc) => sc,
+            DecodedSyscall::Shmat(sc) => sc,
+            DecodedSyscall::Shmdt(sc) => sc,
+            DecodedSyscall::Shmctl(sc) => sc,
+            DecodedSyscall::Semget(sc) => sc,
+            DecodedSyscall::Semop(sc) => sc,
+            DecodedSyscall::Semtimedop(sc) => sc,
+            DecodedSyscall::Semctl(sc) => sc,
+            DecodedSyscall::Msgget(sc) => sc,
+            DecodedSyscall::Msgsnd(sc) => sc,
+            DecodedSyscall::Msgrcv(sc) => sc,
+            DecodedSyscall::Msgctl(sc) => sc,
+            DecodedSyscall::Futex(sc) => sc,
+            DecodedSyscall::FutexWaitv(sc) => sc,
+            DecodedSyscall::SchedYield(sc) => sc,
+            DecodedSyscall::SchedSetparam(sc) => sc,
+            DecodedSyscall::SchedGetparam(sc) => sc,
+            DecodedSyscall::SchedSetscheduler(sc) => sc,
+            DecodedSyscall::SchedGetscheduler(sc) => sc,
+            DecodedSyscall::SchedGetPriorityMax(sc) => sc,
+            DecodedSyscall::SchedGetPriorityMin(sc) => sc,
+            DecodedSyscall::SchedRrGetInterval(sc) => sc,
+            DecodedSyscall::SchedSetaffinity(sc) => sc,
+            DecodedSyscall::SchedGetaffinity(sc) => sc,
+            DecodedSyscall::SchedSetattr(sc) => sc,
+            DecodedSyscall::SchedGetattr(sc) => sc,
+            DecodedSyscall::Setxattr(sc) => sc,
+            DecodedSyscall::Lsetxattr(sc) => sc,
+            DecodedSyscall::Fsetxattr(sc) => sc,
+            DecodedSyscall::Getxattr(sc) => sc,
+            DecodedSyscall::Lgetxattr(sc) => sc,
+            DecodedSyscall::Fgetxattr(sc) => sc,
+            DecodedSyscall::Listxattr(sc) => sc,
+            DecodedSyscall::Llistxattr(sc) => sc,
+            DecodedSyscall::Flistxattr(sc) => sc,
+            DecodedSyscall::Removexattr(sc) => sc,
+            DecodedSyscall::Lremovexattr(sc) => sc,
+            DecodedSyscall::Fremovexattr(sc) => sc,
+            DecodedSyscall::Mkdir(sc) => sc,
+            DecodedSyscall::Mkdirat(sc) => sc,
+            DecodedSyscall::Rmdir(sc) => sc,
+            DecodedSyscall::Unlink(sc) => sc,
+            DecodedSyscall::Unlinkat(sc) => sc,
+            DecodedSyscall::Link(sc) => sc,
+            DecodedSyscall::Linkat(sc) => sc,
+            DecodedSyscall::Symlink(sc) => sc,
+            DecodedSyscall::Symlinkat(sc) => sc,
+            DecodedSyscall::Readlink(sc) => sc,
+            DecodedSyscall::Readlinkat(sc) => sc,
+            DecodedSyscall::Chmod(sc) => sc,
+            DecodedSyscall::Fchmod(sc) => sc,
+            DecodedSyscall::Fchmodat(sc) => sc,
+            DecodedSyscall::Chown(sc) => sc,
+            DecodedSyscall::Fchown(sc) => sc,
+            DecodedSyscall::Lchown(sc) => sc,
+            DecodedSyscall::Fchownat(sc) => sc,
+            DecodedSyscall::Utimensat(sc) => sc,
+            DecodedSyscall::Getcwd(sc) => sc,
+            DecodedSyscall::Chdir(sc) => sc,
+            DecodedSyscall::Fchdir(sc) => sc,
+            DecodedSyscall::Statfs(sc) => sc,
+            DecodedSyscall::Fstatfs(sc) => sc,
+            DecodedSyscall::Sync(sc) => sc,
+            DecodedSyscall::Fsync(sc) => sc,
+            DecodedSyscall::Fdatasync(sc) => sc,
+            DecodedSyscall::Poll(sc) => sc,
+            DecodedSyscall::Ppoll(sc) => sc,
+            DecodedSyscall::Select(sc) => sc,
+            DecodedSyscall::Pselect6(sc) => sc,
+            DecodedSyscall::Eventfd(sc) => sc,
+            DecodedSyscall::Eventfd2(sc) => sc,
+            DecodedSyscall::TimerfdCreate(sc) => sc,
+            DecodedSyscall::TimerfdSettime(sc) => sc,
+            DecodedSyscall::TimerfdGettime(sc) => sc,
         }
 
     }
 }
 
 impl DecodeEntry for DecodedSyscall {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
         /* Why not match on the syscall name? */
         match self {
             DecodedSyscall::Close(x) => x.decode_entry(pid, operation),
@@ -449,6 +825,100 @@ impl DecodeEntry for DecodedSyscall {
             DecodedSyscall::Lseek(x) => x.decode_entry(pid, operation),
             DecodedSyscall::Llseek(x) => x.decode_entry(pid, operation),
             DecodedSyscall::ExitGroup(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Poll(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Ppoll(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Select(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Pselect6(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Eventfd(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Eventfd2(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::TimerfdCreate(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::TimerfdSettime(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::TimerfdGettime(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Mkdir(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Mkdirat(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Rmdir(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Unlink(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Unlinkat(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Link(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Linkat(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Symlink(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Symlinkat(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Readlink(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Readlinkat(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Chmod(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Fchmod(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Fchmodat(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Chown(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Fchown(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Lchown(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Fchownat(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Utimensat(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Getcwd(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Chdir(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Fchdir(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Statfs(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Fstatfs(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Sync(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Fsync(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Fdatasync(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Setxattr(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Lsetxattr(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Fsetxattr(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Getxattr(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Lgetxattr(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Fgetxattr(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Listxattr(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Llistxattr(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Flistxattr(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Removexattr(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Lremovexattr(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Fremovexattr(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Futex(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::FutexWaitv(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::SchedYield(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::SchedSetparam(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::SchedGetparam(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::SchedSetscheduler(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::SchedGetscheduler(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::SchedGetPriorityMax(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::SchedGetPriorityMin(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::SchedRrGetInterval(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::SchedSetaffinity(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::SchedGetaffinity(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::SchedSetattr(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::SchedGetattr(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Shmget(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Shmat(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Shmdt(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Shmctl(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Semget(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Semop(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Semtimedop(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Semctl(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Msgget(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Msgsnd(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Msgrcv(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Msgctl(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Time(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Gettimeofday(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Settimeofday(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Times(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Nanosleep(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::ClockSettime(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::ClockGettime(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::ClockGetres(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::ClockNanosleep(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Alarm(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Getitimer(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::Setitimer(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::TimerCreate(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::TimerSettime(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::TimerGettime(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::TimerGetoverrun(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::TimerDelete(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::IoUringSetup(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::IoUringEnter(x) => x.decode_entry(pid, operation),
+            DecodedSyscall::IoUringRegister(x) => x.decode_entry(pid, operation),
             //DecodedSyscall::(x) => x.decode_entry(pid, operation),
         }
     }
@@ -524,6 +994,100 @@ impl DecodeExit for DecodedSyscall {
             DecodedSyscall::SetRobustList(x) => x.decode_exit(value, pid, operation),
             DecodedSyscall::Lseek(x) => x.decode_exit(value, pid, operation),
             DecodedSyscall::Llseek(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Poll(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Ppoll(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Select(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Pselect6(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Eventfd(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Eventfd2(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::TimerfdCreate(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::TimerfdSettime(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::TimerfdGettime(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Mkdir(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Mkdirat(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Rmdir(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Unlink(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Unlinkat(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Link(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Linkat(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Symlink(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Symlinkat(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Readlink(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Readlinkat(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Chmod(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Fchmod(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Fchmodat(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Chown(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Fchown(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Lchown(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Fchownat(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Utimensat(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Getcwd(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Chdir(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Fchdir(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Statfs(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Fstatfs(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Sync(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Fsync(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Fdatasync(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Setxattr(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Lsetxattr(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Fsetxattr(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Getxattr(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Lgetxattr(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Fgetxattr(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Listxattr(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Llistxattr(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Flistxattr(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Removexattr(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Lremovexattr(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Fremovexattr(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Futex(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::FutexWaitv(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::SchedYield(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::SchedSetparam(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::SchedGetparam(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::SchedSetscheduler(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::SchedGetscheduler(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::SchedGetPriorityMax(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::SchedGetPriorityMin(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::SchedRrGetInterval(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::SchedSetaffinity(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::SchedGetaffinity(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::SchedSetattr(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::SchedGetattr(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Shmget(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Shmat(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Shmdt(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Shmctl(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Semget(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Semop(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Semtimedop(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Semctl(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Msgget(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Msgsnd(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Msgrcv(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Msgctl(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Time(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Gettimeofday(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Settimeofday(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Times(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Nanosleep(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::ClockSettime(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::ClockGettime(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::ClockGetres(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::ClockNanosleep(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Alarm(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Getitimer(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::Setitimer(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::TimerCreate(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::TimerSettime(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::TimerGettime(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::TimerGetoverrun(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::TimerDelete(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::IoUringSetup(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::IoUringEnter(x) => x.decode_exit(value, pid, operation),
+            DecodedSyscall::IoUringRegister(x) => x.decode_exit(value, pid, operation),
             DecodedSyscall::ExitGroup(_) => Ok(()),
             //_ => panic!("oops"),
             //DecodedSyscall::(x) => x.decode_exit(value, pid, operation),
@@ -52,6 +52,58 @@ impl EncodeEntry for DecodedSyscall {
             DecodedSyscall::Read(x) => x.encode_entry(raw, pid, operation),
             DecodedSyscall::Write(x) => x.encode_entry(raw, pid, operation),
             DecodedSyscall::Lseek(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Mkdir(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Mkdirat(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Rmdir(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Unlink(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Unlinkat(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Link(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Linkat(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Symlink(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Symlinkat(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Readlink(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Readlinkat(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Chmod(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Fchmod(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Fchmodat(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Chown(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Fchown(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Lchown(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Fchownat(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Utimensat(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Getcwd(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Chdir(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Fchdir(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Statfs(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Fstatfs(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Sync(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Fsync(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Fdatasync(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Setxattr(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Lsetxattr(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Fsetxattr(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Getxattr(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Lgetxattr(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Fgetxattr(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Listxattr(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Llistxattr(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Flistxattr(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Removexattr(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Lremovexattr(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Fremovexattr(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Shmget(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Shmctl(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Semget(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Semop(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Semtimedop(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Semctl(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Msgget(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Msgsnd(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Msgrcv(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::Msgctl(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::IoUringSetup(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::IoUringEnter(x) => x.encode_entry(raw, pid, operation),
+            DecodedSyscall::IoUringRegister(x) => x.encode_entry(raw, pid, operation),
             //DecodedSyscall::(sysforward/src/tracer/decision_handler.rsx) => x.encode_entry(raw, pid, operation),
             _ => Err(std::io::Error::new(std::io::ErrorKind::Other, "Encode trait not implemented for this syscall")),
         }
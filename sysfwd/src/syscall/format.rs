@@ -0,0 +1,473 @@
+/*
+ * Human-readable rendering of syscalls, in the same spirit as strace:
+ *     openat(AT_FDCWD, "/dev/kbuf", O_RDWR) = 4100 <forwarded>
+ *
+ * The decoded syscall is walked through its serde representation, so every decoded syscall
+ * is rendered without a dedicated formatter. Only some arguments are rendered symbolically.
+ */
+use nix::errno::Errno;
+use serde::{ Serialize, Deserialize };
+use serde_json::{ Map, Value };
+
+use crate::{
+    memory::unwind::Frame,
+    syscall::{ Syscall, Timestamps },
+};
+
+
+
+/*
+ * The formats available to log syscalls
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    /* The whole Syscall structure serialized in JSON */
+    Json,
+    /* One line per syscall, as strace does */
+    Strace,
+}
+
+
+/* Same default as strace -s */
+const MAX_STRLEN: usize = 32;
+
+/* The kernel returns errors as -errno in the range [-4095, -1] */
+const MAX_ERRNO: isize = 4095;
+
+const AT_FDCWD: i32 = -100;
+
+
+/*
+ * Render a syscall in one line, with its return value.
+ * The syscalls executed by the executor are marked as forwarded.
+ */
+pub fn format_strace(syscall: &Syscall, forwarded: bool) -> String
+{
+    let name = match syscall.name.is_empty() {
+        true => format!("syscall_{:#x}", syscall.raw.no),
+        false => syscall.name.clone(),
+    };
+
+    let (args, retval) = match serde_json::to_value(&syscall.decoded) {
+        Ok(Value::Object(variant)) => {
+            // DecodedSyscall is serialized as { "Variant": { fields... } }
+            match variant.into_iter().next() {
+                Some((_, Value::Object(fields))) => format_fields(syscall, &fields),
+                _ => format_raw_args(syscall),
+            }
+        },
+        _ => format_raw_args(syscall),
+    };
+
+    let mut line = format!("{}({}) = {}", name, args.join(", "), retval);
+    if forwarded {
+        line.push_str(" <forwarded>");
+    }
+    line
+}
+
+/*
+ * Render the time spent by the syscall, as strace -T does, in seconds:
+ *     <0.000412 forward=0.000398 executor=0.000021 transport=0.000377>
+ * The forwarding latency is split between the executor and the transport.
+ */
+pub fn format_timings(timestamps: &Timestamps) -> String
+{
+    let total = match timestamps.total() {
+        Some(total) => total,
+        None => return String::new(),
+    };
+
+    let mut timings = format!(" <{}", format_duration(total));
+    let durations = [
+        ("forward", timestamps.forward()),
+        ("executor", timestamps.executor()),
+        ("transport", timestamps.transport()),
+    ];
+    for (name, duration) in durations {
+        if let Some(duration) = duration {
+            timings.push_str(&format!(" {}={}", name, format_duration(duration)));
+        }
+    }
+    timings.push('>');
+    timings
+}
+
+/*
+ * Render a frame of the call stack, as strace -k does:
+ *     /usr/lib/x86_64-linux-gnu/libc.so.6(__open64+0x6e) [0x7ffff7d1a1de]
+ */
+pub fn format_frame(frame: &Frame) -> String
+{
+    let module = frame.module.as_deref().unwrap_or("?");
+    let symbol = frame.symbol.as_deref().unwrap_or("");
+    format!("{}({}+{:#x}) [{:#x}]", module, symbol, frame.offset, frame.address)
+}
+
+pub fn format_duration(nanoseconds: u64) -> String
+{
+    format!("{}.{:06}", nanoseconds / 1_000_000_000, nanoseconds % 1_000_000_000 / 1000)
+}
+
+fn format_raw_args(syscall: &Syscall) -> (Vec<String>, String)
+{
+    let args = syscall.raw.args.iter()
+        .take(6)
+        .map(|arg| format!("{:#x}", arg))
+        .collect();
+    (args, format_retval(syscall.raw.retval, false))
+}
+
+fn format_fields(syscall: &Syscall, fields: &Map<String, Value>) -> (Vec<String>, String)
+{
+    let mut args = Vec::with_capacity(fields.len());
+    let mut is_address = false;
+
+    for (field, value) in fields.iter() {
+        if field.starts_with('_') {
+            continue;
+        }
+        if field == "retval" {
+            // a pointer returned by the syscall (e.g., mmap) is displayed in hexadecimal
+            is_address = matches!(value, Value::Object(obj) if obj.contains_key("direction"));
+            continue;
+        }
+        args.push(format_arg(syscall, field, value));
+    }
+
+    (args, format_retval(syscall.raw.retval, is_address))
+}
+
+fn format_retval(retval: usize, is_address: bool) -> String
+{
+    let signed = retval as isize;
+    if (-MAX_ERRNO..0).contains(&signed) {
+        let errno = Errno::from_i32(-signed as i32);
+        return format!("-1 {:?} ({})", errno, errno.desc());
+    }
+    match is_address {
+        true => format!("{:#x}", retval),
+        false => format!("{}", signed),
+    }
+}
+
+
+/* Symbolic arguments */
+
+fn format_arg(syscall: &Syscall, field: &str, value: &Value) -> String
+{
+    let name = syscall.name.as_str();
+
+    if let Some(raw) = scalar(value) {
+        match (name, field) {
+            ("open" | "openat", "flags") => return format_flags(raw, OPEN_FLAGS, Some((0o3, OPEN_ACCMODES))),
+            (_, "mode") if name.contains("open") || name.contains("mkdir") || name.contains("chmod")
+                || name.contains("mknod") || name == "creat" => return format!("{:#o}", raw),
+            // the flags are truncated by their decoded type
+            ("mmap", "flags") => return format_flags(syscall.raw.args[3], MMAP_FLAGS, Some((0xf, MMAP_TYPES))),
+            ("mmap" | "mprotect", "prot") => return format_flags(raw, PROT_FLAGS, None),
+            ("unlinkat" | "linkat" | "fchownat" | "fchmodat" | "utimensat" | "newfstatat" | "statx"
+                | "faccessat" | "faccessat2" | "execveat" | "name_to_handle_at", "flags") => return format_flags(raw, AT_FLAGS, None),
+            ("lseek", "whence") => return format_enum(raw, SEEK_WHENCE),
+            _ => (),
+        }
+        if field == "fd" || field.ends_with("fd") {
+            return match raw as i32 {
+                AT_FDCWD if field.ends_with("dirfd") => String::from("AT_FDCWD"),
+                fd => format!("{}", fd),
+            };
+        }
+    }
+
+    format_value(field, value)
+}
+
+fn format_flags(value: usize, flags: &[(usize, &str)], mask: Option<(usize, &[(usize, &str)])>) -> String
+{
+    let mut names = Vec::new();
+    let mut remaining = value;
+
+    // a field of exclusive values (e.g., O_RDONLY, O_WRONLY or O_RDWR)
+    if let Some((mask, values)) = mask {
+        let field = value & mask;
+        match values.iter().find(|(v, _)| *v == field) {
+            Some((_, name)) => names.push(name.to_string()),
+            None => names.push(format!("{:#x}", field)),
+        }
+        remaining &= !mask;
+    }
+
+    for (flag, name) in flags.iter() {
+        if *flag != 0 && remaining & flag == *flag {
+            names.push(name.to_string());
+            remaining &= !flag;
+        }
+    }
+
+    if remaining != 0 || names.is_empty() {
+        names.push(format!("{:#x}", remaining));
+    }
+    names.join("|")
+}
+
+fn format_enum(value: usize, values: &[(usize, &str)]) -> String
+{
+    match values.iter().find(|(v, _)| *v == value) {
+        Some((_, name)) => name.to_string(),
+        None => format!("{}", value),
+    }
+}
+
+
+/* Generic arguments */
+
+fn scalar(value: &Value) -> Option<usize>
+{
+    match value {
+        Value::Object(obj) if obj.len() == 1 => obj.get("value")?.as_u64().map(|v| v as usize),
+        _ => None,
+    }
+}
+
+fn format_value(field: &str, value: &Value) -> String
+{
+    match value {
+        Value::Null => String::from("NULL"),
+        Value::Bool(b) => format!("{}", b),
+        Value::Number(n) => format_number(n),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => format_array(field, items),
+        Value::Object(obj) => format_object(field, obj),
+    }
+}
+
+fn format_number(n: &serde_json::Number) -> String
+{
+    match n.as_u64() {
+        Some(v) if (v as i64) < 0 => format!("{}", v as i64),
+        _ => format!("{}", n),
+    }
+}
+
+fn format_array(field: &str, items: &[Value]) -> String
+{
+    let items: Vec<String> = items.iter().map(|item| format_value(field, item)).collect();
+    format!("[{}]", items.join(", "))
+}
+
+fn format_object(field: &str, obj: &Map<String, Value>) -> String
+{
+    /* A direct value */
+    if let Some(value) = obj.get("value").and_then(Value::as_u64) {
+        // an Address also has a direction and a content
+        if obj.contains_key("direction") {
+            return format_address(value as usize);
+        }
+        if obj.len() == 1 {
+            return format_number(&serde_json::Number::from(value));
+        }
+    }
+
+    /* A pointer argument */
+    if let Some(address) = obj.get("address").and_then(Value::as_u64) {
+        if address == 0 {
+            return String::from("NULL");
+        }
+
+        // like strace, a bad pointer is displayed as is
+        if obj.get("fault").is_some_and(|fault| !fault.is_null()) {
+            return format_address(address as usize);
+        }
+
+        // a memory block (Buffer, NullBuffer) is displayed as a string
+        if obj.contains_key("size") {
+            if let Some(Value::Array(content)) = obj.get("content") {
+                return match content.is_empty() {
+                    true => format_address(address as usize),
+                    false => format_bytes(&bytes(content)),
+                };
+            }
+        }
+
+        // otherwise, only what is pointed to is displayed
+        let pointed: Vec<(&String, &Value)> = obj.iter()
+            .filter(|(k, _)| !matches!(k.as_str(), "address" | "direction" | "size" | "count" | "nfds"))
+            .collect();
+        return match pointed.as_slice() {
+            [(_, Value::Null)] => format_address(address as usize),
+            [(_, value)] => format_value(field, value),
+            _ => format_struct(&pointed),
+        };
+    }
+
+    /* A futex operation */
+    if let (Some(cmd), Some(private)) = (obj.get("cmd"), obj.get("private")) {
+        return format_futex_op(cmd, private.as_bool().unwrap_or(false),
+                               obj.get("clock_realtime").and_then(Value::as_bool).unwrap_or(false));
+    }
+
+    /* A structure */
+    let fields: Vec<(&String, &Value)> = obj.iter().collect();
+    format_struct(&fields)
+}
+
+fn format_struct(fields: &[(&String, &Value)]) -> String
+{
+    let fields: Vec<String> = fields.iter()
+        .map(|(k, v)| match k.as_str() {
+            "mtext" => format!("{}={}", k, format_bytes(&bytes(v.as_array().unwrap_or(&Vec::new())))),
+            _ => format!("{}={}", k, format_value(k, v)),
+        })
+        .collect();
+    format!("{{{}}}", fields.join(", "))
+}
+
+fn format_address(address: usize) -> String
+{
+    match address {
+        0 => String::from("NULL"),
+        _ => format!("{:#x}", address),
+    }
+}
+
+fn format_futex_op(cmd: &Value, private: bool, clock_realtime: bool) -> String
+{
+    let mut op = match cmd {
+        Value::String(cmd) => format!("FUTEX_{}", to_upper_snake_case(cmd)),
+        _ => format!("{}", cmd),
+    };
+    if private {
+        op.push_str("_PRIVATE");
+    }
+    if clock_realtime {
+        op.push_str("|FUTEX_CLOCK_REALTIME");
+    }
+    op
+}
+
+fn to_upper_snake_case(name: &str) -> String
+{
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if i > 0 && c.is_ascii_uppercase() {
+            snake.push('_');
+        }
+        snake.push(c.to_ascii_uppercase());
+    }
+    snake
+}
+
+
+/* Memory blocks */
+
+fn bytes(content: &[Value]) -> Vec<u8>
+{
+    content.iter()
+        .filter_map(Value::as_u64)
+        .map(|b| b as u8)
+        .collect()
+}
+
+/*
+ * Quote and escape a memory block, truncated to MAX_STRLEN bytes.
+ */
+pub fn format_bytes(bytes: &[u8]) -> String
+{
+    let mut s = String::with_capacity(MAX_STRLEN + 5);
+    s.push('"');
+    let shown = &bytes[..bytes.len().min(MAX_STRLEN)];
+    for (i, b) in shown.iter().enumerate() {
+        match b {
+            b'"' => s.push_str("\\\""),
+            b'\\' => s.push_str("\\\\"),
+            b'\n' => s.push_str("\\n"),
+            b'\r' => s.push_str("\\r"),
+            b'\t' => s.push_str("\\t"),
+            0x20..=0x7e => s.push(*b as char),
+            // like strace, the octal escape is padded when followed by a digit
+            _ => match shown.get(i + 1) {
+                Some(b'0'..=b'7') => s.push_str(&format!("\\{:03o}", b)),
+                _ => s.push_str(&format!("\\{:o}", b)),
+            },
+        }
+    }
+    s.push('"');
+    if bytes.len() > MAX_STRLEN {
+        s.push_str("...");
+    }
+    s
+}
+
+
+/* Symbolic values for x86_64 */
+
+const OPEN_ACCMODES: &[(usize, &str)] = &[
+    (0o0, "O_RDONLY"),
+    (0o1, "O_WRONLY"),
+    (0o2, "O_RDWR"),
+];
+
+const OPEN_FLAGS: &[(usize, &str)] = &[
+    (0o100, "O_CREAT"),
+    (0o200, "O_EXCL"),
+    (0o400, "O_NOCTTY"),
+    (0o1000, "O_TRUNC"),
+    (0o2000, "O_APPEND"),
+    (0o4000, "O_NONBLOCK"),
+    (0o4010000, "O_SYNC"),
+    (0o10000, "O_DSYNC"),
+    (0o20000, "O_ASYNC"),
+    (0o40000, "O_DIRECT"),
+    (0o100000, "O_LARGEFILE"),
+    (0o20200000, "O_TMPFILE"),
+    (0o200000, "O_DIRECTORY"),
+    (0o400000, "O_NOFOLLOW"),
+    (0o1000000, "O_NOATIME"),
+    (0o2000000, "O_CLOEXEC"),
+    (0o10000000, "O_PATH"),
+];
+
+const PROT_FLAGS: &[(usize, &str)] = &[
+    (0x1, "PROT_READ"),
+    (0x2, "PROT_WRITE"),
+    (0x4, "PROT_EXEC"),
+];
+
+const MMAP_TYPES: &[(usize, &str)] = &[
+    (0x1, "MAP_SHARED"),
+    (0x2, "MAP_PRIVATE"),
+    (0x3, "MAP_SHARED_VALIDATE"),
+];
+
+const MMAP_FLAGS: &[(usize, &str)] = &[
+    (0x10, "MAP_FIXED"),
+    (0x20, "MAP_ANONYMOUS"),
+    (0x100, "MAP_GROWSDOWN"),
+    (0x800, "MAP_DENYWRITE"),
+    (0x1000, "MAP_EXECUTABLE"),
+    (0x2000, "MAP_LOCKED"),
+    (0x4000, "MAP_NORESERVE"),
+    (0x8000, "MAP_POPULATE"),
+    (0x10000, "MAP_NONBLOCK"),
+    (0x20000, "MAP_STACK"),
+    (0x40000, "MAP_HUGETLB"),
+    (0x80000, "MAP_SYNC"),
+    (0x100000, "MAP_FIXED_NOREPLACE"),
+];
+
+const AT_FLAGS: &[(usize, &str)] = &[
+    (0x100, "AT_SYMLINK_NOFOLLOW"),
+    (0x200, "AT_REMOVEDIR"),
+    (0x400, "AT_SYMLINK_FOLLOW"),
+    (0x800, "AT_NO_AUTOMOUNT"),
+    (0x1000, "AT_EMPTY_PATH"),
+];
+
+const SEEK_WHENCE: &[(usize, &str)] = &[
+    (0, "SEEK_SET"),
+    (1, "SEEK_CUR"),
+    (2, "SEEK_END"),
+    (3, "SEEK_DATA"),
+    (4, "SEEK_HOLE"),
+];
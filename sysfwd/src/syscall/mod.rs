@@ -2,25 +2,25 @@
  * Syscall data structures
  */
 pub mod args;
+pub mod structs;
 pub mod decoder;
 pub mod encoder;
 pub mod syscalls;
+pub mod format;
 
 
 use nix::libc::user_regs_struct;
+use nix::time::{ clock_gettime, ClockId };
 use serde::{ Serialize, Deserialize };
 
 use crate::{
+        memory::unwind::Frame,
         syscall::decoder::DecodedSyscall,
         tracer::filtering::Decision,
 };
 
 
 
-/*
- * TODO: add timestamps for calculating how much time the syscall spent in kernel / forwarding.
- */
-
 #[derive(Serialize, Deserialize)]
 #[derive(Clone, Debug)]
 pub struct Syscall {
@@ -32,6 +32,15 @@ pub struct Syscall {
     //pub args: Vec<Option<Box<dyn Decode>>>,         // TODO: replace with Option<T>...
     pub name: String,
     pub decision: Option<Decision>,
+    /* An older peer does not send the timestamps */
+    #[serde(default)]
+    pub timestamps: Timestamps,
+    /* The call stack at the syscall entry, when the tracer unwinds it */
+    #[serde(default)]
+    pub backtrace: Option<Vec<Frame>>,
+    /* The index of the rule which decided on syscall entry, only known by the tracer */
+    #[serde(skip)]
+    pub rule: Option<usize>,
 }
 
 impl Syscall {
@@ -41,12 +50,71 @@ impl Syscall {
             decoded: None,
             name: String::with_capacity(25),
             decision: Some(Decision::Continue),     // Once the filtering implemented, put None 
+            timestamps: Timestamps::default(),
+            backtrace: None,
+            rule: None,
         }
     }
 
 }
 
 
+/*
+ * The moments of the life of a syscall, in nanoseconds of CLOCK_MONOTONIC.
+ *
+ * The tracer and the executor do not share their clock: only the durations measured on the
+ * same side are meaningful. The executor's time is therefore subtracted from the round trip
+ * seen by the tracer to get the time spent in the transport.
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug, Default)]
+pub struct Timestamps {
+    /* Tracer side */
+    pub entry: Option<u64>,
+    pub decoded: Option<u64>,
+    pub forward_sent: Option<u64>,
+    pub reply_received: Option<u64>,
+    pub exit: Option<u64>,
+    /* Executor side */
+    pub executor_start: Option<u64>,
+    pub executor_end: Option<u64>,
+}
+
+impl Timestamps {
+    pub fn now() -> Option<u64> {
+        let time = clock_gettime(ClockId::CLOCK_MONOTONIC).ok()?;
+        Some(time.tv_sec() as u64 * 1_000_000_000 + time.tv_nsec() as u64)
+    }
+
+    /* From the entry stop to the exit stop */
+    pub fn total(&self) -> Option<u64> {
+        Self::elapsed(self.entry, self.exit)
+    }
+
+    pub fn decode(&self) -> Option<u64> {
+        Self::elapsed(self.entry, self.decoded)
+    }
+
+    /* The round trip to the executor, as seen by the tracer */
+    pub fn forward(&self) -> Option<u64> {
+        Self::elapsed(self.forward_sent, self.reply_received)
+    }
+
+    pub fn executor(&self) -> Option<u64> {
+        Self::elapsed(self.executor_start, self.executor_end)
+    }
+
+    /* The round trip without the execution: serialization and network */
+    pub fn transport(&self) -> Option<u64> {
+        Some(self.forward()?.saturating_sub(self.executor()?))
+    }
+
+    fn elapsed(start: Option<u64>, end: Option<u64>) -> Option<u64> {
+        Some(end?.saturating_sub(start?))
+    }
+}
+
+
 /* 
  * A raw syscall represents the raw intercepted values 
  * 
@@ -0,0 +1,1369 @@
+/*
+ * Kernel structures used by syscall arguments.
+ *
+ * Layouts follow the x86_64 Linux ABI, and fields are read with the native endianness
+ * since the tracer and the tracee share the same architecture for now.
+ */
+use serde::{ Serialize, Deserialize };
+
+use crate::{
+    syscall::{
+        args::{ Direction, KernelStruct, read_pointed },
+        decoder::DecodeArg,
+        encoder::EncodeArg,
+    },
+    targets::operation::Operation,
+};
+
+
+
+/* Helpers to extract fields from a raw memory block */
+
+pub(crate) fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_ne_bytes(bytes[offset..offset + 2].try_into().unwrap())
+}
+
+pub(crate) fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_ne_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+pub(crate) fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_ne_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+
+/*
+ * struct epoll_event {
+ *     uint32_t     events;
+ *     epoll_data_t data;
+ * } __attribute__((packed));
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct EpollEvent {
+    pub events: u32,
+    pub data: u64,
+}
+
+impl KernelStruct for EpollEvent {
+    const SIZE: usize = 12;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            events: read_u32(bytes, 0),
+            data: read_u64(bytes, 4),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&self.events.to_ne_bytes());
+        bytes.extend_from_slice(&self.data.to_ne_bytes());
+        bytes
+    }
+}
+
+
+/*
+ * struct pollfd {
+ *     int   fd;
+ *     short events;
+ *     short revents;
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct PollFd {
+    pub fd: i32,
+    pub events: u16,
+    pub revents: u16,
+}
+
+impl KernelStruct for PollFd {
+    const SIZE: usize = 8;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            fd: read_u32(bytes, 0) as i32,
+            events: read_u16(bytes, 4),
+            revents: read_u16(bytes, 6),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&self.fd.to_ne_bytes());
+        bytes.extend_from_slice(&self.events.to_ne_bytes());
+        bytes.extend_from_slice(&self.revents.to_ne_bytes());
+        bytes
+    }
+}
+
+
+/*
+ * struct timespec {
+ *     time_t tv_sec;
+ *     long   tv_nsec;
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug)]
+pub struct Timespec {
+    pub tv_sec: i64,
+    pub tv_nsec: i64,
+}
+
+impl KernelStruct for Timespec {
+    const SIZE: usize = 16;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            tv_sec: read_u64(bytes, 0) as i64,
+            tv_nsec: read_u64(bytes, 8) as i64,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&self.tv_sec.to_ne_bytes());
+        bytes.extend_from_slice(&self.tv_nsec.to_ne_bytes());
+        bytes
+    }
+}
+
+
+/*
+ * struct timeval {
+ *     time_t      tv_sec;
+ *     suseconds_t tv_usec;
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug)]
+pub struct Timeval {
+    pub tv_sec: i64,
+    pub tv_usec: i64,
+}
+
+impl KernelStruct for Timeval {
+    const SIZE: usize = 16;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            tv_sec: read_u64(bytes, 0) as i64,
+            tv_usec: read_u64(bytes, 8) as i64,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&self.tv_sec.to_ne_bytes());
+        bytes.extend_from_slice(&self.tv_usec.to_ne_bytes());
+        bytes
+    }
+}
+
+
+/*
+ * struct itimerspec {
+ *     struct timespec it_interval;
+ *     struct timespec it_value;
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug)]
+pub struct Itimerspec {
+    pub it_interval: Timespec,
+    pub it_value: Timespec,
+}
+
+impl KernelStruct for Itimerspec {
+    const SIZE: usize = 2 * Timespec::SIZE;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            it_interval: Timespec::from_bytes(&bytes[..Timespec::SIZE]),
+            it_value: Timespec::from_bytes(&bytes[Timespec::SIZE..]),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.it_interval.to_bytes();
+        bytes.extend(self.it_value.to_bytes());
+        bytes
+    }
+}
+
+
+/*
+ * struct itimerval {
+ *     struct timeval it_interval;
+ *     struct timeval it_value;
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug)]
+pub struct Itimerval {
+    pub it_interval: Timeval,
+    pub it_value: Timeval,
+}
+
+impl KernelStruct for Itimerval {
+    const SIZE: usize = 2 * Timeval::SIZE;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            it_interval: Timeval::from_bytes(&bytes[..Timeval::SIZE]),
+            it_value: Timeval::from_bytes(&bytes[Timeval::SIZE..]),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.it_interval.to_bytes();
+        bytes.extend(self.it_value.to_bytes());
+        bytes
+    }
+}
+
+
+/*
+ * struct timezone {
+ *     int tz_minuteswest;
+ *     int tz_dsttime;
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug)]
+pub struct Timezone {
+    pub tz_minuteswest: i32,
+    pub tz_dsttime: i32,
+}
+
+impl KernelStruct for Timezone {
+    const SIZE: usize = 8;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            tz_minuteswest: read_u32(bytes, 0) as i32,
+            tz_dsttime: read_u32(bytes, 4) as i32,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&self.tz_minuteswest.to_ne_bytes());
+        bytes.extend_from_slice(&self.tz_dsttime.to_ne_bytes());
+        bytes
+    }
+}
+
+
+/*
+ * struct tms {
+ *     clock_t tms_utime;
+ *     clock_t tms_stime;
+ *     clock_t tms_cutime;
+ *     clock_t tms_cstime;
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug)]
+pub struct Tms {
+    pub tms_utime: i64,
+    pub tms_stime: i64,
+    pub tms_cutime: i64,
+    pub tms_cstime: i64,
+}
+
+impl KernelStruct for Tms {
+    const SIZE: usize = 32;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            tms_utime: read_u64(bytes, 0) as i64,
+            tms_stime: read_u64(bytes, 8) as i64,
+            tms_cutime: read_u64(bytes, 16) as i64,
+            tms_cstime: read_u64(bytes, 24) as i64,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&self.tms_utime.to_ne_bytes());
+        bytes.extend_from_slice(&self.tms_stime.to_ne_bytes());
+        bytes.extend_from_slice(&self.tms_cutime.to_ne_bytes());
+        bytes.extend_from_slice(&self.tms_cstime.to_ne_bytes());
+        bytes
+    }
+}
+
+
+/*
+ * struct sigevent {
+ *     union sigval sigev_value;
+ *     int sigev_signo;
+ *     int sigev_notify;
+ *     union { int _tid; ... } _sigev_un;     (padded to 64 bytes)
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug)]
+pub struct Sigevent {
+    pub sigev_value: u64,
+    pub sigev_signo: i32,
+    pub sigev_notify: i32,
+    pub sigev_notify_thread_id: i32,
+}
+
+impl KernelStruct for Sigevent {
+    const SIZE: usize = 64;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            sigev_value: read_u64(bytes, 0),
+            sigev_signo: read_u32(bytes, 8) as i32,
+            sigev_notify: read_u32(bytes, 12) as i32,
+            sigev_notify_thread_id: read_u32(bytes, 16) as i32,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&self.sigev_value.to_ne_bytes());
+        bytes.extend_from_slice(&self.sigev_signo.to_ne_bytes());
+        bytes.extend_from_slice(&self.sigev_notify.to_ne_bytes());
+        bytes.extend_from_slice(&self.sigev_notify_thread_id.to_ne_bytes());
+        bytes.resize(Self::SIZE, 0);
+        bytes
+    }
+}
+
+
+/*
+ * The default fs.nr_open: RLIMIT_NOFILE cannot be raised above it,
+ * and poll() rejects more fds than RLIMIT_NOFILE.
+ */
+pub const NR_OPEN: usize = 1 << 20;
+
+
+/*
+ * An fd_set used by select(), whose size in memory depends on nfds.
+ * Only the file descriptors set in the bitmap are kept.
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct FdSet {
+    pub address: usize,
+    pub direction: Direction,
+    pub nfds: usize,
+    pub fds: Vec<usize>,
+    /* The first address which could not be read (EFAULT) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fault: Option<usize>,
+}
+
+impl FdSet {
+    /* nfds is the int given to select(): the kernel rejects a negative one and never goes beyond its fds */
+    pub fn new(address: usize, direction: Direction, nfds: usize) -> Self {
+        Self {
+            address,
+            direction,
+            nfds: (nfds as i32).clamp(0, NR_OPEN as i32) as usize,
+            fds: Vec::new(),
+            fault: None,
+        }
+    }
+
+    /* Also bounded when the set is received from the other peer */
+    fn nfds(&self) -> usize {
+        self.nfds.min(NR_OPEN)
+    }
+
+    /* The kernel reads and writes the bitmap by chunks of unsigned long */
+    fn size(&self) -> usize {
+        self.nfds().div_ceil(64) * 8
+    }
+}
+
+impl DecodeArg for FdSet {
+
+    fn decode(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fds.clear();
+        if self.address == 0 || self.nfds() == 0 {
+            return Ok(());
+        }
+
+        let mem = read_pointed(pid, operation, self.address, self.size(), &mut self.fault)?;
+        for fd in 0..self.nfds() {
+            if let Some(byte) = mem.get(fd / 8) {
+                if byte & (1 << (fd % 8)) != 0 {
+                    self.fds.push(fd);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl EncodeArg for FdSet {
+
+    fn encode(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        if self.address == 0 {
+            return Ok(());
+        }
+
+        let mut mem = vec![0u8; self.size()];
+        for fd in self.fds.iter().filter(|&&fd| fd < self.nfds()) {
+            mem[fd / 8] |= 1 << (fd % 8);
+        }
+        operation.memory.write(pid, self.address, mem)?;
+        Ok(())
+    }
+}
+
+
+/*
+ * struct statfs {
+ *     __fsword_t f_type;
+ *     __fsword_t f_bsize;
+ *     fsblkcnt_t f_blocks;
+ *     fsblkcnt_t f_bfree;
+ *     fsblkcnt_t f_bavail;
+ *     fsfilcnt_t f_files;
+ *     fsfilcnt_t f_ffree;
+ *     fsid_t     f_fsid;
+ *     __fsword_t f_namelen;
+ *     __fsword_t f_frsize;
+ *     __fsword_t f_flags;
+ *     __fsword_t f_spare[4];
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct StatfsBuf {
+    pub f_type: u64,
+    pub f_bsize: u64,
+    pub f_blocks: u64,
+    pub f_bfree: u64,
+    pub f_bavail: u64,
+    pub f_files: u64,
+    pub f_ffree: u64,
+    pub f_fsid: [u32; 2],
+    pub f_namelen: u64,
+    pub f_frsize: u64,
+    pub f_flags: u64,
+}
+
+impl KernelStruct for StatfsBuf {
+    const SIZE: usize = 120;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            f_type: read_u64(bytes, 0),
+            f_bsize: read_u64(bytes, 8),
+            f_blocks: read_u64(bytes, 16),
+            f_bfree: read_u64(bytes, 24),
+            f_bavail: read_u64(bytes, 32),
+            f_files: read_u64(bytes, 40),
+            f_ffree: read_u64(bytes, 48),
+            f_fsid: [read_u32(bytes, 56), read_u32(bytes, 60)],
+            f_namelen: read_u64(bytes, 64),
+            f_frsize: read_u64(bytes, 72),
+            f_flags: read_u64(bytes, 80),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        for field in [self.f_type, self.f_bsize, self.f_blocks, self.f_bfree,
+                      self.f_bavail, self.f_files, self.f_ffree] {
+            bytes.extend_from_slice(&field.to_ne_bytes());
+        }
+        bytes.extend_from_slice(&self.f_fsid[0].to_ne_bytes());
+        bytes.extend_from_slice(&self.f_fsid[1].to_ne_bytes());
+        for field in [self.f_namelen, self.f_frsize, self.f_flags] {
+            bytes.extend_from_slice(&field.to_ne_bytes());
+        }
+        // f_spare
+        bytes.resize(Self::SIZE, 0);
+        bytes
+    }
+}
+
+
+/*
+ * Plain integers pointed to by an argument (e.g., a futex word or a size_t *)
+ */
+impl KernelStruct for u32 {
+    const SIZE: usize = 4;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        read_u32(bytes, 0)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_ne_bytes().to_vec()
+    }
+}
+
+impl KernelStruct for u64 {
+    const SIZE: usize = 8;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        read_u64(bytes, 0)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_ne_bytes().to_vec()
+    }
+}
+
+
+/*
+ * struct futex_waitv {
+ *     __u64 val;
+ *     __u64 uaddr;
+ *     __u32 flags;
+ *     __u32 __reserved;
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct FutexWaitv {
+    pub val: u64,
+    pub uaddr: u64,
+    pub flags: u32,
+}
+
+impl KernelStruct for FutexWaitv {
+    const SIZE: usize = 24;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            val: read_u64(bytes, 0),
+            uaddr: read_u64(bytes, 8),
+            flags: read_u32(bytes, 16),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&self.val.to_ne_bytes());
+        bytes.extend_from_slice(&self.uaddr.to_ne_bytes());
+        bytes.extend_from_slice(&self.flags.to_ne_bytes());
+        bytes.extend_from_slice(&0u32.to_ne_bytes());
+        bytes
+    }
+}
+
+
+/*
+ * struct robust_list_head {
+ *     struct robust_list list;
+ *     long futex_offset;
+ *     struct robust_list *list_op_pending;
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct RobustListHead {
+    pub next: u64,
+    pub futex_offset: i64,
+    pub list_op_pending: u64,
+}
+
+impl KernelStruct for RobustListHead {
+    const SIZE: usize = 24;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            next: read_u64(bytes, 0),
+            futex_offset: read_u64(bytes, 8) as i64,
+            list_op_pending: read_u64(bytes, 16),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&self.next.to_ne_bytes());
+        bytes.extend_from_slice(&self.futex_offset.to_ne_bytes());
+        bytes.extend_from_slice(&self.list_op_pending.to_ne_bytes());
+        bytes
+    }
+}
+
+
+/*
+ * A robust futex list registered by a thread.
+ * Besides the head, the list is walked to collect the address of each held futex,
+ * in the same way the kernel does when the thread exits.
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct RobustList {
+    pub address: usize,
+    pub direction: Direction,
+    pub head: Option<RobustListHead>,
+    pub futexes: Vec<usize>,
+    /* The first address which could not be read (EFAULT) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fault: Option<usize>,
+}
+
+/* Same limit as the kernel to avoid looping forever on a corrupted list */
+const ROBUST_LIST_LIMIT: usize = 2048;
+
+impl RobustList {
+    pub fn new(address: usize, direction: Direction) -> Self {
+        Self {
+            address,
+            direction,
+            head: None,
+            futexes: Vec::new(),
+            fault: None,
+        }
+    }
+}
+
+impl DecodeArg for RobustList {
+
+    fn decode(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.head = None;
+        self.futexes.clear();
+        if self.address == 0 {
+            return Ok(());
+        }
+
+        let mem = read_pointed(pid, operation, self.address, RobustListHead::SIZE, &mut self.fault)?;
+        if self.fault.is_some() {
+            return Ok(());
+        }
+        let head = RobustListHead::from_bytes(&mem[..RobustListHead::SIZE]);
+
+        // The list is circular and ends when pointing back to the head
+        let mut entry = head.next as usize;
+        while entry != self.address && entry != 0 && self.futexes.len() < ROBUST_LIST_LIMIT {
+            self.futexes.push(entry.wrapping_add(head.futex_offset as usize));
+            // like the kernel, stop walking at a bad entry
+            let mem = match operation.memory.read(pid, entry, 8) {
+                Ok(mem) => mem,
+                Err(_) => break,
+            };
+            entry = read_u64(&mem, 0) as usize;
+        }
+
+        self.head = Some(head);
+        Ok(())
+    }
+}
+
+
+/*
+ * struct sched_attr {
+ *     u32 size;
+ *     u32 sched_policy;
+ *     u64 sched_flags;
+ *     s32 sched_nice;
+ *     u32 sched_priority;
+ *     u64 sched_runtime;
+ *     u64 sched_deadline;
+ *     u64 sched_period;
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct SchedAttr {
+    pub size: u32,
+    pub sched_policy: u32,
+    pub sched_flags: u64,
+    pub sched_nice: i32,
+    pub sched_priority: u32,
+    pub sched_runtime: u64,
+    pub sched_deadline: u64,
+    pub sched_period: u64,
+}
+
+impl KernelStruct for SchedAttr {
+    /* SCHED_ATTR_SIZE_VER0 */
+    const SIZE: usize = 48;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            size: read_u32(bytes, 0),
+            sched_policy: read_u32(bytes, 4),
+            sched_flags: read_u64(bytes, 8),
+            sched_nice: read_u32(bytes, 16) as i32,
+            sched_priority: read_u32(bytes, 20),
+            sched_runtime: read_u64(bytes, 24),
+            sched_deadline: read_u64(bytes, 32),
+            sched_period: read_u64(bytes, 40),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&self.size.to_ne_bytes());
+        bytes.extend_from_slice(&self.sched_policy.to_ne_bytes());
+        bytes.extend_from_slice(&self.sched_flags.to_ne_bytes());
+        bytes.extend_from_slice(&self.sched_nice.to_ne_bytes());
+        bytes.extend_from_slice(&self.sched_priority.to_ne_bytes());
+        bytes.extend_from_slice(&self.sched_runtime.to_ne_bytes());
+        bytes.extend_from_slice(&self.sched_deadline.to_ne_bytes());
+        bytes.extend_from_slice(&self.sched_period.to_ne_bytes());
+        bytes
+    }
+}
+
+
+/*
+ * A cpu_set_t used by sched_{get,set}affinity(), whose size is given by cpusetsize.
+ * Only the CPUs set in the mask are kept.
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct CpuSet {
+    pub address: usize,
+    pub direction: Direction,
+    pub size: usize,
+    pub cpus: Vec<usize>,
+    /* The first address which could not be read (EFAULT) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fault: Option<usize>,
+}
+
+impl CpuSet {
+    pub fn new(address: usize, direction: Direction, size: usize) -> Self {
+        Self {
+            address,
+            direction,
+            size,
+            cpus: Vec::new(),
+            fault: None,
+        }
+    }
+}
+
+impl DecodeArg for CpuSet {
+
+    fn decode(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.cpus.clear();
+        if self.address == 0 || self.size == 0 {
+            return Ok(());
+        }
+
+        let mem = read_pointed(pid, operation, self.address, self.size, &mut self.fault)?;
+        for cpu in 0..self.size * 8 {
+            if let Some(byte) = mem.get(cpu / 8) {
+                if byte & (1 << (cpu % 8)) != 0 {
+                    self.cpus.push(cpu);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+
+/*
+ * struct ipc64_perm {
+ *     __kernel_key_t key;
+ *     __kernel_uid32_t uid, gid, cuid, cgid;
+ *     __kernel_mode_t mode;       (u16 + padding)
+ *     unsigned short seq;         (u16 + padding)
+ *     unsigned long __unused1, __unused2;
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct IpcPerm {
+    pub key: i32,
+    pub uid: u32,
+    pub gid: u32,
+    pub cuid: u32,
+    pub cgid: u32,
+    pub mode: u16,
+    pub seq: u16,
+}
+
+impl KernelStruct for IpcPerm {
+    const SIZE: usize = 48;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            key: read_u32(bytes, 0) as i32,
+            uid: read_u32(bytes, 4),
+            gid: read_u32(bytes, 8),
+            cuid: read_u32(bytes, 12),
+            cgid: read_u32(bytes, 16),
+            mode: read_u16(bytes, 20),
+            seq: read_u16(bytes, 24),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&self.key.to_ne_bytes());
+        bytes.extend_from_slice(&self.uid.to_ne_bytes());
+        bytes.extend_from_slice(&self.gid.to_ne_bytes());
+        bytes.extend_from_slice(&self.cuid.to_ne_bytes());
+        bytes.extend_from_slice(&self.cgid.to_ne_bytes());
+        bytes.extend_from_slice(&self.mode.to_ne_bytes());
+        bytes.extend_from_slice(&0u16.to_ne_bytes());
+        bytes.extend_from_slice(&self.seq.to_ne_bytes());
+        bytes.resize(Self::SIZE, 0);
+        bytes
+    }
+}
+
+
+/*
+ * struct shmid64_ds {
+ *     struct ipc64_perm shm_perm;
+ *     size_t shm_segsz;
+ *     long shm_atime, shm_dtime, shm_ctime;
+ *     pid_t shm_cpid, shm_lpid;
+ *     unsigned long shm_nattch;
+ *     unsigned long __unused4, __unused5;
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct ShmidDs {
+    pub shm_perm: IpcPerm,
+    pub shm_segsz: u64,
+    pub shm_atime: i64,
+    pub shm_dtime: i64,
+    pub shm_ctime: i64,
+    pub shm_cpid: i32,
+    pub shm_lpid: i32,
+    pub shm_nattch: u64,
+}
+
+impl KernelStruct for ShmidDs {
+    const SIZE: usize = 112;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            shm_perm: IpcPerm::from_bytes(&bytes[0..IpcPerm::SIZE]),
+            shm_segsz: read_u64(bytes, 48),
+            shm_atime: read_u64(bytes, 56) as i64,
+            shm_dtime: read_u64(bytes, 64) as i64,
+            shm_ctime: read_u64(bytes, 72) as i64,
+            shm_cpid: read_u32(bytes, 80) as i32,
+            shm_lpid: read_u32(bytes, 84) as i32,
+            shm_nattch: read_u64(bytes, 88),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.shm_perm.to_bytes();
+        bytes.extend_from_slice(&self.shm_segsz.to_ne_bytes());
+        bytes.extend_from_slice(&self.shm_atime.to_ne_bytes());
+        bytes.extend_from_slice(&self.shm_dtime.to_ne_bytes());
+        bytes.extend_from_slice(&self.shm_ctime.to_ne_bytes());
+        bytes.extend_from_slice(&self.shm_cpid.to_ne_bytes());
+        bytes.extend_from_slice(&self.shm_lpid.to_ne_bytes());
+        bytes.extend_from_slice(&self.shm_nattch.to_ne_bytes());
+        bytes.resize(Self::SIZE, 0);
+        bytes
+    }
+}
+
+
+/*
+ * struct msqid64_ds {
+ *     struct ipc64_perm msg_perm;
+ *     long msg_stime, msg_rtime, msg_ctime;
+ *     unsigned long msg_cbytes, msg_qnum, msg_qbytes;
+ *     pid_t msg_lspid, msg_lrpid;
+ *     unsigned long __unused4, __unused5;
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct MsqidDs {
+    pub msg_perm: IpcPerm,
+    pub msg_stime: i64,
+    pub msg_rtime: i64,
+    pub msg_ctime: i64,
+    pub msg_cbytes: u64,
+    pub msg_qnum: u64,
+    pub msg_qbytes: u64,
+    pub msg_lspid: i32,
+    pub msg_lrpid: i32,
+}
+
+impl KernelStruct for MsqidDs {
+    const SIZE: usize = 120;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            msg_perm: IpcPerm::from_bytes(&bytes[0..IpcPerm::SIZE]),
+            msg_stime: read_u64(bytes, 48) as i64,
+            msg_rtime: read_u64(bytes, 56) as i64,
+            msg_ctime: read_u64(bytes, 64) as i64,
+            msg_cbytes: read_u64(bytes, 72),
+            msg_qnum: read_u64(bytes, 80),
+            msg_qbytes: read_u64(bytes, 88),
+            msg_lspid: read_u32(bytes, 96) as i32,
+            msg_lrpid: read_u32(bytes, 100) as i32,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.msg_perm.to_bytes();
+        bytes.extend_from_slice(&self.msg_stime.to_ne_bytes());
+        bytes.extend_from_slice(&self.msg_rtime.to_ne_bytes());
+        bytes.extend_from_slice(&self.msg_ctime.to_ne_bytes());
+        bytes.extend_from_slice(&self.msg_cbytes.to_ne_bytes());
+        bytes.extend_from_slice(&self.msg_qnum.to_ne_bytes());
+        bytes.extend_from_slice(&self.msg_qbytes.to_ne_bytes());
+        bytes.extend_from_slice(&self.msg_lspid.to_ne_bytes());
+        bytes.extend_from_slice(&self.msg_lrpid.to_ne_bytes());
+        bytes.resize(Self::SIZE, 0);
+        bytes
+    }
+}
+
+
+/*
+ * struct sembuf {
+ *     unsigned short sem_num;
+ *     short sem_op;
+ *     short sem_flg;
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Sembuf {
+    pub sem_num: u16,
+    pub sem_op: i16,
+    pub sem_flg: i16,
+}
+
+impl KernelStruct for Sembuf {
+    const SIZE: usize = 6;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            sem_num: read_u16(bytes, 0),
+            sem_op: read_u16(bytes, 2) as i16,
+            sem_flg: read_u16(bytes, 4) as i16,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&self.sem_num.to_ne_bytes());
+        bytes.extend_from_slice(&self.sem_op.to_ne_bytes());
+        bytes.extend_from_slice(&self.sem_flg.to_ne_bytes());
+        bytes
+    }
+}
+
+
+/*
+ * msgsnd() rejects a message larger than msg_ctlmax (MSGMAX, 8192 by default),
+ * which can be raised up to INT_MAX.
+ */
+pub const MSGSZ_MAX: usize = i32::MAX as usize;
+
+
+/*
+ * struct msgbuf {
+ *     long mtype;
+ *     char mtext[msgsz];
+ * };
+ * The size given to msgsnd() and msgrcv() only covers mtext.
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct MsgBuf {
+    pub address: usize,
+    pub direction: Direction,
+    pub size: usize,
+    pub mtype: Option<i64>,
+    pub mtext: Vec<u8>,
+    /* The first address which could not be read (EFAULT) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fault: Option<usize>,
+}
+
+impl MsgBuf {
+    pub fn new(address: usize, direction: Direction, size: usize) -> Self {
+        Self {
+            address,
+            direction,
+            size,
+            mtype: None,
+            mtext: Vec::new(),
+            fault: None,
+        }
+    }
+}
+
+impl DecodeArg for MsgBuf {
+
+    fn decode(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        // msgsz is given by the tracee
+        let size = Some(self.size)
+            .filter(|&size| size <= MSGSZ_MAX)
+            .and_then(|size| size.checked_add(8))
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("msgsz {:#x} larger than any message", self.size)))?;
+
+        let mem = read_pointed(pid, operation, self.address, size, &mut self.fault)?;
+        if self.fault.is_some() {
+            self.mtype = None;
+            self.mtext.clear();
+            return Ok(());
+        }
+        self.mtype = Some(read_u64(&mem, 0) as i64);
+        self.mtext = mem[8..].iter().take(self.size).copied().collect();
+        Ok(())
+    }
+}
+
+impl EncodeArg for MsgBuf {
+
+    fn encode(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        if let Some(mtype) = self.mtype {
+            let mut mem = mtype.to_ne_bytes().to_vec();
+            mem.extend_from_slice(&self.mtext);
+            operation.memory.write(pid, self.address, mem)?;
+        }
+        Ok(())
+    }
+}
+
+
+/*
+ * struct io_sqring_offsets {
+ *     __u32 head, tail, ring_mask, ring_entries, flags, dropped, array, resv1;
+ *     __u64 user_addr;
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IoSqringOffsets {
+    pub head: u32,
+    pub tail: u32,
+    pub ring_mask: u32,
+    pub ring_entries: u32,
+    pub flags: u32,
+    pub dropped: u32,
+    pub array: u32,
+    pub user_addr: u64,
+}
+
+impl KernelStruct for IoSqringOffsets {
+    const SIZE: usize = 40;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            head: read_u32(bytes, 0),
+            tail: read_u32(bytes, 4),
+            ring_mask: read_u32(bytes, 8),
+            ring_entries: read_u32(bytes, 12),
+            flags: read_u32(bytes, 16),
+            dropped: read_u32(bytes, 20),
+            array: read_u32(bytes, 24),
+            user_addr: read_u64(bytes, 32),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        for field in [self.head, self.tail, self.ring_mask, self.ring_entries,
+                      self.flags, self.dropped, self.array, 0] {
+            bytes.extend_from_slice(&field.to_ne_bytes());
+        }
+        bytes.extend_from_slice(&self.user_addr.to_ne_bytes());
+        bytes
+    }
+}
+
+
+/*
+ * struct io_cqring_offsets {
+ *     __u32 head, tail, ring_mask, ring_entries, overflow, cqes, flags, resv1;
+ *     __u64 user_addr;
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IoCqringOffsets {
+    pub head: u32,
+    pub tail: u32,
+    pub ring_mask: u32,
+    pub ring_entries: u32,
+    pub overflow: u32,
+    pub cqes: u32,
+    pub flags: u32,
+    pub user_addr: u64,
+}
+
+impl KernelStruct for IoCqringOffsets {
+    const SIZE: usize = 40;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            head: read_u32(bytes, 0),
+            tail: read_u32(bytes, 4),
+            ring_mask: read_u32(bytes, 8),
+            ring_entries: read_u32(bytes, 12),
+            overflow: read_u32(bytes, 16),
+            cqes: read_u32(bytes, 20),
+            flags: read_u32(bytes, 24),
+            user_addr: read_u64(bytes, 32),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        for field in [self.head, self.tail, self.ring_mask, self.ring_entries,
+                      self.overflow, self.cqes, self.flags, 0] {
+            bytes.extend_from_slice(&field.to_ne_bytes());
+        }
+        bytes.extend_from_slice(&self.user_addr.to_ne_bytes());
+        bytes
+    }
+}
+
+
+/*
+ * struct io_uring_params {
+ *     __u32 sq_entries, cq_entries, flags, sq_thread_cpu, sq_thread_idle, features, wq_fd;
+ *     __u32 resv[3];
+ *     struct io_sqring_offsets sq_off;
+ *     struct io_cqring_offsets cq_off;
+ * };
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IoUringParams {
+    pub sq_entries: u32,
+    pub cq_entries: u32,
+    pub flags: u32,
+    pub sq_thread_cpu: u32,
+    pub sq_thread_idle: u32,
+    pub features: u32,
+    pub wq_fd: u32,
+    pub sq_off: IoSqringOffsets,
+    pub cq_off: IoCqringOffsets,
+}
+
+impl KernelStruct for IoUringParams {
+    const SIZE: usize = 120;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            sq_entries: read_u32(bytes, 0),
+            cq_entries: read_u32(bytes, 4),
+            flags: read_u32(bytes, 8),
+            sq_thread_cpu: read_u32(bytes, 12),
+            sq_thread_idle: read_u32(bytes, 16),
+            features: read_u32(bytes, 20),
+            wq_fd: read_u32(bytes, 24),
+            sq_off: IoSqringOffsets::from_bytes(&bytes[40..80]),
+            cq_off: IoCqringOffsets::from_bytes(&bytes[80..120]),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        for field in [self.sq_entries, self.cq_entries, self.flags, self.sq_thread_cpu,
+                      self.sq_thread_idle, self.features, self.wq_fd, 0, 0, 0] {
+            bytes.extend_from_slice(&field.to_ne_bytes());
+        }
+        bytes.extend(self.sq_off.to_bytes());
+        bytes.extend(self.cq_off.to_bytes());
+        bytes
+    }
+}
+
+
+/*
+ * struct io_uring_sqe (the 64 first bytes, the extra space of IORING_SETUP_SQE128 is not kept)
+ *     __u8 opcode; __u8 flags; __u16 ioprio; __s32 fd;
+ *     __u64 off; __u64 addr; __u32 len; __u32 op_flags;
+ *     __u64 user_data;
+ *     __u16 buf_index; __u16 personality; __s32 splice_fd_in;
+ *     __u64 addr3; __u64 __pad2[1];
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug)]
+pub struct IoUringSqe {
+    pub opcode: u8,
+    pub flags: u8,
+    pub ioprio: u16,
+    pub fd: i32,
+    pub off: u64,
+    pub addr: u64,
+    pub len: u32,
+    pub op_flags: u32,
+    pub user_data: u64,
+    pub buf_index: u16,
+    pub personality: u16,
+    pub splice_fd_in: i32,
+    pub addr3: u64,
+}
+
+impl KernelStruct for IoUringSqe {
+    const SIZE: usize = 64;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            opcode: bytes[0],
+            flags: bytes[1],
+            ioprio: read_u16(bytes, 2),
+            fd: read_u32(bytes, 4) as i32,
+            off: read_u64(bytes, 8),
+            addr: read_u64(bytes, 16),
+            len: read_u32(bytes, 24),
+            op_flags: read_u32(bytes, 28),
+            user_data: read_u64(bytes, 32),
+            buf_index: read_u16(bytes, 40),
+            personality: read_u16(bytes, 42),
+            splice_fd_in: read_u32(bytes, 44) as i32,
+            addr3: read_u64(bytes, 48),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.push(self.opcode);
+        bytes.push(self.flags);
+        bytes.extend_from_slice(&self.ioprio.to_ne_bytes());
+        bytes.extend_from_slice(&self.fd.to_ne_bytes());
+        bytes.extend_from_slice(&self.off.to_ne_bytes());
+        bytes.extend_from_slice(&self.addr.to_ne_bytes());
+        bytes.extend_from_slice(&self.len.to_ne_bytes());
+        bytes.extend_from_slice(&self.op_flags.to_ne_bytes());
+        bytes.extend_from_slice(&self.user_data.to_ne_bytes());
+        bytes.extend_from_slice(&self.buf_index.to_ne_bytes());
+        bytes.extend_from_slice(&self.personality.to_ne_bytes());
+        bytes.extend_from_slice(&self.splice_fd_in.to_ne_bytes());
+        bytes.extend_from_slice(&self.addr3.to_ne_bytes());
+        bytes.resize(Self::SIZE, 0);
+        bytes
+    }
+}
+
+
+/*
+ * struct io_uring_cqe (the 16 first bytes, the extra space of IORING_SETUP_CQE32 is not kept)
+ *     __u64 user_data; __s32 res; __u32 flags;
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug)]
+pub struct IoUringCqe {
+    pub user_data: u64,
+    pub res: i32,
+    pub flags: u32,
+}
+
+impl KernelStruct for IoUringCqe {
+    const SIZE: usize = 16;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            user_data: read_u64(bytes, 0),
+            res: read_u32(bytes, 8) as i32,
+            flags: read_u32(bytes, 12),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&self.user_data.to_ne_bytes());
+        bytes.extend_from_slice(&self.res.to_ne_bytes());
+        bytes.extend_from_slice(&self.flags.to_ne_bytes());
+        bytes
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        syscall::args::TypedArray,
+        targets::{ process_vm::ProcessVm, ptrace::Ptrace },
+    };
+
+    use super::*;
+
+    /* The test process reads its own memory */
+    fn operation() -> Box<Operation>
+    {
+        Box::new(Operation { register: Box::new(Ptrace { }), memory: Box::new(ProcessVm { }) })
+    }
+
+    fn pid() -> i32
+    {
+        std::process::id() as i32
+    }
+
+    #[test]
+    fn fdset_keeps_the_fds_set_below_nfds()
+    {
+        let bitmap: [u64; 2] = [0b1_0101, 1];
+        let mut fdset = FdSet::new(bitmap.as_ptr() as usize, Direction::InOut, 4);
+        fdset.decode(pid(), &operation()).unwrap();
+        assert_eq!(fdset.fds, vec![0, 2]);
+
+        let mut fdset = FdSet::new(bitmap.as_ptr() as usize, Direction::InOut, 65);
+        fdset.decode(pid(), &operation()).unwrap();
+        assert_eq!(fdset.fds, vec![0, 2, 4, 64]);
+    }
+
+    #[test]
+    fn fdset_bounds_nfds_as_the_kernel()
+    {
+        // the int is negative whether the register is sign-extended or not
+        assert_eq!(FdSet::new(0x1000, Direction::InOut, usize::MAX).nfds, 0);
+        assert_eq!(FdSet::new(0x1000, Direction::InOut, 0xffff_ffff).nfds, 0);
+        assert_eq!(FdSet::new(0x1000, Direction::InOut, i32::MAX as usize).nfds, NR_OPEN);
+
+        let bitmap = [0u64; 1];
+        let mut fdset = FdSet::new(bitmap.as_ptr() as usize, Direction::InOut, usize::MAX);
+        fdset.decode(pid(), &operation()).unwrap();
+        assert!(fdset.fds.is_empty());
+    }
+
+    #[test]
+    fn typed_array_too_large_for_memory_is_a_fault()
+    {
+        let pollfds = [0u64; 1];
+        let mut array: TypedArray<PollFd> = TypedArray::new(pollfds.as_ptr() as usize, Direction::InOut, usize::MAX);
+        array.decode(pid(), &operation()).unwrap();
+        assert!(array.content.is_empty());
+        assert_eq!(array.fault, Some(pollfds.as_ptr() as usize));
+    }
+
+    #[test]
+    fn msgbuf_larger_than_msgmax_is_not_decoded()
+    {
+        let msg: [u64; 2] = [1, u64::from_ne_bytes(*b"message!")];
+        let mut msgbuf = MsgBuf::new(msg.as_ptr() as usize, Direction::In, 8);
+        msgbuf.decode(pid(), &operation()).unwrap();
+        assert_eq!(msgbuf.mtype, Some(1));
+        assert_eq!(msgbuf.mtext, b"message!");
+
+        for size in [usize::MAX, usize::MAX - 4, MSGSZ_MAX + 1] {
+            let mut msgbuf = MsgBuf::new(msg.as_ptr() as usize, Direction::In, size);
+            assert!(msgbuf.decode(pid(), &operation()).is_err());
+        }
+    }
+
+    #[test]
+    fn msqid_ds_layout()
+    {
+        let mut bytes: Vec<u8> = (0..MsqidDs::SIZE as u8).collect();
+        // the unused fields are not kept
+        bytes[104..].fill(0);
+        let msqid_ds = MsqidDs::from_bytes(&bytes);
+        assert_eq!(msqid_ds.msg_qbytes, u64::from_ne_bytes(bytes[88..96].try_into().unwrap()));
+        assert_eq!(msqid_ds.msg_lrpid, i32::from_ne_bytes(bytes[100..104].try_into().unwrap()));
+        assert_eq!(msqid_ds.to_bytes()[IpcPerm::SIZE..], bytes[IpcPerm::SIZE..]);
+    }
+}
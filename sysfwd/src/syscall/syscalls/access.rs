@@ -31,9 +31,10 @@ impl Access {
     }
 }
 impl DecodeEntry for Access {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.pathname.decode(pid, operation).unwrap();
-        self.mode.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pathname.decode(pid, operation)?;
+        self.mode.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -60,11 +61,12 @@ impl Faccessat {
     }
 }
 impl DecodeEntry for Faccessat {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.dirfd.decode(pid, operation).unwrap();
-        self.pathname.decode(pid, operation).unwrap();
-        self.mode.decode(pid, operation).unwrap();
-        self.flags.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.dirfd.decode(pid, operation)?;
+        self.pathname.decode(pid, operation)?;
+        self.mode.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -91,10 +93,11 @@ impl Faccessat2 {
     }
 }
 impl DecodeEntry for Faccessat2 {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.dirfd.decode(pid, operation).unwrap();
-        self.pathname.decode(pid, operation).unwrap();
-        self.mode.decode(pid, operation).unwrap();
-        self.flags.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.dirfd.decode(pid, operation)?;
+        self.pathname.decode(pid, operation)?;
+        self.mode.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
     }
 }
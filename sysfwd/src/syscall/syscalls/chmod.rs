@@ -0,0 +1,277 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, Fd, NullBuffer },
+    syscall::{
+        decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+        encoder::{ EncodeArg, EncodeEntry },
+    },
+    targets::operation::Operation,
+};
+
+
+// int chmod(const char *pathname, mode_t mode)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Chmod {
+    pub pathname: NullBuffer,
+    pub mode: Integer,
+    pub retval: Option<Integer>,
+}
+impl Chmod {
+    pub fn new(raw: RawSyscall) -> Self {
+        let pathname = NullBuffer::new(raw.args[0], Direction::In);
+        let mode = Integer::new(raw.args[1]);
+        let retval = None;
+        Self { pathname, mode, retval }
+    }
+}
+impl DecodeEntry for Chmod {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pathname.decode(pid, operation)?;
+        self.mode.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Chmod {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.pathname.address;
+        self.pathname.encode(pid, operation)?;
+        raw.args[1] = self.mode.value;
+        Ok(raw)
+    }
+}
+
+// int fchmod(int fd, mode_t mode)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Fchmod {
+    pub fd: Fd,
+    pub mode: Integer,
+    pub retval: Option<Integer>,
+}
+impl Fchmod {
+    pub fn new(raw: RawSyscall) -> Self {
+        let fd = Fd::new(raw.args[0]);
+        let mode = Integer::new(raw.args[1]);
+        let retval = None;
+        Self { fd, mode, retval }
+    }
+}
+impl DecodeEntry for Fchmod {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.mode.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Fchmod {
+    fn encode_entry(&mut self, mut raw: RawSyscall, _pid: i32, _operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.fd.value;
+        raw.args[1] = self.mode.value;
+        Ok(raw)
+    }
+}
+
+// int fchmodat(int dirfd, const char *pathname, mode_t mode, int flags)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Fchmodat {
+    pub dirfd: Fd,
+    pub pathname: NullBuffer,
+    pub mode: Integer,
+    pub flags: Integer,
+    pub retval: Option<Integer>,
+}
+impl Fchmodat {
+    pub fn new(raw: RawSyscall) -> Self {
+        let dirfd = Fd::new(raw.args[0]);
+        let pathname = NullBuffer::new(raw.args[1], Direction::In);
+        let mode = Integer::new(raw.args[2]);
+        let flags = Integer::new(raw.args[3]);
+        let retval = None;
+        Self { dirfd, pathname, mode, flags, retval }
+    }
+}
+impl DecodeEntry for Fchmodat {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.dirfd.decode(pid, operation)?;
+        self.pathname.decode(pid, operation)?;
+        self.mode.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Fchmodat {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.dirfd.value;
+        raw.args[1] = self.pathname.address;
+        self.pathname.encode(pid, operation)?;
+        raw.args[2] = self.mode.value;
+        raw.args[3] = self.flags.value;
+        Ok(raw)
+    }
+}
+
+// int chown(const char *pathname, uid_t owner, gid_t group)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Chown {
+    pub pathname: NullBuffer,
+    pub owner: Integer,
+    pub group: Integer,
+    pub retval: Option<Integer>,
+}
+impl Chown {
+    pub fn new(raw: RawSyscall) -> Self {
+        let pathname = NullBuffer::new(raw.args[0], Direction::In);
+        let owner = Integer::new(raw.args[1]);
+        let group = Integer::new(raw.args[2]);
+        let retval = None;
+        Self { pathname, owner, group, retval }
+    }
+}
+impl DecodeEntry for Chown {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pathname.decode(pid, operation)?;
+        self.owner.decode(pid, operation)?;
+        self.group.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Chown {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.pathname.address;
+        self.pathname.encode(pid, operation)?;
+        raw.args[1] = self.owner.value;
+        raw.args[2] = self.group.value;
+        Ok(raw)
+    }
+}
+
+// int fchown(int fd, uid_t owner, gid_t group)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Fchown {
+    pub fd: Fd,
+    pub owner: Integer,
+    pub group: Integer,
+    pub retval: Option<Integer>,
+}
+impl Fchown {
+    pub fn new(raw: RawSyscall) -> Self {
+        let fd = Fd::new(raw.args[0]);
+        let owner = Integer::new(raw.args[1]);
+        let group = Integer::new(raw.args[2]);
+        let retval = None;
+        Self { fd, owner, group, retval }
+    }
+}
+impl DecodeEntry for Fchown {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.owner.decode(pid, operation)?;
+        self.group.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Fchown {
+    fn encode_entry(&mut self, mut raw: RawSyscall, _pid: i32, _operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.fd.value;
+        raw.args[1] = self.owner.value;
+        raw.args[2] = self.group.value;
+        Ok(raw)
+    }
+}
+
+// int lchown(const char *pathname, uid_t owner, gid_t group)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Lchown {
+    pub pathname: NullBuffer,
+    pub owner: Integer,
+    pub group: Integer,
+    pub retval: Option<Integer>,
+}
+impl Lchown {
+    pub fn new(raw: RawSyscall) -> Self {
+        let pathname = NullBuffer::new(raw.args[0], Direction::In);
+        let owner = Integer::new(raw.args[1]);
+        let group = Integer::new(raw.args[2]);
+        let retval = None;
+        Self { pathname, owner, group, retval }
+    }
+}
+impl DecodeEntry for Lchown {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pathname.decode(pid, operation)?;
+        self.owner.decode(pid, operation)?;
+        self.group.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Lchown {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.pathname.address;
+        self.pathname.encode(pid, operation)?;
+        raw.args[1] = self.owner.value;
+        raw.args[2] = self.group.value;
+        Ok(raw)
+    }
+}
+
+// int fchownat(int dirfd, const char *pathname, uid_t owner, gid_t group, int flags)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Fchownat {
+    pub dirfd: Fd,
+    pub pathname: NullBuffer,
+    pub owner: Integer,
+    pub group: Integer,
+    pub flags: Integer,
+    pub retval: Option<Integer>,
+}
+impl Fchownat {
+    pub fn new(raw: RawSyscall) -> Self {
+        let dirfd = Fd::new(raw.args[0]);
+        let pathname = NullBuffer::new(raw.args[1], Direction::In);
+        let owner = Integer::new(raw.args[2]);
+        let group = Integer::new(raw.args[3]);
+        let flags = Integer::new(raw.args[4]);
+        let retval = None;
+        Self { dirfd, pathname, owner, group, flags, retval }
+    }
+}
+impl DecodeEntry for Fchownat {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.dirfd.decode(pid, operation)?;
+        self.pathname.decode(pid, operation)?;
+        self.owner.decode(pid, operation)?;
+        self.group.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Fchownat {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.dirfd.value;
+        raw.args[1] = self.pathname.address;
+        self.pathname.encode(pid, operation)?;
+        raw.args[2] = self.owner.value;
+        raw.args[3] = self.group.value;
+        raw.args[4] = self.flags.value;
+        Ok(raw)
+    }
+}
@@ -0,0 +1,115 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, Fd, Size, Buffer, NullBuffer },
+    syscall::{
+        decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+        encoder::{ EncodeArg, EncodeEntry },
+    },
+    targets::operation::Operation,
+};
+
+
+// char *getcwd(char buf[.size], size_t size)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Getcwd {
+    pub buf: Buffer,
+    pub size: Size,
+    pub retval: Option<Size>,
+}
+impl Getcwd {
+    pub fn new(raw: RawSyscall) -> Self {
+        let buf = Buffer::new(raw.args[0], Direction::Out, raw.args[1]);
+        let size = Size::new(raw.args[1]);
+        let retval = None;
+        Self { buf, size, retval }
+    }
+}
+impl DecodeEntry for Getcwd {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.size.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Getcwd {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Size::from(value));
+        // The kernel returns the number of bytes written in the buffer
+        if (value as isize) > 0 {
+            self.buf.size = value;
+            self.buf.decode(pid, operation)?;
+            self.buf.content.truncate(value);
+        }
+        Ok(())
+    }
+}
+impl EncodeEntry for Getcwd {
+    fn encode_entry(&mut self, mut raw: RawSyscall, _pid: i32, _operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.buf.address;
+        raw.args[1] = self.size.value;
+        Ok(raw)
+    }
+}
+
+// int chdir(const char *path)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Chdir {
+    pub path: NullBuffer,
+    pub retval: Option<Integer>,
+}
+impl Chdir {
+    pub fn new(raw: RawSyscall) -> Self {
+        let path = NullBuffer::new(raw.args[0], Direction::In);
+        let retval = None;
+        Self { path, retval }
+    }
+}
+impl DecodeEntry for Chdir {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.path.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Chdir {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.path.address;
+        self.path.encode(pid, operation)?;
+        Ok(raw)
+    }
+}
+
+// int fchdir(int fd)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Fchdir {
+    pub fd: Fd,
+    pub retval: Option<Integer>,
+}
+impl Fchdir {
+    pub fn new(raw: RawSyscall) -> Self {
+        let fd = Fd::new(raw.args[0]);
+        let retval = None;
+        Self { fd, retval }
+    }
+}
+impl DecodeEntry for Fchdir {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Fchdir {
+    fn encode_entry(&mut self, mut raw: RawSyscall, _pid: i32, _operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.fd.value;
+        Ok(raw)
+    }
+}
@@ -31,10 +31,11 @@ impl Getdents {
     }
 }
 impl DecodeEntry for Getdents {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.dirp.decode(pid, operation).unwrap();
-        self.count.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.dirp.decode(pid, operation)?;
+        self.count.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -59,10 +60,11 @@ impl Getdents64 {
     }
 }
 impl DecodeEntry for Getdents64 {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.dirp.decode(pid, operation).unwrap();
-        self.count.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.dirp.decode(pid, operation)?;
+        self.count.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -87,9 +89,10 @@ impl Readdir {
     }
 }
 impl DecodeEntry for Readdir {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.dirp.decode(pid, operation).unwrap();
-        self.count.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.dirp.decode(pid, operation)?;
+        self.count.decode(pid, operation)?;
+        Ok(())
     }
 }
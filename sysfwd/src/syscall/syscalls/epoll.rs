@@ -6,7 +6,8 @@ use serde::{ Serialize, Deserialize };
 use decoding_macro::DecodeExit;
 use crate::{
     syscall::RawSyscall,
-    syscall::args::{ Direction, Integer, Fd, Struct },
+    syscall::args::{ Direction, Integer, Fd, Struct, TypedStruct, TypedArray },
+    syscall::structs::{ EpollEvent, Timespec },
     syscall::decoder::{ DecodeArg, DecodeEntry, DecodeExit },
     targets::operation::Operation,
 };
@@ -29,8 +30,9 @@ impl EpollCreate {
     }
 }
 impl DecodeEntry for EpollCreate {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.size.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.size.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -50,8 +52,9 @@ impl EpollCreate1 {
     }
 }
 impl DecodeEntry for EpollCreate1 {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.size.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.size.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -63,7 +66,7 @@ pub struct EpollCtl {
     pub epfd: Fd,
     pub op: Integer,
     pub fd: Fd,
-    pub event: Struct,
+    pub event: TypedStruct<EpollEvent>,
     pub retval: Option<Integer>,
 }
 impl EpollCtl {
@@ -71,27 +74,27 @@ impl EpollCtl {
         let epfd = Fd::new(raw.args[0]);
         let op = Integer::new(raw.args[1]);
         let fd = Fd::new(raw.args[2]);
-        let event = Struct::new(raw.args[3], Direction::InOut);
+        let event = TypedStruct::new(raw.args[3], Direction::In);
         let retval = None;
         Self { epfd, op, fd, event, retval }
     }
 }
 impl DecodeEntry for EpollCtl {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.epfd.decode(pid, operation).unwrap();
-        self.op.decode(pid, operation).unwrap();
-        self.fd.decode(pid, operation).unwrap();
-        self.event.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.epfd.decode(pid, operation)?;
+        self.op.decode(pid, operation)?;
+        self.fd.decode(pid, operation)?;
+        self.event.decode(pid, operation)?;
+        Ok(())
     }
 }
 
 // int epoll_wait(int epfd, struct epoll_event *events, int maxevents, int timeout);
 #[derive(Serialize, Deserialize)]
 #[derive(Clone, Debug)]
-#[derive(DecodeExit)]
 pub struct EpollWait {
     pub epfd: Fd,
-    pub events: Struct,
+    pub events: TypedArray<EpollEvent>,
     pub maxevents: Integer,
     pub timeout: Integer,
     pub retval: Option<Integer>,
@@ -99,7 +102,7 @@ pub struct EpollWait {
 impl EpollWait {
     pub fn new(raw: RawSyscall) -> Self {
         let epfd = Fd::new(raw.args[0]);
-        let events = Struct::new(raw.args[1], Direction::InOut);
+        let events = TypedArray::new(raw.args[1], Direction::Out, raw.args[2]);
         let maxevents = Integer::new(raw.args[2]);
         let timeout = Integer::new(raw.args[3]);
         let retval = None;
@@ -107,21 +110,31 @@ impl EpollWait {
     }
 }
 impl DecodeEntry for EpollWait {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.epfd.decode(pid, operation).unwrap();
-        self.events.decode(pid, operation).unwrap();
-        self.maxevents.decode(pid, operation).unwrap();
-        self.timeout.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.epfd.decode(pid, operation)?;
+        self.maxevents.decode(pid, operation)?;
+        self.timeout.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for EpollWait {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        // On success, the kernel returns the number of events written in the array
+        if (value as isize) > 0 {
+            self.events.count = value;
+            self.events.decode(pid, operation)?;
+        }
+        Ok(())
     }
 }
 
 // int epoll_pwait(int epfd, struct epoll_event *events, int maxevents, int timeout, const sigset_t *_Nullable sigmask);
 #[derive(Serialize, Deserialize)]
 #[derive(Clone, Debug)]
-#[derive(DecodeExit)]
 pub struct EpollPwait {
     pub epfd: Fd,
-    pub events: Struct,
+    pub events: TypedArray<EpollEvent>,
     pub maxevents: Integer,
     pub timeout: Integer,
     pub sigmask: Struct,
@@ -130,7 +143,7 @@ pub struct EpollPwait {
 impl EpollPwait {
     pub fn new(raw: RawSyscall) -> Self {
         let epfd = Fd::new(raw.args[0]);
-        let events = Struct::new(raw.args[1], Direction::InOut);
+        let events = TypedArray::new(raw.args[1], Direction::Out, raw.args[2]);
         let maxevents = Integer::new(raw.args[2]);
         let timeout = Integer::new(raw.args[3]);
         let sigmask = Struct::new(raw.args[4], Direction::In);
@@ -139,44 +152,65 @@ impl EpollPwait {
     }
 }
 impl DecodeEntry for EpollPwait {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.epfd.decode(pid, operation).unwrap();
-        self.events.decode(pid, operation).unwrap();
-        self.maxevents.decode(pid, operation).unwrap();
-        self.timeout.decode(pid, operation).unwrap();
-        self.sigmask.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.epfd.decode(pid, operation)?;
+        self.maxevents.decode(pid, operation)?;
+        self.timeout.decode(pid, operation)?;
+        self.sigmask.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for EpollPwait {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        // On success, the kernel returns the number of events written in the array
+        if (value as isize) > 0 {
+            self.events.count = value;
+            self.events.decode(pid, operation)?;
+        }
+        Ok(())
     }
 }
 
 // int epoll_pwait2(int epfd, struct epoll_event *events, int maxevents, const struct timespec *_Nullable timeout, const sigset_t *_Nullable sigmask);
 #[derive(Serialize, Deserialize)]
 #[derive(Clone, Debug)]
-#[derive(DecodeExit)]
 pub struct EpollPwait2 {
     pub epfd: Fd,
-    pub events: Struct,
+    pub events: TypedArray<EpollEvent>,
     pub maxevents: Integer,
-    pub timeout: Struct,
+    pub timeout: TypedStruct<Timespec>,
     pub sigmask: Struct,
     pub retval: Option<Integer>,
 }
 impl EpollPwait2 {
     pub fn new(raw: RawSyscall) -> Self {
         let epfd = Fd::new(raw.args[0]);
-        let events = Struct::new(raw.args[1], Direction::InOut);
+        let events = TypedArray::new(raw.args[1], Direction::Out, raw.args[2]);
         let maxevents = Integer::new(raw.args[2]);
-        let timeout = Struct::new(raw.args[3], Direction::In);
+        let timeout = TypedStruct::new(raw.args[3], Direction::In);
         let sigmask = Struct::new(raw.args[4], Direction::In);
         let retval = None;
         Self { epfd, events, maxevents, timeout, sigmask, retval }
     }
 }
 impl DecodeEntry for EpollPwait2 {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.epfd.decode(pid, operation).unwrap();
-        self.events.decode(pid, operation).unwrap();
-        self.maxevents.decode(pid, operation).unwrap();
-        self.timeout.decode(pid, operation).unwrap();
-        self.sigmask.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.epfd.decode(pid, operation)?;
+        self.maxevents.decode(pid, operation)?;
+        self.timeout.decode(pid, operation)?;
+        self.sigmask.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for EpollPwait2 {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        // On success, the kernel returns the number of events written in the array
+        if (value as isize) > 0 {
+            self.events.count = value;
+            self.events.decode(pid, operation)?;
+        }
+        Ok(())
     }
 }
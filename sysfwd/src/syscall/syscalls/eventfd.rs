@@ -0,0 +1,61 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Integer, Fd },
+    syscall::decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+    targets::operation::Operation,
+};
+
+
+
+// int eventfd(unsigned int initval);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Eventfd {
+    pub initval: Integer,
+    pub retval: Option<Fd>,
+}
+impl Eventfd {
+    pub fn new(raw: RawSyscall) -> Self {
+        let initval = Integer::new(raw.args[0]);
+        let retval = None;
+        Self { initval, retval }
+    }
+}
+impl DecodeEntry for Eventfd {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.initval.decode(pid, operation)?;
+        Ok(())
+    }
+}
+
+// int eventfd2(unsigned int initval, int flags);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Eventfd2 {
+    pub initval: Integer,
+    pub flags: Integer,
+    pub retval: Option<Fd>,
+}
+impl Eventfd2 {
+    pub fn new(raw: RawSyscall) -> Self {
+        let initval = Integer::new(raw.args[0]);
+        let flags = Integer::new(raw.args[1]);
+        let retval = None;
+        Self { initval, flags, retval }
+    }
+}
+impl DecodeEntry for Eventfd2 {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.initval.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
+    }
+}
@@ -32,10 +32,11 @@ impl Execve {
     }
 }
 impl DecodeEntry for Execve {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.pathname.decode(pid, operation).unwrap();
-        self.argv.decode(pid, operation).unwrap();
-        self.envp.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pathname.decode(pid, operation)?;
+        self.argv.decode(pid, operation)?;
+        self.envp.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -63,11 +64,12 @@ impl Execveat {
     }
 }
 impl DecodeEntry for Execveat {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.dirfd.decode(pid, operation).unwrap();
-        self.pathname.decode(pid, operation).unwrap();
-        self.argv.decode(pid, operation).unwrap();
-        self.envp.decode(pid, operation).unwrap();
-        self.flags.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.dirfd.decode(pid, operation)?;
+        self.pathname.decode(pid, operation)?;
+        self.argv.decode(pid, operation)?;
+        self.envp.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
     }
 }
\ No newline at end of file
@@ -30,7 +30,8 @@ impl ExitGroup {
     }
 }
 impl DecodeEntry for ExitGroup {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.status.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.status.decode(pid, operation)?;
+        Ok(())
     }
 }
@@ -36,10 +36,11 @@ impl Fallocate {
 }
 
 impl DecodeEntry for Fallocate {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.mode.decode(pid, operation).unwrap();
-        self.offset.decode(pid, operation).unwrap();
-        self.len.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.mode.decode(pid, operation)?;
+        self.offset.decode(pid, operation)?;
+        self.len.decode(pid, operation)?;
+        Ok(())
     }
 }
\ No newline at end of file
@@ -37,12 +37,13 @@ impl NameToHandleAt {
     }
 }
 impl DecodeEntry for NameToHandleAt {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.dirfd.decode(pid, operation).unwrap();
-        self.pathname.decode(pid, operation).unwrap();
-        self.handle.decode(pid, operation).unwrap();
-        self.mount_id.decode(pid, operation).unwrap();
-        self.flags.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.dirfd.decode(pid, operation)?;
+        self.pathname.decode(pid, operation)?;
+        self.handle.decode(pid, operation)?;
+        self.mount_id.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -66,9 +67,10 @@ impl OpenByHandleAt {
     }
 }
 impl DecodeEntry for OpenByHandleAt {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.mount_fd.decode(pid, operation).unwrap();
-        self.handle.decode(pid, operation).unwrap();
-        self.flags.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.mount_fd.decode(pid, operation)?;
+        self.handle.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
     }
 }
\ No newline at end of file
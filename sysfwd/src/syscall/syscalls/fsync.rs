@@ -0,0 +1,88 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Integer, Fd },
+    syscall::{
+        decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+        encoder::EncodeEntry,
+    },
+    targets::operation::Operation,
+};
+
+
+// void sync(void)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Sync {
+    pub retval: Option<Integer>,
+}
+impl Sync {
+    pub fn new(_raw: RawSyscall) -> Self {
+        let retval = None;
+        Self { retval }
+    }
+}
+impl DecodeEntry for Sync { }
+impl EncodeEntry for Sync { }
+
+// int fsync(int fd)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Fsync {
+    pub fd: Fd,
+    pub retval: Option<Integer>,
+}
+impl Fsync {
+    pub fn new(raw: RawSyscall) -> Self {
+        let fd = Fd::new(raw.args[0]);
+        let retval = None;
+        Self { fd, retval }
+    }
+}
+impl DecodeEntry for Fsync {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Fsync {
+    fn encode_entry(&mut self, mut raw: RawSyscall, _pid: i32, _operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.fd.value;
+        Ok(raw)
+    }
+}
+
+// int fdatasync(int fd)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Fdatasync {
+    pub fd: Fd,
+    pub retval: Option<Integer>,
+}
+impl Fdatasync {
+    pub fn new(raw: RawSyscall) -> Self {
+        let fd = Fd::new(raw.args[0]);
+        let retval = None;
+        Self { fd, retval }
+    }
+}
+impl DecodeEntry for Fdatasync {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Fdatasync {
+    fn encode_entry(&mut self, mut raw: RawSyscall, _pid: i32, _operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.fd.value;
+        Ok(raw)
+    }
+}
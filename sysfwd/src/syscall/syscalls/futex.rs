@@ -0,0 +1,198 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, TypedStruct, TypedArray },
+    syscall::structs::{ Timespec, FutexWaitv as FutexWaiter },
+    syscall::decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+    targets::operation::Operation,
+};
+
+
+
+/* Futex operations (see linux/futex.h) */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FutexCmd {
+    Wait,
+    Wake,
+    Fd,
+    Requeue,
+    CmpRequeue,
+    WakeOp,
+    LockPi,
+    UnlockPi,
+    TrylockPi,
+    WaitBitset,
+    WakeBitset,
+    WaitRequeuePi,
+    CmpRequeuePi,
+    LockPi2,
+    Unknown(u32),
+}
+
+const FUTEX_PRIVATE_FLAG: u32 = 128;
+const FUTEX_CLOCK_REALTIME: u32 = 256;
+const FUTEX_CMD_MASK: u32 = !(FUTEX_PRIVATE_FLAG | FUTEX_CLOCK_REALTIME);
+
+/* The kernel rejects futex_waitv() with more waiters than this */
+const FUTEX_WAITV_MAX: usize = 128;
+
+impl FutexCmd {
+    pub fn from_raw(cmd: u32) -> Self {
+        match cmd {
+            0 => FutexCmd::Wait,
+            1 => FutexCmd::Wake,
+            2 => FutexCmd::Fd,
+            3 => FutexCmd::Requeue,
+            4 => FutexCmd::CmpRequeue,
+            5 => FutexCmd::WakeOp,
+            6 => FutexCmd::LockPi,
+            7 => FutexCmd::UnlockPi,
+            8 => FutexCmd::TrylockPi,
+            9 => FutexCmd::WaitBitset,
+            10 => FutexCmd::WakeBitset,
+            11 => FutexCmd::WaitRequeuePi,
+            12 => FutexCmd::CmpRequeuePi,
+            13 => FutexCmd::LockPi2,
+            x => FutexCmd::Unknown(x),
+        }
+    }
+
+    /* The 4th argument is a struct timespec * only for these operations, otherwise it is val2 */
+    pub fn has_timeout(&self) -> bool {
+        matches!(self, FutexCmd::Wait | FutexCmd::WaitBitset | FutexCmd::LockPi
+            | FutexCmd::LockPi2 | FutexCmd::WaitRequeuePi)
+    }
+
+    pub fn has_val2(&self) -> bool {
+        matches!(self, FutexCmd::Requeue | FutexCmd::CmpRequeue | FutexCmd::WakeOp
+            | FutexCmd::CmpRequeuePi)
+    }
+
+    pub fn has_uaddr2(&self) -> bool {
+        matches!(self, FutexCmd::Requeue | FutexCmd::CmpRequeue | FutexCmd::WakeOp
+            | FutexCmd::WaitRequeuePi | FutexCmd::CmpRequeuePi)
+    }
+}
+
+
+/* The futex_op argument, split into the operation and its modifier flags */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct FutexOp {
+    pub value: usize,
+    pub cmd: FutexCmd,
+    pub private: bool,
+    pub clock_realtime: bool,
+}
+
+impl FutexOp {
+    pub fn new(value: usize) -> Self {
+        let op = value as u32;
+        Self {
+            value,
+            cmd: FutexCmd::from_raw(op & FUTEX_CMD_MASK),
+            private: op & FUTEX_PRIVATE_FLAG != 0,
+            clock_realtime: op & FUTEX_CLOCK_REALTIME != 0,
+        }
+    }
+}
+
+impl DecodeArg for FutexOp {
+    fn decode(&mut self, _pid: i32, _operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        Ok(())
+    }
+}
+
+
+
+// long syscall(SYS_futex, uint32_t *uaddr, int futex_op, uint32_t val,
+//              const struct timespec *timeout,   /* or: uint32_t val2 */
+//              uint32_t *uaddr2, uint32_t val3);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Futex {
+    pub uaddr: TypedStruct<u32>,
+    pub futex_op: FutexOp,
+    pub val: Integer,
+    pub timeout: Option<TypedStruct<Timespec>>,
+    pub val2: Option<Integer>,
+    pub uaddr2: Option<TypedStruct<u32>>,
+    pub val3: Integer,
+    pub retval: Option<Integer>,
+}
+impl Futex {
+    pub fn new(raw: RawSyscall) -> Self {
+        let uaddr = TypedStruct::new(raw.args[0], Direction::In);
+        let futex_op = FutexOp::new(raw.args[1]);
+        let val = Integer::new(raw.args[2]);
+        let timeout = futex_op.cmd.has_timeout().then(|| TypedStruct::new(raw.args[3], Direction::In));
+        let val2 = futex_op.cmd.has_val2().then(|| Integer::new(raw.args[3]));
+        let uaddr2 = futex_op.cmd.has_uaddr2().then(|| TypedStruct::new(raw.args[4], Direction::In));
+        let val3 = Integer::new(raw.args[5]);
+        let retval = None;
+        Self { uaddr, futex_op, val, timeout, val2, uaddr2, val3, retval }
+    }
+}
+impl DecodeEntry for Futex {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        // The futex word may already be unmapped (e.g., FUTEX_WAKE on a freed stack),
+        // the address is what matters so a failed read is not fatal
+        let _ = self.uaddr.decode(pid, operation);
+        self.futex_op.decode(pid, operation)?;
+        self.val.decode(pid, operation)?;
+        if let Some(timeout) = &mut self.timeout {
+            timeout.decode(pid, operation)?;
+        }
+        if let Some(val2) = &mut self.val2 {
+            val2.decode(pid, operation)?;
+        }
+        if let Some(uaddr2) = &mut self.uaddr2 {
+            let _ = uaddr2.decode(pid, operation);
+        }
+        self.val3.decode(pid, operation)?;
+        Ok(())
+    }
+}
+
+
+// long syscall(SYS_futex_waitv, struct futex_waitv *waiters, unsigned int nr_futexes,
+//              unsigned int flags, struct timespec *timeout, clockid_t clockid);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct FutexWaitv {
+    pub waiters: TypedArray<FutexWaiter>,
+    pub nr_futexes: Integer,
+    pub flags: Integer,
+    pub timeout: TypedStruct<Timespec>,
+    pub clockid: Integer,
+    pub retval: Option<Integer>,
+}
+impl FutexWaitv {
+    pub fn new(raw: RawSyscall) -> Self {
+        let waiters = TypedArray::new(raw.args[0], Direction::In, raw.args[1].min(FUTEX_WAITV_MAX));
+        let nr_futexes = Integer::new(raw.args[1]);
+        let flags = Integer::new(raw.args[2]);
+        let timeout = TypedStruct::new(raw.args[3], Direction::In);
+        let clockid = Integer::new(raw.args[4]);
+        let retval = None;
+        Self { waiters, nr_futexes, flags, timeout, clockid, retval }
+    }
+}
+impl DecodeEntry for FutexWaitv {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.waiters.decode(pid, operation)?;
+        self.nr_futexes.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        self.timeout.decode(pid, operation)?;
+        self.clockid.decode(pid, operation)?;
+        Ok(())
+    }
+}
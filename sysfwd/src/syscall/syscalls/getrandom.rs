@@ -35,9 +35,10 @@ impl Getrandom {
 }
 
 impl DecodeEntry for Getrandom {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.buf.decode(pid, operation).unwrap();
-        self.buflen.decode(pid, operation).unwrap();
-        self.flags.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.buf.decode(pid, operation)?;
+        self.buflen.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
     }
 }
\ No newline at end of file
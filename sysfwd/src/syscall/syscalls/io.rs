@@ -35,17 +35,18 @@ impl Read {
     }
 }
 impl DecodeEntry for Read {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.buf.decode(pid, operation).unwrap();
-        self.count.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.buf.decode(pid, operation)?;
+        self.count.decode(pid, operation)?;
+        Ok(())
     }
 }
 impl EncodeEntry for Read {
     fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
         raw.args[0] = self.fd.value;
         raw.args[1] = self.buf.address;
-        self.buf.encode(pid, operation).unwrap();
+        self.buf.encode(pid, operation)?;
         raw.args[2] = self.count.value;
         Ok(raw)
     }
@@ -71,17 +72,18 @@ impl Write {
     }
 }
 impl DecodeEntry for Write {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.buf.decode(pid, operation).unwrap();
-        self.count.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.buf.decode(pid, operation)?;
+        self.count.decode(pid, operation)?;
+        Ok(())
     }
 }
 impl EncodeEntry for Write {
     fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
         raw.args[0] = self.fd.value;
         raw.args[1] = self.buf.address;
-        self.buf.encode(pid, operation).unwrap();
+        self.buf.encode(pid, operation)?;
         raw.args[2] = self.count.value;
         Ok(raw)
     }
@@ -107,10 +109,11 @@ impl Readv {
     }
 }
 impl DecodeEntry for Readv {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.iov.decode(pid, operation).unwrap();
-        self.iovcnt.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.iov.decode(pid, operation)?;
+        self.iovcnt.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -134,10 +137,11 @@ impl Writev {
     }
 }
 impl DecodeEntry for Writev {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.iov.decode(pid, operation).unwrap();
-        self.iovcnt.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.iov.decode(pid, operation)?;
+        self.iovcnt.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -165,11 +169,12 @@ impl Pread {
     }
 }
 impl DecodeEntry for Pread {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.buf.decode(pid, operation).unwrap();
-        self.nbytes.decode(pid, operation).unwrap();
-        self.offset.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.buf.decode(pid, operation)?;
+        self.nbytes.decode(pid, operation)?;
+        self.offset.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -195,11 +200,12 @@ impl Pwrite {
     }
 }
 impl DecodeEntry for Pwrite {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.buf.decode(pid, operation).unwrap();
-        self.nbytes.decode(pid, operation).unwrap();
-        self.offset.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.buf.decode(pid, operation)?;
+        self.nbytes.decode(pid, operation)?;
+        self.offset.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -226,11 +232,12 @@ impl Preadv {
     }
 }
 impl DecodeEntry for Preadv {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.iov.decode(pid, operation).unwrap();
-        self.iovcnt.decode(pid, operation).unwrap();
-        self.offset.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.iov.decode(pid, operation)?;
+        self.iovcnt.decode(pid, operation)?;
+        self.offset.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -256,11 +263,12 @@ impl Pwritev {
     }
 }
 impl DecodeEntry for Pwritev {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.iov.decode(pid, operation).unwrap();
-        self.iovcnt.decode(pid, operation).unwrap();
-        self.offset.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.iov.decode(pid, operation)?;
+        self.iovcnt.decode(pid, operation)?;
+        self.offset.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -290,12 +298,13 @@ impl Preadv2 {
     }
 }
 impl DecodeEntry for Preadv2 {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.iov.decode(pid, operation).unwrap();
-        self.iovcnt.decode(pid, operation).unwrap();
-        self.offset.decode(pid, operation).unwrap();
-        self.flags.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.iov.decode(pid, operation)?;
+        self.iovcnt.decode(pid, operation)?;
+        self.offset.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -324,11 +333,12 @@ impl Pwritev2 {
     }
 }
 impl DecodeEntry for Pwritev2 {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.iov.decode(pid, operation).unwrap();
-        self.iovcnt.decode(pid, operation).unwrap();
-        self.offset.decode(pid, operation).unwrap();
-        self.flags.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.iov.decode(pid, operation)?;
+        self.iovcnt.decode(pid, operation)?;
+        self.offset.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
     }
 }
\ No newline at end of file
@@ -0,0 +1,407 @@
+/*
+ * io_uring submits its operations through rings shared between the process and the kernel,
+ * so io_uring_enter() is the only syscall seen for them.
+ * The rings cannot be found from the syscall arguments alone: their addresses come from the
+ * mmap() calls on the io_uring fd, which is why IoUringRing is maintained by the engines.
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, Fd, Size, Address, Buffer, TypedStruct, KernelStruct },
+    syscall::structs::{ read_u32, IoUringParams, IoUringSqe, IoUringCqe },
+    syscall::{
+        decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+        encoder::{ EncodeArg, EncodeEntry },
+    },
+    targets::operation::Operation,
+};
+
+
+
+/* mmap() offsets of the rings (see linux/io_uring.h) */
+pub const IORING_OFF_SQ_RING: usize = 0;
+pub const IORING_OFF_CQ_RING: usize = 0x8000000;
+pub const IORING_OFF_SQES: usize = 0x10000000;
+
+const IORING_FEAT_SINGLE_MMAP: u32 = 1 << 0;
+const IORING_SETUP_SQE128: u32 = 1 << 10;
+const IORING_SETUP_CQE32: u32 = 1 << 11;
+
+/* The SQE fd is an index in the registered files and not a fd */
+pub const IOSQE_FIXED_FILE: u8 = 1 << 0;
+
+/* Operations using a plain memory buffer at sqe.addr of sqe.len bytes */
+const IORING_OP_READ_FIXED: u8 = 4;
+const IORING_OP_WRITE_FIXED: u8 = 5;
+const IORING_OP_READ: u8 = 22;
+const IORING_OP_WRITE: u8 = 23;
+const IORING_OP_SEND: u8 = 26;
+const IORING_OP_RECV: u8 = 27;
+
+
+/*
+ * The memory buffer referenced by a SQE, if any.
+ * The direction tells if the buffer is read (In) or filled (Out) by the kernel.
+ */
+pub fn sqe_buffer(sqe: &IoUringSqe) -> Option<Buffer> {
+    match sqe.opcode {
+        IORING_OP_WRITE | IORING_OP_WRITE_FIXED | IORING_OP_SEND =>
+            Some(Buffer::new(sqe.addr as usize, Direction::In, sqe.len as usize)),
+        IORING_OP_READ | IORING_OP_READ_FIXED | IORING_OP_RECV =>
+            Some(Buffer::new(sqe.addr as usize, Direction::Out, sqe.len as usize)),
+        _ => None,
+    }
+}
+
+
+/*
+ * The location of the rings of an io_uring instance in a process.
+ */
+#[derive(Clone, Debug)]
+pub struct IoUringRing {
+    pub params: IoUringParams,
+    pub sq_ring: Option<usize>,
+    pub cq_ring: Option<usize>,
+    pub sqes: Option<usize>,
+}
+
+impl IoUringRing {
+
+    pub fn new(params: IoUringParams) -> Self {
+        Self {
+            params,
+            sq_ring: None,
+            cq_ring: None,
+            sqes: None,
+        }
+    }
+
+    /* Record the address where a ring has been mapped, given the mmap() offset */
+    pub fn map(&mut self, offset: usize, address: usize) {
+        match offset {
+            IORING_OFF_SQ_RING => {
+                self.sq_ring = Some(address);
+                if self.params.features & IORING_FEAT_SINGLE_MMAP != 0 {
+                    self.cq_ring = Some(address);
+                }
+            },
+            IORING_OFF_CQ_RING => self.cq_ring = Some(address),
+            IORING_OFF_SQES => self.sqes = Some(address),
+            _ => (),
+        }
+    }
+
+    /* The offsets to mmap() for a complete mapping of the rings */
+    pub fn offsets(&self) -> Vec<usize> {
+        if self.params.features & IORING_FEAT_SINGLE_MMAP != 0 {
+            vec![IORING_OFF_SQ_RING, IORING_OFF_SQES]
+        } else {
+            vec![IORING_OFF_SQ_RING, IORING_OFF_CQ_RING, IORING_OFF_SQES]
+        }
+    }
+
+    /* The length to mmap() at a given offset */
+    pub fn mapping_size(&self, offset: usize) -> usize {
+        let sq_size = self.params.sq_off.array as usize + self.params.sq_entries as usize * 4;
+        let cq_size = self.params.cq_off.cqes as usize + self.params.cq_entries as usize * self.cqe_size();
+        match offset {
+            IORING_OFF_SQ_RING if self.params.features & IORING_FEAT_SINGLE_MMAP != 0 => sq_size.max(cq_size),
+            IORING_OFF_SQ_RING => sq_size,
+            IORING_OFF_CQ_RING => cq_size,
+            IORING_OFF_SQES => self.params.sq_entries as usize * self.sqe_size(),
+            _ => 0,
+        }
+    }
+
+    pub fn is_mapped(&self) -> bool {
+        self.sq_ring.is_some() && self.cq_ring.is_some() && self.sqes.is_some()
+    }
+
+    fn sqe_size(&self) -> usize {
+        match self.params.flags & IORING_SETUP_SQE128 {
+            0 => IoUringSqe::SIZE,
+            _ => 2 * IoUringSqe::SIZE,
+        }
+    }
+
+    fn cqe_size(&self) -> usize {
+        match self.params.flags & IORING_SETUP_CQE32 {
+            0 => IoUringCqe::SIZE,
+            _ => 2 * IoUringCqe::SIZE,
+        }
+    }
+
+    fn mapped(address: Option<usize>) -> Result<usize, std::io::Error> {
+        address.ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "io_uring ring not mapped"))
+    }
+
+    fn read_u32(pid: i32, operation: &Operation, address: usize) -> Result<u32, std::io::Error> {
+        let mem = operation.memory.read(pid, address, 4)?;
+        Ok(read_u32(&mem, 0))
+    }
+
+    fn write_u32(pid: i32, operation: &Operation, address: usize, value: u32) -> Result<(), std::io::Error> {
+        operation.memory.write(pid, address, value.to_ne_bytes().to_vec())?;
+        Ok(())
+    }
+
+    /*
+     * Fill the header of rings mapped anonymously, as the kernel would have done.
+     * Used when the actual rings live on the executor side.
+     */
+    pub fn init_header(&self, pid: i32, operation: &Operation) -> Result<(), std::io::Error> {
+        let sq_ring = Self::mapped(self.sq_ring)?;
+        let sq_off = &self.params.sq_off;
+        Self::write_u32(pid, operation, sq_ring + sq_off.ring_mask as usize, self.params.sq_entries - 1)?;
+        Self::write_u32(pid, operation, sq_ring + sq_off.ring_entries as usize, self.params.sq_entries)?;
+
+        let cq_ring = Self::mapped(self.cq_ring)?;
+        let cq_off = &self.params.cq_off;
+        Self::write_u32(pid, operation, cq_ring + cq_off.ring_mask as usize, self.params.cq_entries - 1)?;
+        Self::write_u32(pid, operation, cq_ring + cq_off.ring_entries as usize, self.params.cq_entries)?;
+        Ok(())
+    }
+
+    /* Read at most `max` SQEs not yet consumed by the kernel */
+    pub fn read_submissions(&self, pid: i32, operation: &Operation, max: usize) -> Result<Vec<IoUringSqe>, std::io::Error> {
+        let sq_ring = Self::mapped(self.sq_ring)?;
+        let sqes = Self::mapped(self.sqes)?;
+        let sq_off = &self.params.sq_off;
+        let mask = self.params.sq_entries.wrapping_sub(1);
+
+        let head = Self::read_u32(pid, operation, sq_ring + sq_off.head as usize)?;
+        let tail = Self::read_u32(pid, operation, sq_ring + sq_off.tail as usize)?;
+        let count = (tail.wrapping_sub(head) as usize).min(max).min(self.params.sq_entries as usize);
+
+        let mut submissions = Vec::with_capacity(count);
+        for i in 0..count {
+            let slot = head.wrapping_add(i as u32) & mask;
+            let index = Self::read_u32(pid, operation, sq_ring + sq_off.array as usize + slot as usize * 4)? & mask;
+            let mem = operation.memory.read(pid, sqes + index as usize * self.sqe_size(), IoUringSqe::SIZE)?;
+            submissions.push(IoUringSqe::from_bytes(&mem));
+        }
+        Ok(submissions)
+    }
+
+    /* Mark SQEs as consumed, as the kernel does when they are submitted */
+    pub fn consume_submissions(&self, pid: i32, operation: &Operation, count: usize) -> Result<(), std::io::Error> {
+        let sq_ring = Self::mapped(self.sq_ring)?;
+        let head_address = sq_ring + self.params.sq_off.head as usize;
+        let head = Self::read_u32(pid, operation, head_address)?;
+        Self::write_u32(pid, operation, head_address, head.wrapping_add(count as u32))?;
+        Ok(())
+    }
+
+    /* Append SQEs to the submission queue, as the process does before io_uring_enter() */
+    pub fn push_submissions(&self, pid: i32, operation: &Operation, submissions: &[IoUringSqe]) -> Result<(), std::io::Error> {
+        let sq_ring = Self::mapped(self.sq_ring)?;
+        let sqes = Self::mapped(self.sqes)?;
+        let sq_off = &self.params.sq_off;
+        let mask = self.params.sq_entries.wrapping_sub(1);
+
+        let tail_address = sq_ring + sq_off.tail as usize;
+        let mut tail = Self::read_u32(pid, operation, tail_address)?;
+        for sqe in submissions {
+            let slot = tail & mask;
+            operation.memory.write(pid, sqes + slot as usize * self.sqe_size(), sqe.to_bytes())?;
+            Self::write_u32(pid, operation, sq_ring + sq_off.array as usize + slot as usize * 4, slot)?;
+            tail = tail.wrapping_add(1);
+        }
+        Self::write_u32(pid, operation, tail_address, tail)?;
+        Ok(())
+    }
+
+    /* Read the CQEs not yet consumed by the process */
+    pub fn read_completions(&self, pid: i32, operation: &Operation) -> Result<Vec<IoUringCqe>, std::io::Error> {
+        let cq_ring = Self::mapped(self.cq_ring)?;
+        let cq_off = &self.params.cq_off;
+        let mask = self.params.cq_entries.wrapping_sub(1);
+
+        let head = Self::read_u32(pid, operation, cq_ring + cq_off.head as usize)?;
+        let tail = Self::read_u32(pid, operation, cq_ring + cq_off.tail as usize)?;
+        let count = (tail.wrapping_sub(head) as usize).min(self.params.cq_entries as usize);
+
+        let mut completions = Vec::with_capacity(count);
+        for i in 0..count {
+            let slot = head.wrapping_add(i as u32) & mask;
+            let mem = operation.memory.read(pid, cq_ring + cq_off.cqes as usize + slot as usize * self.cqe_size(), IoUringCqe::SIZE)?;
+            completions.push(IoUringCqe::from_bytes(&mem));
+        }
+        Ok(completions)
+    }
+
+    /* Read and consume the pending CQEs, as the process does after io_uring_enter() */
+    pub fn pop_completions(&self, pid: i32, operation: &Operation) -> Result<Vec<IoUringCqe>, std::io::Error> {
+        let completions = self.read_completions(pid, operation)?;
+        let head_address = Self::mapped(self.cq_ring)? + self.params.cq_off.head as usize;
+        let head = Self::read_u32(pid, operation, head_address)?;
+        Self::write_u32(pid, operation, head_address, head.wrapping_add(completions.len() as u32))?;
+        Ok(completions)
+    }
+
+    /* Append CQEs to the completion queue, as the kernel does */
+    pub fn push_completions(&self, pid: i32, operation: &Operation, completions: &[IoUringCqe]) -> Result<(), std::io::Error> {
+        let cq_ring = Self::mapped(self.cq_ring)?;
+        let cq_off = &self.params.cq_off;
+        let mask = self.params.cq_entries.wrapping_sub(1);
+
+        let tail_address = cq_ring + cq_off.tail as usize;
+        let mut tail = Self::read_u32(pid, operation, tail_address)?;
+        for cqe in completions {
+            let slot = tail & mask;
+            operation.memory.write(pid, cq_ring + cq_off.cqes as usize + slot as usize * self.cqe_size(), cqe.to_bytes())?;
+            tail = tail.wrapping_add(1);
+        }
+        Self::write_u32(pid, operation, tail_address, tail)?;
+        Ok(())
+    }
+}
+
+
+
+// int io_uring_setup(u32 entries, struct io_uring_params *p);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct IoUringSetup {
+    pub entries: Integer,
+    pub params: TypedStruct<IoUringParams>,
+    pub retval: Option<Fd>,
+}
+impl IoUringSetup {
+    pub fn new(raw: RawSyscall) -> Self {
+        let entries = Integer::new(raw.args[0]);
+        let params = TypedStruct::new(raw.args[1], Direction::InOut);
+        let retval = None;
+        Self { entries, params, retval }
+    }
+}
+impl DecodeEntry for IoUringSetup {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.entries.decode(pid, operation)?;
+        self.params.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for IoUringSetup {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Fd::from(value));
+        // the kernel fills the ring sizes and offsets
+        if (value as isize) >= 0 {
+            self.params.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+impl EncodeEntry for IoUringSetup {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.entries.value;
+        raw.args[1] = self.params.address;
+        self.params.encode(pid, operation)?;
+        Ok(raw)
+    }
+}
+
+
+// int io_uring_enter(unsigned int fd, unsigned int to_submit, unsigned int min_complete,
+//                    unsigned int flags, const void *argp, size_t argsz);
+// The SQEs and CQEs are filled by the engines from the rings, respectively on entry and exit.
+// When forwarded, the buffers carry the memory used by the submitted operations on entry,
+// and the memory filled by the completed operations on exit.
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct IoUringEnter {
+    pub fd: Fd,
+    pub to_submit: Integer,
+    pub min_complete: Integer,
+    pub flags: Integer,
+    pub argp: Address,
+    pub argsz: Size,
+    pub sqes: Vec<IoUringSqe>,
+    pub cqes: Vec<IoUringCqe>,
+    pub buffers: Vec<Buffer>,
+    pub retval: Option<Integer>,
+}
+impl IoUringEnter {
+    pub fn new(raw: RawSyscall) -> Self {
+        let fd = Fd::new(raw.args[0]);
+        let to_submit = Integer::new(raw.args[1]);
+        let min_complete = Integer::new(raw.args[2]);
+        let flags = Integer::new(raw.args[3]);
+        let argp = Address::new(raw.args[4], Direction::In);
+        let argsz = Size::new(raw.args[5]);
+        let sqes = Vec::new();
+        let cqes = Vec::new();
+        let buffers = Vec::new();
+        let retval = None;
+        Self { fd, to_submit, min_complete, flags, argp, argsz, sqes, cqes, buffers, retval }
+    }
+}
+impl DecodeEntry for IoUringEnter {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.to_submit.decode(pid, operation)?;
+        self.min_complete.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        self.argp.decode(pid, operation)?;
+        self.argsz.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for IoUringEnter {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.fd.value;
+        raw.args[1] = self.to_submit.value;
+        raw.args[2] = self.min_complete.value;
+        raw.args[3] = self.flags.value;
+        raw.args[4] = self.argp.value;
+        raw.args[5] = self.argsz.value;
+        for buffer in self.buffers.iter_mut().filter(|b| matches!(b.direction, Direction::In)) {
+            buffer.encode(pid, operation)?;
+        }
+        Ok(raw)
+    }
+}
+
+
+// int io_uring_register(unsigned int fd, unsigned int opcode, void *arg, unsigned int nr_args);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct IoUringRegister {
+    pub fd: Fd,
+    pub opcode: Integer,
+    pub arg: Address,
+    pub nr_args: Integer,
+    pub retval: Option<Integer>,
+}
+impl IoUringRegister {
+    pub fn new(raw: RawSyscall) -> Self {
+        let fd = Fd::new(raw.args[0]);
+        let opcode = Integer::new(raw.args[1]);
+        let arg = Address::new(raw.args[2], Direction::InOut);
+        let nr_args = Integer::new(raw.args[3]);
+        let retval = None;
+        Self { fd, opcode, arg, nr_args, retval }
+    }
+}
+impl DecodeEntry for IoUringRegister {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.opcode.decode(pid, operation)?;
+        self.arg.decode(pid, operation)?;
+        self.nr_args.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for IoUringRegister {
+    fn encode_entry(&mut self, mut raw: RawSyscall, _pid: i32, _operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.fd.value;
+        raw.args[1] = self.opcode.value;
+        raw.args[2] = self.arg.value;
+        raw.args[3] = self.nr_args.value;
+        Ok(raw)
+    }
+}
@@ -34,9 +34,10 @@ impl Ioctl {
     }
 }
 impl DecodeEntry for Ioctl {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.request.decode(pid, operation).unwrap();
-        self.arg.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.request.decode(pid, operation)?;
+        self.arg.decode(pid, operation)?;
+        Ok(())
     }
 }
@@ -0,0 +1,266 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, Fd, Size, Buffer, NullBuffer },
+    syscall::{
+        decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+        encoder::{ EncodeArg, EncodeEntry },
+    },
+    targets::operation::Operation,
+};
+
+
+// int link(const char *oldpath, const char *newpath)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Link {
+    pub oldpath: NullBuffer,
+    pub newpath: NullBuffer,
+    pub retval: Option<Integer>,
+}
+impl Link {
+    pub fn new(raw: RawSyscall) -> Self {
+        let oldpath = NullBuffer::new(raw.args[0], Direction::In);
+        let newpath = NullBuffer::new(raw.args[1], Direction::In);
+        let retval = None;
+        Self { oldpath, newpath, retval }
+    }
+}
+impl DecodeEntry for Link {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.oldpath.decode(pid, operation)?;
+        self.newpath.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Link {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.oldpath.address;
+        self.oldpath.encode(pid, operation)?;
+        raw.args[1] = self.newpath.address;
+        self.newpath.encode(pid, operation)?;
+        Ok(raw)
+    }
+}
+
+// int linkat(int olddirfd, const char *oldpath, int newdirfd, const char *newpath, int flags)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Linkat {
+    pub olddirfd: Fd,
+    pub oldpath: NullBuffer,
+    pub newdirfd: Fd,
+    pub newpath: NullBuffer,
+    pub flags: Integer,
+    pub retval: Option<Integer>,
+}
+impl Linkat {
+    pub fn new(raw: RawSyscall) -> Self {
+        let olddirfd = Fd::new(raw.args[0]);
+        let oldpath = NullBuffer::new(raw.args[1], Direction::In);
+        let newdirfd = Fd::new(raw.args[2]);
+        let newpath = NullBuffer::new(raw.args[3], Direction::In);
+        let flags = Integer::new(raw.args[4]);
+        let retval = None;
+        Self { olddirfd, oldpath, newdirfd, newpath, flags, retval }
+    }
+}
+impl DecodeEntry for Linkat {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.olddirfd.decode(pid, operation)?;
+        self.oldpath.decode(pid, operation)?;
+        self.newdirfd.decode(pid, operation)?;
+        self.newpath.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Linkat {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.olddirfd.value;
+        raw.args[1] = self.oldpath.address;
+        self.oldpath.encode(pid, operation)?;
+        raw.args[2] = self.newdirfd.value;
+        raw.args[3] = self.newpath.address;
+        self.newpath.encode(pid, operation)?;
+        raw.args[4] = self.flags.value;
+        Ok(raw)
+    }
+}
+
+// int symlink(const char *target, const char *linkpath)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Symlink {
+    pub target: NullBuffer,
+    pub linkpath: NullBuffer,
+    pub retval: Option<Integer>,
+}
+impl Symlink {
+    pub fn new(raw: RawSyscall) -> Self {
+        let target = NullBuffer::new(raw.args[0], Direction::In);
+        let linkpath = NullBuffer::new(raw.args[1], Direction::In);
+        let retval = None;
+        Self { target, linkpath, retval }
+    }
+}
+impl DecodeEntry for Symlink {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.target.decode(pid, operation)?;
+        self.linkpath.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Symlink {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.target.address;
+        self.target.encode(pid, operation)?;
+        raw.args[1] = self.linkpath.address;
+        self.linkpath.encode(pid, operation)?;
+        Ok(raw)
+    }
+}
+
+// int symlinkat(const char *target, int newdirfd, const char *linkpath)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Symlinkat {
+    pub target: NullBuffer,
+    pub newdirfd: Fd,
+    pub linkpath: NullBuffer,
+    pub retval: Option<Integer>,
+}
+impl Symlinkat {
+    pub fn new(raw: RawSyscall) -> Self {
+        let target = NullBuffer::new(raw.args[0], Direction::In);
+        let newdirfd = Fd::new(raw.args[1]);
+        let linkpath = NullBuffer::new(raw.args[2], Direction::In);
+        let retval = None;
+        Self { target, newdirfd, linkpath, retval }
+    }
+}
+impl DecodeEntry for Symlinkat {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.target.decode(pid, operation)?;
+        self.newdirfd.decode(pid, operation)?;
+        self.linkpath.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Symlinkat {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.target.address;
+        self.target.encode(pid, operation)?;
+        raw.args[1] = self.newdirfd.value;
+        raw.args[2] = self.linkpath.address;
+        self.linkpath.encode(pid, operation)?;
+        Ok(raw)
+    }
+}
+
+// ssize_t readlink(const char *restrict pathname, char *restrict buf, size_t bufsiz)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Readlink {
+    pub pathname: NullBuffer,
+    pub buf: Buffer,
+    pub bufsiz: Size,
+    pub retval: Option<Size>,
+}
+impl Readlink {
+    pub fn new(raw: RawSyscall) -> Self {
+        let pathname = NullBuffer::new(raw.args[0], Direction::In);
+        let buf = Buffer::new(raw.args[1], Direction::Out, raw.args[2]);
+        let bufsiz = Size::new(raw.args[2]);
+        let retval = None;
+        Self { pathname, buf, bufsiz, retval }
+    }
+}
+impl DecodeEntry for Readlink {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pathname.decode(pid, operation)?;
+        self.bufsiz.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Readlink {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Size::from(value));
+        // The kernel returns the number of bytes written in the buffer
+        if (value as isize) > 0 {
+            self.buf.size = value;
+            self.buf.decode(pid, operation)?;
+            self.buf.content.truncate(value);
+        }
+        Ok(())
+    }
+}
+impl EncodeEntry for Readlink {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.pathname.address;
+        self.pathname.encode(pid, operation)?;
+        raw.args[1] = self.buf.address;
+        raw.args[2] = self.bufsiz.value;
+        Ok(raw)
+    }
+}
+
+// ssize_t readlinkat(int dirfd, const char *restrict pathname, char *restrict buf, size_t bufsiz)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Readlinkat {
+    pub dirfd: Fd,
+    pub pathname: NullBuffer,
+    pub buf: Buffer,
+    pub bufsiz: Size,
+    pub retval: Option<Size>,
+}
+impl Readlinkat {
+    pub fn new(raw: RawSyscall) -> Self {
+        let dirfd = Fd::new(raw.args[0]);
+        let pathname = NullBuffer::new(raw.args[1], Direction::In);
+        let buf = Buffer::new(raw.args[2], Direction::Out, raw.args[3]);
+        let bufsiz = Size::new(raw.args[3]);
+        let retval = None;
+        Self { dirfd, pathname, buf, bufsiz, retval }
+    }
+}
+impl DecodeEntry for Readlinkat {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.dirfd.decode(pid, operation)?;
+        self.pathname.decode(pid, operation)?;
+        self.bufsiz.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Readlinkat {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Size::from(value));
+        // The kernel returns the number of bytes written in the buffer
+        if (value as isize) > 0 {
+            self.buf.size = value;
+            self.buf.decode(pid, operation)?;
+            self.buf.content.truncate(value);
+        }
+        Ok(())
+    }
+}
+impl EncodeEntry for Readlinkat {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.dirfd.value;
+        raw.args[1] = self.pathname.address;
+        self.pathname.encode(pid, operation)?;
+        raw.args[2] = self.buf.address;
+        raw.args[3] = self.bufsiz.value;
+        Ok(raw)
+    }
+}
@@ -36,10 +36,11 @@ impl Lseek {
     }
 }
 impl DecodeEntry for Lseek {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.offset.decode(pid, operation).unwrap();
-        self.whence.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.offset.decode(pid, operation)?;
+        self.whence.decode(pid, operation)?;
+        Ok(())
     }
 }
 impl EncodeEntry for Lseek {
@@ -76,12 +77,13 @@ impl Llseek {
     }
 }
 impl DecodeEntry for Llseek {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.offset_high.decode(pid, operation).unwrap();
-        self.offset_low.decode(pid, operation).unwrap();
-        self.result.decode(pid, operation).unwrap();
-        self.whence.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.offset_high.decode(pid, operation)?;
+        self.offset_low.decode(pid, operation)?;
+        self.result.decode(pid, operation)?;
+        self.whence.decode(pid, operation)?;
+        Ok(())
     }
 }
 impl EncodeEntry for Llseek {
@@ -32,8 +32,9 @@ impl MemfdCreate {
 }
 
 impl DecodeEntry for MemfdCreate {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.name.decode(pid, operation).unwrap();
-        self.flags.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.name.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
     }
 }
\ No newline at end of file
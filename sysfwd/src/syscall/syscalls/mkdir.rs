@@ -0,0 +1,115 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, Fd, NullBuffer },
+    syscall::{
+        decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+        encoder::{ EncodeArg, EncodeEntry },
+    },
+    targets::operation::Operation,
+};
+
+
+// int mkdir(const char *pathname, mode_t mode)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Mkdir {
+    pub pathname: NullBuffer,
+    pub mode: Integer,
+    pub retval: Option<Integer>,
+}
+impl Mkdir {
+    pub fn new(raw: RawSyscall) -> Self {
+        let pathname = NullBuffer::new(raw.args[0], Direction::In);
+        let mode = Integer::new(raw.args[1]);
+        let retval = None;
+        Self { pathname, mode, retval }
+    }
+}
+impl DecodeEntry for Mkdir {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pathname.decode(pid, operation)?;
+        self.mode.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Mkdir {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.pathname.address;
+        self.pathname.encode(pid, operation)?;
+        raw.args[1] = self.mode.value;
+        Ok(raw)
+    }
+}
+
+// int mkdirat(int dirfd, const char *pathname, mode_t mode)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Mkdirat {
+    pub dirfd: Fd,
+    pub pathname: NullBuffer,
+    pub mode: Integer,
+    pub retval: Option<Integer>,
+}
+impl Mkdirat {
+    pub fn new(raw: RawSyscall) -> Self {
+        let dirfd = Fd::new(raw.args[0]);
+        let pathname = NullBuffer::new(raw.args[1], Direction::In);
+        let mode = Integer::new(raw.args[2]);
+        let retval = None;
+        Self { dirfd, pathname, mode, retval }
+    }
+}
+impl DecodeEntry for Mkdirat {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.dirfd.decode(pid, operation)?;
+        self.pathname.decode(pid, operation)?;
+        self.mode.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Mkdirat {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.dirfd.value;
+        raw.args[1] = self.pathname.address;
+        self.pathname.encode(pid, operation)?;
+        raw.args[2] = self.mode.value;
+        Ok(raw)
+    }
+}
+
+// int rmdir(const char *pathname)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Rmdir {
+    pub pathname: NullBuffer,
+    pub retval: Option<Integer>,
+}
+impl Rmdir {
+    pub fn new(raw: RawSyscall) -> Self {
+        let pathname = NullBuffer::new(raw.args[0], Direction::In);
+        let retval = None;
+        Self { pathname, retval }
+    }
+}
+impl DecodeEntry for Rmdir {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pathname.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Rmdir {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.pathname.address;
+        self.pathname.encode(pid, operation)?;
+        Ok(raw)
+    }
+}
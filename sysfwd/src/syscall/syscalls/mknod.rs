@@ -31,10 +31,11 @@ impl Mknod {
     }
 }
 impl DecodeEntry for Mknod {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.pathname.decode(pid, operation).unwrap();
-        self.mode.decode(pid, operation).unwrap();
-        self.dev.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pathname.decode(pid, operation)?;
+        self.mode.decode(pid, operation)?;
+        self.dev.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -62,10 +63,11 @@ impl Mknodat {
 }
 
 impl DecodeEntry for Mknodat {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.dirfd.decode(pid, operation).unwrap();
-        self.pathname.decode(pid, operation).unwrap();
-        self.mode.decode(pid, operation).unwrap();
-        self.dev.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.dirfd.decode(pid, operation)?;
+        self.pathname.decode(pid, operation)?;
+        self.mode.decode(pid, operation)?;
+        self.dev.decode(pid, operation)?;
+        Ok(())
     }
 }
\ No newline at end of file
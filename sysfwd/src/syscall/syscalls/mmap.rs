@@ -36,8 +36,9 @@ impl Brk {
     }
 }
 impl DecodeEntry for Brk {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.addr.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.addr.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -58,8 +59,9 @@ impl Sbrk {
     }
 }
 impl DecodeEntry for Sbrk {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.increment.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.increment.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -90,13 +92,14 @@ impl Mmap {
     }
 }
 impl DecodeEntry for Mmap {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.addr.decode(pid, operation).unwrap();
-        self.length.decode(pid, operation).unwrap();
-        self.prot.decode(pid, operation).unwrap();
-        self.flags.decode(pid, operation).unwrap();
-        self.fd.decode(pid, operation).unwrap();
-        self.offset.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.addr.decode(pid, operation)?;
+        self.length.decode(pid, operation)?;
+        self.prot.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        self.fd.decode(pid, operation)?;
+        self.offset.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -125,12 +128,13 @@ impl Mremap {
     }
 }
 impl DecodeEntry for Mremap {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.old_address.decode(pid, operation).unwrap();
-        self.old_size.decode(pid, operation).unwrap();
-        self.new_size.decode(pid, operation).unwrap();
-        self.flags.decode(pid, operation).unwrap();
-        self.new_address.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.old_address.decode(pid, operation)?;
+        self.old_size.decode(pid, operation)?;
+        self.new_size.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        self.new_address.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -154,9 +158,10 @@ impl Munmap {
     }
 }
 impl DecodeEntry for Munmap {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.addr.decode(pid, operation).unwrap();
-        self.length.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.addr.decode(pid, operation)?;
+        self.length.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -181,10 +186,11 @@ impl Mprotect {
     }
 }
 impl DecodeEntry for Mprotect {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.addr.decode(pid, operation).unwrap();
-        self.len.decode(pid, operation).unwrap();
-        self.prot.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.addr.decode(pid, operation)?;
+        self.len.decode(pid, operation)?;
+        self.prot.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -209,9 +215,10 @@ impl Madvise {
     }
 }
 impl DecodeEntry for Madvise {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.addr.decode(pid, operation).unwrap();
-        self.length.decode(pid, operation).unwrap();
-        self.advice.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.addr.decode(pid, operation)?;
+        self.length.decode(pid, operation)?;
+        self.advice.decode(pid, operation)?;
+        Ok(())
     }
 }
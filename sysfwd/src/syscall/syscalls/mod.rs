@@ -22,7 +22,27 @@ pub mod resource;
 pub mod rseq;
 pub mod getrandom;
 pub mod epoll;
+pub mod poll;
+pub mod eventfd;
+pub mod timerfd;
 pub mod robust_list;
 pub mod tid;
 pub mod lseek;
 pub mod exit;
+pub mod mkdir;
+pub mod unlink;
+pub mod link;
+pub mod chmod;
+pub mod utimensat;
+pub mod cwd;
+pub mod statfs;
+pub mod fsync;
+pub mod xattr;
+pub mod futex;
+pub mod sched;
+pub mod shm;
+pub mod sem;
+pub mod msg;
+pub mod time;
+pub mod timer;
+pub mod io_uring;
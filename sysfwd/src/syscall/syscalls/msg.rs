@@ -0,0 +1,215 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, Size, TypedStruct },
+    syscall::structs::{ MsgBuf, MsqidDs },
+    syscall::{
+        decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+        encoder::{ EncodeArg, EncodeEntry },
+    },
+    targets::operation::Operation,
+};
+
+
+
+/* msgctl() commands using a struct msqid_ds (IPC_64 is masked out) */
+const IPC_64: usize = 0x100;
+const IPC_SET: usize = 1;
+const IPC_STAT: usize = 2;
+const MSG_STAT: usize = 11;
+const MSG_STAT_ANY: usize = 13;
+
+
+// int msgget(key_t key, int msgflg);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Msgget {
+    pub key: Integer,
+    pub msgflg: Integer,
+    pub retval: Option<Integer>,
+}
+impl Msgget {
+    pub fn new(raw: RawSyscall) -> Self {
+        let key = Integer::new(raw.args[0]);
+        let msgflg = Integer::new(raw.args[1]);
+        let retval = None;
+        Self { key, msgflg, retval }
+    }
+}
+impl DecodeEntry for Msgget {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.key.decode(pid, operation)?;
+        self.msgflg.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Msgget {
+    fn encode_entry(&mut self, mut raw: RawSyscall, _pid: i32, _operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.key.value;
+        raw.args[1] = self.msgflg.value;
+        Ok(raw)
+    }
+}
+
+
+// int msgsnd(int msqid, const void *msgp, size_t msgsz, int msgflg);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Msgsnd {
+    pub msqid: Integer,
+    pub msgp: MsgBuf,
+    pub msgsz: Size,
+    pub msgflg: Integer,
+    pub retval: Option<Integer>,
+}
+impl Msgsnd {
+    pub fn new(raw: RawSyscall) -> Self {
+        let msqid = Integer::new(raw.args[0]);
+        let msgp = MsgBuf::new(raw.args[1], Direction::In, raw.args[2]);
+        let msgsz = Size::new(raw.args[2]);
+        let msgflg = Integer::new(raw.args[3]);
+        let retval = None;
+        Self { msqid, msgp, msgsz, msgflg, retval }
+    }
+}
+impl DecodeEntry for Msgsnd {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.msqid.decode(pid, operation)?;
+        self.msgp.decode(pid, operation)?;
+        self.msgsz.decode(pid, operation)?;
+        self.msgflg.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Msgsnd {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.msqid.value;
+        raw.args[1] = self.msgp.address;
+        self.msgp.encode(pid, operation)?;
+        raw.args[2] = self.msgsz.value;
+        raw.args[3] = self.msgflg.value;
+        Ok(raw)
+    }
+}
+
+
+// ssize_t msgrcv(int msqid, void *msgp, size_t msgsz, long msgtyp, int msgflg);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Msgrcv {
+    pub msqid: Integer,
+    pub msgp: MsgBuf,
+    pub msgsz: Size,
+    pub msgtyp: Integer,
+    pub msgflg: Integer,
+    pub retval: Option<Size>,
+}
+impl Msgrcv {
+    pub fn new(raw: RawSyscall) -> Self {
+        let msqid = Integer::new(raw.args[0]);
+        let msgp = MsgBuf::new(raw.args[1], Direction::Out, raw.args[2]);
+        let msgsz = Size::new(raw.args[2]);
+        let msgtyp = Integer::new(raw.args[3]);
+        let msgflg = Integer::new(raw.args[4]);
+        let retval = None;
+        Self { msqid, msgp, msgsz, msgtyp, msgflg, retval }
+    }
+}
+impl DecodeEntry for Msgrcv {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.msqid.decode(pid, operation)?;
+        self.msgsz.decode(pid, operation)?;
+        self.msgtyp.decode(pid, operation)?;
+        self.msgflg.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Msgrcv {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Size::from(value));
+        // the return value is the number of bytes copied into mtext
+        if (value as isize) >= 0 {
+            self.msgp.size = value;
+            self.msgp.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+impl EncodeEntry for Msgrcv {
+    fn encode_entry(&mut self, mut raw: RawSyscall, _pid: i32, _operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.msqid.value;
+        raw.args[1] = self.msgp.address;
+        raw.args[2] = self.msgsz.value;
+        raw.args[3] = self.msgtyp.value;
+        raw.args[4] = self.msgflg.value;
+        Ok(raw)
+    }
+}
+
+
+// int msgctl(int msqid, int cmd, struct msqid_ds *buf);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Msgctl {
+    pub msqid: Integer,
+    pub cmd: Integer,
+    pub buf: Option<TypedStruct<MsqidDs>>,
+    pub retval: Option<Integer>,
+}
+impl Msgctl {
+    pub fn new(raw: RawSyscall) -> Self {
+        let msqid = Integer::new(raw.args[0]);
+        let cmd = Integer::new(raw.args[1]);
+        // IPC_INFO and MSG_INFO use another structure which is not decoded
+        let buf = match raw.args[1] & !IPC_64 {
+            IPC_SET => Some(TypedStruct::new(raw.args[2], Direction::In)),
+            IPC_STAT | MSG_STAT | MSG_STAT_ANY => Some(TypedStruct::new(raw.args[2], Direction::Out)),
+            _ => None,
+        };
+        let retval = None;
+        Self { msqid, cmd, buf, retval }
+    }
+}
+impl DecodeEntry for Msgctl {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.msqid.decode(pid, operation)?;
+        self.cmd.decode(pid, operation)?;
+        if let Some(buf) = &mut self.buf {
+            if matches!(buf.direction, Direction::In) {
+                buf.decode(pid, operation)?;
+            }
+        }
+        Ok(())
+    }
+}
+impl DecodeExit for Msgctl {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if let Some(buf) = &mut self.buf {
+            if matches!(buf.direction, Direction::Out) && (value as isize) >= 0 {
+                buf.decode(pid, operation)?;
+            }
+        }
+        Ok(())
+    }
+}
+impl EncodeEntry for Msgctl {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.msqid.value;
+        raw.args[1] = self.cmd.value;
+        if let Some(buf) = &mut self.buf {
+            raw.args[2] = buf.address;
+            if matches!(buf.direction, Direction::In) {
+                buf.encode(pid, operation)?;
+            }
+        }
+        Ok(raw)
+    }
+}
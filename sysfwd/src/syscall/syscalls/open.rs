@@ -30,8 +30,9 @@ impl Close {
     }
 }
 impl DecodeEntry for Close {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        Ok(())
     }
 }
 impl EncodeEntry for Close {
@@ -59,15 +60,16 @@ impl Creat {
     }
 }
 impl DecodeEntry for Creat {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.pathname.decode(pid, operation).unwrap();
-        self.mode.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pathname.decode(pid, operation)?;
+        self.mode.decode(pid, operation)?;
+        Ok(())
     }
 }
 impl EncodeEntry for Creat {
     fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
         raw.args[0] = self.pathname.address;
-        self.pathname.encode(pid, operation).unwrap();
+        self.pathname.encode(pid, operation)?;
         raw.args[1] = self.mode.value;
         Ok(raw)
     }
@@ -94,16 +96,17 @@ impl Open {
     }
 }
 impl DecodeEntry for Open {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.pathname.decode(pid, operation).unwrap();
-        self.flags.decode(pid, operation).unwrap();
-        self.mode.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pathname.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        self.mode.decode(pid, operation)?;
+        Ok(())
     }
 }
 impl EncodeEntry for Open {
     fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
         raw.args[0] = self.pathname.address;
-        self.pathname.encode(pid, operation).unwrap();
+        self.pathname.encode(pid, operation)?;
         raw.args[1] = self.mode.value;
         Ok(raw)
     }
@@ -140,18 +143,19 @@ impl Openat {
     }
 }
 impl DecodeEntry for Openat {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.dirfd.decode(pid, operation).unwrap();
-        self.pathname.decode(pid, operation).unwrap();
-        self.flags.decode(pid, operation).unwrap();
-        self.mode.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.dirfd.decode(pid, operation)?;
+        self.pathname.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        self.mode.decode(pid, operation)?;
+        Ok(())
     }
 }
 impl EncodeEntry for Openat {
     fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
         raw.args[0] = self.dirfd.value;
         raw.args[1] = self.pathname.address;
-        self.pathname.encode(pid, operation).unwrap();
+        self.pathname.encode(pid, operation)?;
         raw.args[2] = self.flags.value;
         raw.args[3] = self.mode.value;
         Ok(raw)
@@ -180,20 +184,21 @@ impl Openat2 {
     }
 }
 impl DecodeEntry for Openat2 {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.dirfd.decode(pid, operation).unwrap();
-        self.pathname.decode(pid, operation).unwrap();
-        self.how.decode(pid, operation).unwrap();
-        self.size.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.dirfd.decode(pid, operation)?;
+        self.pathname.decode(pid, operation)?;
+        self.how.decode(pid, operation)?;
+        self.size.decode(pid, operation)?;
+        Ok(())
     }
 }
 impl EncodeEntry for Openat2 {
     fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
         raw.args[0] = self.dirfd.value;
         raw.args[1] = self.pathname.address;
-        self.pathname.encode(pid, operation).unwrap();
+        self.pathname.encode(pid, operation)?;
         raw.args[2] = self.how.address;
-        self.how.encode(pid, operation).unwrap();
+        self.how.encode(pid, operation)?;
         raw.args[3] = self.size.value;
         Ok(raw)
     }
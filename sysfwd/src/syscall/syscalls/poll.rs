@@ -0,0 +1,190 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, Size, Struct, TypedStruct, TypedArray },
+    syscall::structs::{ FdSet, PollFd, Timespec, Timeval, NR_OPEN },
+    syscall::decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+    targets::operation::Operation,
+};
+
+
+
+// int poll(struct pollfd *fds, nfds_t nfds, int timeout);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Poll {
+    pub fds: TypedArray<PollFd>,
+    pub nfds: Size,
+    pub timeout: Integer,
+    pub retval: Option<Integer>,
+}
+impl Poll {
+    pub fn new(raw: RawSyscall) -> Self {
+        let fds = TypedArray::new(raw.args[0], Direction::InOut, raw.args[1].min(NR_OPEN));
+        let nfds = Size::new(raw.args[1]);
+        let timeout = Integer::new(raw.args[2]);
+        let retval = None;
+        Self { fds, nfds, timeout, retval }
+    }
+}
+impl DecodeEntry for Poll {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fds.decode(pid, operation)?;
+        self.nfds.decode(pid, operation)?;
+        self.timeout.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Poll {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        // The kernel fills the revents field of each entry
+        if (value as isize) >= 0 {
+            self.fds.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+// int ppoll(struct pollfd *fds, nfds_t nfds, const struct timespec *_Nullable tmo_p, const sigset_t *_Nullable sigmask, size_t sigsetsize);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Ppoll {
+    pub fds: TypedArray<PollFd>,
+    pub nfds: Size,
+    pub tmo_p: TypedStruct<Timespec>,
+    pub sigmask: Struct,
+    pub sigsetsize: Size,
+    pub retval: Option<Integer>,
+}
+impl Ppoll {
+    pub fn new(raw: RawSyscall) -> Self {
+        let fds = TypedArray::new(raw.args[0], Direction::InOut, raw.args[1].min(NR_OPEN));
+        let nfds = Size::new(raw.args[1]);
+        let tmo_p = TypedStruct::new(raw.args[2], Direction::InOut);
+        let sigmask = Struct::new(raw.args[3], Direction::In);
+        let sigsetsize = Size::new(raw.args[4]);
+        let retval = None;
+        Self { fds, nfds, tmo_p, sigmask, sigsetsize, retval }
+    }
+}
+impl DecodeEntry for Ppoll {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fds.decode(pid, operation)?;
+        self.nfds.decode(pid, operation)?;
+        self.tmo_p.decode(pid, operation)?;
+        self.sigmask.decode(pid, operation)?;
+        self.sigsetsize.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Ppoll {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        // The kernel fills the revents field of each entry and updates the remaining time
+        if (value as isize) >= 0 {
+            self.fds.decode(pid, operation)?;
+            self.tmo_p.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+// int select(int nfds, fd_set *_Nullable restrict readfds, fd_set *_Nullable restrict writefds, fd_set *_Nullable restrict exceptfds, struct timeval *_Nullable restrict timeout);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Select {
+    pub nfds: Integer,
+    pub readfds: FdSet,
+    pub writefds: FdSet,
+    pub exceptfds: FdSet,
+    pub timeout: TypedStruct<Timeval>,
+    pub retval: Option<Integer>,
+}
+impl Select {
+    pub fn new(raw: RawSyscall) -> Self {
+        let nfds = Integer::new(raw.args[0]);
+        let readfds = FdSet::new(raw.args[1], Direction::InOut, raw.args[0]);
+        let writefds = FdSet::new(raw.args[2], Direction::InOut, raw.args[0]);
+        let exceptfds = FdSet::new(raw.args[3], Direction::InOut, raw.args[0]);
+        let timeout = TypedStruct::new(raw.args[4], Direction::InOut);
+        let retval = None;
+        Self { nfds, readfds, writefds, exceptfds, timeout, retval }
+    }
+}
+impl DecodeEntry for Select {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.nfds.decode(pid, operation)?;
+        self.readfds.decode(pid, operation)?;
+        self.writefds.decode(pid, operation)?;
+        self.exceptfds.decode(pid, operation)?;
+        self.timeout.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Select {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        // On return, the sets only contain the ready file descriptors
+        if (value as isize) >= 0 {
+            self.readfds.decode(pid, operation)?;
+            self.writefds.decode(pid, operation)?;
+            self.exceptfds.decode(pid, operation)?;
+            self.timeout.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+// int pselect6(int nfds, fd_set *readfds, fd_set *writefds, fd_set *exceptfds, struct timespec *timeout, void *sig);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Pselect6 {
+    pub nfds: Integer,
+    pub readfds: FdSet,
+    pub writefds: FdSet,
+    pub exceptfds: FdSet,
+    pub timeout: TypedStruct<Timespec>,
+    pub sig: Struct,
+    pub retval: Option<Integer>,
+}
+impl Pselect6 {
+    pub fn new(raw: RawSyscall) -> Self {
+        let nfds = Integer::new(raw.args[0]);
+        let readfds = FdSet::new(raw.args[1], Direction::InOut, raw.args[0]);
+        let writefds = FdSet::new(raw.args[2], Direction::InOut, raw.args[0]);
+        let exceptfds = FdSet::new(raw.args[3], Direction::InOut, raw.args[0]);
+        let timeout = TypedStruct::new(raw.args[4], Direction::InOut);
+        let sig = Struct::new(raw.args[5], Direction::In);
+        let retval = None;
+        Self { nfds, readfds, writefds, exceptfds, timeout, sig, retval }
+    }
+}
+impl DecodeEntry for Pselect6 {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.nfds.decode(pid, operation)?;
+        self.readfds.decode(pid, operation)?;
+        self.writefds.decode(pid, operation)?;
+        self.exceptfds.decode(pid, operation)?;
+        self.timeout.decode(pid, operation)?;
+        self.sig.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Pselect6 {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        // On return, the sets only contain the ready file descriptors
+        if (value as isize) >= 0 {
+            self.readfds.decode(pid, operation)?;
+            self.writefds.decode(pid, operation)?;
+            self.exceptfds.decode(pid, operation)?;
+            self.timeout.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
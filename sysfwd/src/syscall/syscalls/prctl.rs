@@ -35,12 +35,13 @@ impl Prctl {
     }
 }
 impl DecodeEntry for Prctl {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.option.decode(pid, operation).unwrap();
-        self.arg2.decode(pid, operation).unwrap();
-        self.arg3.decode(pid, operation).unwrap();
-        self.arg4.decode(pid, operation).unwrap();
-        self.arg5.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.option.decode(pid, operation)?;
+        self.arg2.decode(pid, operation)?;
+        self.arg3.decode(pid, operation)?;
+        self.arg4.decode(pid, operation)?;
+        self.arg5.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -64,8 +65,9 @@ impl ArchPrctl {
     }
 }
 impl DecodeEntry for ArchPrctl {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.code.decode(pid, operation).unwrap();
-        self.addr.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.code.decode(pid, operation)?;
+        self.addr.decode(pid, operation)?;
+        Ok(())
     }
 }
\ No newline at end of file
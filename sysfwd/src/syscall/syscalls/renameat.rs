@@ -32,9 +32,10 @@ impl Rename {
 }
 
 impl DecodeEntry for Rename {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.oldpath.decode(pid, operation).unwrap();
-        self.newpath.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.oldpath.decode(pid, operation)?;
+        self.newpath.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -62,11 +63,12 @@ impl Renameat {
 }
 
 impl DecodeEntry for Renameat {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.olddirfd.decode(pid, operation).unwrap();
-        self.oldpath.decode(pid, operation).unwrap();
-        self.newdirfd.decode(pid, operation).unwrap();
-        self.newpath.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.olddirfd.decode(pid, operation)?;
+        self.oldpath.decode(pid, operation)?;
+        self.newdirfd.decode(pid, operation)?;
+        self.newpath.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -97,11 +99,12 @@ impl Renameat2 {
 }
 
 impl DecodeEntry for Renameat2 {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.olddirfd.decode(pid, operation).unwrap();
-        self.oldpath.decode(pid, operation).unwrap();
-        self.newdirfd.decode(pid, operation).unwrap();
-        self.newpath.decode(pid, operation).unwrap();
-        self.flags.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.olddirfd.decode(pid, operation)?;
+        self.oldpath.decode(pid, operation)?;
+        self.newdirfd.decode(pid, operation)?;
+        self.newpath.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
     }
 }
\ No newline at end of file
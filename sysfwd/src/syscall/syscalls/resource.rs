@@ -29,9 +29,10 @@ impl Getrlimit {
     }
 }
 impl DecodeEntry for Getrlimit {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.resource.decode(pid, operation).unwrap();
-        self.rlim.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.resource.decode(pid, operation)?;
+        self.rlim.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -54,9 +55,10 @@ impl Setrlimit {
     }
 }
 impl DecodeEntry for Setrlimit {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.resource.decode(pid, operation).unwrap();
-        self.rlim.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.resource.decode(pid, operation)?;
+        self.rlim.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -83,11 +85,12 @@ impl Prlimit {
     }
 }
 impl DecodeEntry for Prlimit {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.pid.decode(pid, operation).unwrap();
-        self.resource.decode(pid, operation).unwrap();
-        self.new_limit.decode(pid, operation).unwrap();
-        self.old_limit.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pid.decode(pid, operation)?;
+        self.resource.decode(pid, operation)?;
+        self.new_limit.decode(pid, operation)?;
+        self.old_limit.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -110,8 +113,9 @@ impl Getrusage {
     }
 }
 impl DecodeEntry for Getrusage {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.who.decode(pid, operation).unwrap();
-        self.usage.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.who.decode(pid, operation)?;
+        self.usage.decode(pid, operation)?;
+        Ok(())
     }
 }
@@ -5,7 +5,8 @@ use serde::{ Serialize, Deserialize };
 use decoding_macro::DecodeExit;
 use crate::{
     syscall::RawSyscall,
-    syscall::args::{ Direction, Integer, Size, Address },
+    syscall::args::{ Direction, Integer, Size, TypedStruct },
+    syscall::structs::RobustList,
     syscall::decoder::{ DecodeArg, DecodeEntry, DecodeExit },
     targets::operation::Operation,
 };
@@ -15,27 +16,48 @@ use crate::{
  // long syscall(SYS_get_robust_list, int pid, struct robust_list_head **head_ptr, size_t *len_ptr);
 #[derive(Serialize, Deserialize)]
 #[derive(Clone, Debug)]
-#[derive(DecodeExit)]
 pub struct GetRobustList {
     pub pid: Integer,
-    pub head_ptr: Address,
-    pub len_ptr: Address,
+    pub head_ptr: TypedStruct<u64>,
+    pub len_ptr: TypedStruct<u64>,
+    pub head: Option<RobustList>,
     pub retval: Option<Integer>,
 }
 impl GetRobustList {
     pub fn new(raw: RawSyscall) -> Self {
         let pid = Integer::new(raw.args[0]);
-        let head_ptr = Address::new(raw.args[1], Direction::InOut);
-        let len_ptr = Address::new(raw.args[2], Direction::InOut);
+        let head_ptr = TypedStruct::new(raw.args[1], Direction::Out);
+        let len_ptr = TypedStruct::new(raw.args[2], Direction::Out);
+        let head = None;
         let retval = None;
-        Self { pid, head_ptr, len_ptr, retval }
+        Self { pid, head_ptr, len_ptr, head, retval }
     }
 }
 impl DecodeEntry for GetRobustList {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.pid.decode(pid, operation).unwrap();
-        self.head_ptr.decode(pid, operation).unwrap();
-        self.len_ptr.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pid.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for GetRobustList {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if value != 0 {
+            return Ok(());
+        }
+
+        self.head_ptr.decode(pid, operation)?;
+        self.len_ptr.decode(pid, operation)?;
+
+        // The list belongs to the tracee only if it asked for its own (pid 0)
+        if let Some(address) = self.head_ptr.content {
+            if self.pid.value == 0 || self.pid.value as i32 == pid {
+                let mut head = RobustList::new(address as usize, Direction::Out);
+                head.decode(pid, operation)?;
+                self.head = Some(head);
+            }
+        }
+        Ok(())
     }
 }
 
@@ -45,24 +67,22 @@ impl DecodeEntry for GetRobustList {
 #[derive(Clone, Debug)]
 #[derive(DecodeExit)]
 pub struct SetRobustList {
-    pub pid: Integer,
-    pub head_ptr: Address,
-    pub len_ptr: Size,
+    pub head: RobustList,
+    pub len: Size,
     pub retval: Option<Integer>,
 }
 impl SetRobustList {
     pub fn new(raw: RawSyscall) -> Self {
-        let pid = Integer::new(raw.args[0]);
-        let head_ptr = Address::new(raw.args[1], Direction::In);
-        let len_ptr = Size::new(raw.args[2]);
+        let head = RobustList::new(raw.args[0], Direction::In);
+        let len = Size::new(raw.args[1]);
         let retval = None;
-        Self { pid, head_ptr, len_ptr, retval }
+        Self { head, len, retval }
     }
 }
 impl DecodeEntry for SetRobustList {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.pid.decode(pid, operation).unwrap();
-        self.head_ptr.decode(pid, operation).unwrap();
-        self.len_ptr.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.head.decode(pid, operation)?;
+        self.len.decode(pid, operation)?;
+        Ok(())
     }
 }
@@ -33,10 +33,11 @@ impl Rseq {
     }
 }
 impl DecodeEntry for Rseq {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.rseq.decode(pid, operation).unwrap();
-        self.rseq_len.decode(pid, operation).unwrap();
-        self.flags.decode(pid, operation).unwrap();
-        self.sig.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.rseq.decode(pid, operation)?;
+        self.rseq_len.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        self.sig.decode(pid, operation)?;
+        Ok(())
     }
 }
@@ -0,0 +1,355 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, Size, TypedStruct },
+    syscall::structs::{ CpuSet, SchedAttr, Timespec },
+    syscall::decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+    targets::operation::Operation,
+};
+
+
+
+// int sched_yield(void);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct SchedYield {
+    pub retval: Option<Integer>,
+}
+impl SchedYield {
+    pub fn new(_raw: RawSyscall) -> Self {
+        let retval = None;
+        Self { retval }
+    }
+}
+impl DecodeEntry for SchedYield { }
+
+
+// int sched_setparam(pid_t pid, const struct sched_param *param);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct SchedSetparam {
+    pub pid: Integer,
+    pub param: TypedStruct<u32>,
+    pub retval: Option<Integer>,
+}
+impl SchedSetparam {
+    pub fn new(raw: RawSyscall) -> Self {
+        let pid = Integer::new(raw.args[0]);
+        let param = TypedStruct::new(raw.args[1], Direction::In);
+        let retval = None;
+        Self { pid, param, retval }
+    }
+}
+impl DecodeEntry for SchedSetparam {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pid.decode(pid, operation)?;
+        self.param.decode(pid, operation)?;
+        Ok(())
+    }
+}
+
+
+// int sched_getparam(pid_t pid, struct sched_param *param);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct SchedGetparam {
+    pub pid: Integer,
+    pub param: TypedStruct<u32>,
+    pub retval: Option<Integer>,
+}
+impl SchedGetparam {
+    pub fn new(raw: RawSyscall) -> Self {
+        let pid = Integer::new(raw.args[0]);
+        let param = TypedStruct::new(raw.args[1], Direction::Out);
+        let retval = None;
+        Self { pid, param, retval }
+    }
+}
+impl DecodeEntry for SchedGetparam {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pid.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for SchedGetparam {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if value == 0 {
+            self.param.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+
+// int sched_setscheduler(pid_t pid, int policy, const struct sched_param *param);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct SchedSetscheduler {
+    pub pid: Integer,
+    pub policy: Integer,
+    pub param: TypedStruct<u32>,
+    pub retval: Option<Integer>,
+}
+impl SchedSetscheduler {
+    pub fn new(raw: RawSyscall) -> Self {
+        let pid = Integer::new(raw.args[0]);
+        let policy = Integer::new(raw.args[1]);
+        let param = TypedStruct::new(raw.args[2], Direction::In);
+        let retval = None;
+        Self { pid, policy, param, retval }
+    }
+}
+impl DecodeEntry for SchedSetscheduler {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pid.decode(pid, operation)?;
+        self.policy.decode(pid, operation)?;
+        self.param.decode(pid, operation)?;
+        Ok(())
+    }
+}
+
+
+// int sched_getscheduler(pid_t pid);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct SchedGetscheduler {
+    pub pid: Integer,
+    pub retval: Option<Integer>,
+}
+impl SchedGetscheduler {
+    pub fn new(raw: RawSyscall) -> Self {
+        let pid = Integer::new(raw.args[0]);
+        let retval = None;
+        Self { pid, retval }
+    }
+}
+impl DecodeEntry for SchedGetscheduler {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pid.decode(pid, operation)?;
+        Ok(())
+    }
+}
+
+
+// int sched_get_priority_max(int policy);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct SchedGetPriorityMax {
+    pub policy: Integer,
+    pub retval: Option<Integer>,
+}
+impl SchedGetPriorityMax {
+    pub fn new(raw: RawSyscall) -> Self {
+        let policy = Integer::new(raw.args[0]);
+        let retval = None;
+        Self { policy, retval }
+    }
+}
+impl DecodeEntry for SchedGetPriorityMax {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.policy.decode(pid, operation)?;
+        Ok(())
+    }
+}
+
+
+// int sched_get_priority_min(int policy);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct SchedGetPriorityMin {
+    pub policy: Integer,
+    pub retval: Option<Integer>,
+}
+impl SchedGetPriorityMin {
+    pub fn new(raw: RawSyscall) -> Self {
+        let policy = Integer::new(raw.args[0]);
+        let retval = None;
+        Self { policy, retval }
+    }
+}
+impl DecodeEntry for SchedGetPriorityMin {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.policy.decode(pid, operation)?;
+        Ok(())
+    }
+}
+
+
+// int sched_rr_get_interval(pid_t pid, struct timespec *tp);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct SchedRrGetInterval {
+    pub pid: Integer,
+    pub tp: TypedStruct<Timespec>,
+    pub retval: Option<Integer>,
+}
+impl SchedRrGetInterval {
+    pub fn new(raw: RawSyscall) -> Self {
+        let pid = Integer::new(raw.args[0]);
+        let tp = TypedStruct::new(raw.args[1], Direction::Out);
+        let retval = None;
+        Self { pid, tp, retval }
+    }
+}
+impl DecodeEntry for SchedRrGetInterval {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pid.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for SchedRrGetInterval {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if value == 0 {
+            self.tp.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+
+// int sched_setaffinity(pid_t pid, size_t cpusetsize, const cpu_set_t *mask);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct SchedSetaffinity {
+    pub pid: Integer,
+    pub cpusetsize: Size,
+    pub mask: CpuSet,
+    pub retval: Option<Integer>,
+}
+impl SchedSetaffinity {
+    pub fn new(raw: RawSyscall) -> Self {
+        let pid = Integer::new(raw.args[0]);
+        let cpusetsize = Size::new(raw.args[1]);
+        let mask = CpuSet::new(raw.args[2], Direction::In, raw.args[1]);
+        let retval = None;
+        Self { pid, cpusetsize, mask, retval }
+    }
+}
+impl DecodeEntry for SchedSetaffinity {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pid.decode(pid, operation)?;
+        self.cpusetsize.decode(pid, operation)?;
+        self.mask.decode(pid, operation)?;
+        Ok(())
+    }
+}
+
+
+// int sched_getaffinity(pid_t pid, size_t cpusetsize, cpu_set_t *mask);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct SchedGetaffinity {
+    pub pid: Integer,
+    pub cpusetsize: Size,
+    pub mask: CpuSet,
+    pub retval: Option<Integer>,
+}
+impl SchedGetaffinity {
+    pub fn new(raw: RawSyscall) -> Self {
+        let pid = Integer::new(raw.args[0]);
+        let cpusetsize = Size::new(raw.args[1]);
+        let mask = CpuSet::new(raw.args[2], Direction::Out, raw.args[1]);
+        let retval = None;
+        Self { pid, cpusetsize, mask, retval }
+    }
+}
+impl DecodeEntry for SchedGetaffinity {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pid.decode(pid, operation)?;
+        self.cpusetsize.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for SchedGetaffinity {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        // The raw syscall returns the number of bytes written into the mask
+        if (value as isize) > 0 {
+            self.mask.size = value;
+            self.mask.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+
+// int sched_setattr(pid_t pid, struct sched_attr *attr, unsigned int flags);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct SchedSetattr {
+    pub pid: Integer,
+    pub attr: TypedStruct<SchedAttr>,
+    pub flags: Integer,
+    pub retval: Option<Integer>,
+}
+impl SchedSetattr {
+    pub fn new(raw: RawSyscall) -> Self {
+        let pid = Integer::new(raw.args[0]);
+        let attr = TypedStruct::new(raw.args[1], Direction::In);
+        let flags = Integer::new(raw.args[2]);
+        let retval = None;
+        Self { pid, attr, flags, retval }
+    }
+}
+impl DecodeEntry for SchedSetattr {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pid.decode(pid, operation)?;
+        self.attr.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
+    }
+}
+
+
+// int sched_getattr(pid_t pid, struct sched_attr *attr, unsigned int size, unsigned int flags);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct SchedGetattr {
+    pub pid: Integer,
+    pub attr: TypedStruct<SchedAttr>,
+    pub size: Size,
+    pub flags: Integer,
+    pub retval: Option<Integer>,
+}
+impl SchedGetattr {
+    pub fn new(raw: RawSyscall) -> Self {
+        let pid = Integer::new(raw.args[0]);
+        let attr = TypedStruct::new(raw.args[1], Direction::Out);
+        let size = Size::new(raw.args[2]);
+        let flags = Integer::new(raw.args[3]);
+        let retval = None;
+        Self { pid, attr, size, flags, retval }
+    }
+}
+impl DecodeEntry for SchedGetattr {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pid.decode(pid, operation)?;
+        self.size.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for SchedGetattr {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if value == 0 {
+            self.attr.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
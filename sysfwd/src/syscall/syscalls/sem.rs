@@ -0,0 +1,181 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, TypedArray, TypedStruct },
+    syscall::structs::{ Sembuf, Timespec },
+    syscall::{
+        decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+        encoder::{ EncodeArg, EncodeEntry },
+    },
+    targets::operation::Operation,
+};
+
+
+
+/* The kernel rejects semop() with more operations than this (SEMOPM) */
+const SEMOPM: usize = 500;
+
+
+// int semget(key_t key, int nsems, int semflg);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Semget {
+    pub key: Integer,
+    pub nsems: Integer,
+    pub semflg: Integer,
+    pub retval: Option<Integer>,
+}
+impl Semget {
+    pub fn new(raw: RawSyscall) -> Self {
+        let key = Integer::new(raw.args[0]);
+        let nsems = Integer::new(raw.args[1]);
+        let semflg = Integer::new(raw.args[2]);
+        let retval = None;
+        Self { key, nsems, semflg, retval }
+    }
+}
+impl DecodeEntry for Semget {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.key.decode(pid, operation)?;
+        self.nsems.decode(pid, operation)?;
+        self.semflg.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Semget {
+    fn encode_entry(&mut self, mut raw: RawSyscall, _pid: i32, _operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.key.value;
+        raw.args[1] = self.nsems.value;
+        raw.args[2] = self.semflg.value;
+        Ok(raw)
+    }
+}
+
+
+// int semop(int semid, struct sembuf *sops, size_t nsops);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Semop {
+    pub semid: Integer,
+    pub sops: TypedArray<Sembuf>,
+    pub nsops: Integer,
+    pub retval: Option<Integer>,
+}
+impl Semop {
+    pub fn new(raw: RawSyscall) -> Self {
+        let semid = Integer::new(raw.args[0]);
+        let sops = TypedArray::new(raw.args[1], Direction::In, raw.args[2].min(SEMOPM));
+        let nsops = Integer::new(raw.args[2]);
+        let retval = None;
+        Self { semid, sops, nsops, retval }
+    }
+}
+impl DecodeEntry for Semop {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.semid.decode(pid, operation)?;
+        self.sops.decode(pid, operation)?;
+        self.nsops.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Semop {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.semid.value;
+        raw.args[1] = self.sops.address;
+        self.sops.encode(pid, operation)?;
+        raw.args[2] = self.nsops.value;
+        Ok(raw)
+    }
+}
+
+
+// int semtimedop(int semid, struct sembuf *sops, size_t nsops, const struct timespec *timeout);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Semtimedop {
+    pub semid: Integer,
+    pub sops: TypedArray<Sembuf>,
+    pub nsops: Integer,
+    pub timeout: TypedStruct<Timespec>,
+    pub retval: Option<Integer>,
+}
+impl Semtimedop {
+    pub fn new(raw: RawSyscall) -> Self {
+        let semid = Integer::new(raw.args[0]);
+        let sops = TypedArray::new(raw.args[1], Direction::In, raw.args[2].min(SEMOPM));
+        let nsops = Integer::new(raw.args[2]);
+        let timeout = TypedStruct::new(raw.args[3], Direction::In);
+        let retval = None;
+        Self { semid, sops, nsops, timeout, retval }
+    }
+}
+impl DecodeEntry for Semtimedop {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.semid.decode(pid, operation)?;
+        self.sops.decode(pid, operation)?;
+        self.nsops.decode(pid, operation)?;
+        self.timeout.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Semtimedop {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.semid.value;
+        raw.args[1] = self.sops.address;
+        self.sops.encode(pid, operation)?;
+        raw.args[2] = self.nsops.value;
+        raw.args[3] = self.timeout.address;
+        self.timeout.encode(pid, operation)?;
+        Ok(raw)
+    }
+}
+
+
+// int semctl(int semid, int semnum, int cmd, ...);
+// The optional union semun argument is kept as a raw value.
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Semctl {
+    pub semid: Integer,
+    pub semnum: Integer,
+    pub cmd: Integer,
+    pub arg: Integer,
+    pub retval: Option<Integer>,
+}
+impl Semctl {
+    pub fn new(raw: RawSyscall) -> Self {
+        let semid = Integer::new(raw.args[0]);
+        let semnum = Integer::new(raw.args[1]);
+        let cmd = Integer::new(raw.args[2]);
+        let arg = Integer::new(raw.args[3]);
+        let retval = None;
+        Self { semid, semnum, cmd, arg, retval }
+    }
+}
+impl DecodeEntry for Semctl {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.semid.decode(pid, operation)?;
+        self.semnum.decode(pid, operation)?;
+        self.cmd.decode(pid, operation)?;
+        self.arg.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Semctl {
+    fn encode_entry(&mut self, mut raw: RawSyscall, _pid: i32, _operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.semid.value;
+        raw.args[1] = self.semnum.value;
+        raw.args[2] = self.cmd.value;
+        raw.args[3] = self.arg.value;
+        Ok(raw)
+    }
+}
@@ -0,0 +1,175 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, Size, Address, TypedStruct },
+    syscall::structs::ShmidDs,
+    syscall::{
+        decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+        encoder::{ EncodeArg, EncodeEntry },
+    },
+    targets::operation::Operation,
+};
+
+
+
+/* shmctl() commands using a struct shmid_ds (IPC_64 is masked out) */
+const IPC_64: usize = 0x100;
+const IPC_SET: usize = 1;
+const IPC_STAT: usize = 2;
+const SHM_STAT: usize = 13;
+const SHM_STAT_ANY: usize = 15;
+
+
+// int shmget(key_t key, size_t size, int shmflg);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Shmget {
+    pub key: Integer,
+    pub size: Size,
+    pub shmflg: Integer,
+    pub retval: Option<Integer>,
+}
+impl Shmget {
+    pub fn new(raw: RawSyscall) -> Self {
+        let key = Integer::new(raw.args[0]);
+        let size = Size::new(raw.args[1]);
+        let shmflg = Integer::new(raw.args[2]);
+        let retval = None;
+        Self { key, size, shmflg, retval }
+    }
+}
+impl DecodeEntry for Shmget {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.key.decode(pid, operation)?;
+        self.size.decode(pid, operation)?;
+        self.shmflg.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Shmget {
+    fn encode_entry(&mut self, mut raw: RawSyscall, _pid: i32, _operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.key.value;
+        raw.args[1] = self.size.value;
+        raw.args[2] = self.shmflg.value;
+        Ok(raw)
+    }
+}
+
+
+// void *shmat(int shmid, const void *shmaddr, int shmflg);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Shmat {
+    pub shmid: Integer,
+    pub shmaddr: Address,
+    pub shmflg: Integer,
+    pub retval: Option<Address>,
+}
+impl Shmat {
+    pub fn new(raw: RawSyscall) -> Self {
+        let shmid = Integer::new(raw.args[0]);
+        let shmaddr = Address::new(raw.args[1], Direction::In);
+        let shmflg = Integer::new(raw.args[2]);
+        let retval = None;
+        Self { shmid, shmaddr, shmflg, retval }
+    }
+}
+impl DecodeEntry for Shmat {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.shmid.decode(pid, operation)?;
+        self.shmaddr.decode(pid, operation)?;
+        self.shmflg.decode(pid, operation)?;
+        Ok(())
+    }
+}
+
+
+// int shmdt(const void *shmaddr);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Shmdt {
+    pub shmaddr: Address,
+    pub retval: Option<Integer>,
+}
+impl Shmdt {
+    pub fn new(raw: RawSyscall) -> Self {
+        let shmaddr = Address::new(raw.args[0], Direction::In);
+        let retval = None;
+        Self { shmaddr, retval }
+    }
+}
+impl DecodeEntry for Shmdt {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.shmaddr.decode(pid, operation)?;
+        Ok(())
+    }
+}
+
+
+// int shmctl(int shmid, int cmd, struct shmid_ds *buf);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Shmctl {
+    pub shmid: Integer,
+    pub cmd: Integer,
+    pub buf: Option<TypedStruct<ShmidDs>>,
+    pub retval: Option<Integer>,
+}
+impl Shmctl {
+    pub fn new(raw: RawSyscall) -> Self {
+        let shmid = Integer::new(raw.args[0]);
+        let cmd = Integer::new(raw.args[1]);
+        // IPC_INFO and SHM_INFO use other structures which are not decoded
+        let buf = match raw.args[1] & !IPC_64 {
+            IPC_SET => Some(TypedStruct::new(raw.args[2], Direction::In)),
+            IPC_STAT | SHM_STAT | SHM_STAT_ANY => Some(TypedStruct::new(raw.args[2], Direction::Out)),
+            _ => None,
+        };
+        let retval = None;
+        Self { shmid, cmd, buf, retval }
+    }
+}
+impl DecodeEntry for Shmctl {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.shmid.decode(pid, operation)?;
+        self.cmd.decode(pid, operation)?;
+        if let Some(buf) = &mut self.buf {
+            if matches!(buf.direction, Direction::In) {
+                buf.decode(pid, operation)?;
+            }
+        }
+        Ok(())
+    }
+}
+impl DecodeExit for Shmctl {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if let Some(buf) = &mut self.buf {
+            if matches!(buf.direction, Direction::Out) && (value as isize) >= 0 {
+                buf.decode(pid, operation)?;
+            }
+        }
+        Ok(())
+    }
+}
+impl EncodeEntry for Shmctl {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.shmid.value;
+        raw.args[1] = self.cmd.value;
+        if let Some(buf) = &mut self.buf {
+            raw.args[2] = buf.address;
+            if matches!(buf.direction, Direction::In) {
+                buf.encode(pid, operation)?;
+            }
+        }
+        Ok(raw)
+    }
+}
@@ -30,9 +30,10 @@ impl Stat {
     }
 }
 impl DecodeEntry for Stat {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.pathname.decode(pid, operation).unwrap();
-        self.statbuf.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pathname.decode(pid, operation)?;
+        self.statbuf.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -55,9 +56,10 @@ impl Fstat {
     }
 }
 impl DecodeEntry for Fstat {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.statbuf.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.statbuf.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -80,9 +82,10 @@ impl Lstat {
     }
 }
 impl DecodeEntry for Lstat {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.pathname.decode(pid, operation).unwrap();
-        self.statbuf.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pathname.decode(pid, operation)?;
+        self.statbuf.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -109,10 +112,11 @@ impl Fstatat {
     }
 }
 impl DecodeEntry for Fstatat {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.dirfd.decode(pid, operation).unwrap();
-        self.pathname.decode(pid, operation).unwrap();
-        self.statbuf.decode(pid, operation).unwrap();
-        self.flags.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.dirfd.decode(pid, operation)?;
+        self.pathname.decode(pid, operation)?;
+        self.statbuf.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
     }
 }
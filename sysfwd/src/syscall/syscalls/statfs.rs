@@ -0,0 +1,95 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, Fd, NullBuffer, TypedStruct },
+    syscall::structs::StatfsBuf,
+    syscall::{
+        decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+        encoder::{ EncodeArg, EncodeEntry },
+    },
+    targets::operation::Operation,
+};
+
+
+// int statfs(const char *path, struct statfs *buf)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Statfs {
+    pub path: NullBuffer,
+    pub buf: TypedStruct<StatfsBuf>,
+    pub retval: Option<Integer>,
+}
+impl Statfs {
+    pub fn new(raw: RawSyscall) -> Self {
+        let path = NullBuffer::new(raw.args[0], Direction::In);
+        let buf = TypedStruct::new(raw.args[1], Direction::Out);
+        let retval = None;
+        Self { path, buf, retval }
+    }
+}
+impl DecodeEntry for Statfs {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.path.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Statfs {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if value == 0 {
+            self.buf.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+impl EncodeEntry for Statfs {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.path.address;
+        self.path.encode(pid, operation)?;
+        raw.args[1] = self.buf.address;
+        Ok(raw)
+    }
+}
+
+// int fstatfs(int fd, struct statfs *buf)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Fstatfs {
+    pub fd: Fd,
+    pub buf: TypedStruct<StatfsBuf>,
+    pub retval: Option<Integer>,
+}
+impl Fstatfs {
+    pub fn new(raw: RawSyscall) -> Self {
+        let fd = Fd::new(raw.args[0]);
+        let buf = TypedStruct::new(raw.args[1], Direction::Out);
+        let retval = None;
+        Self { fd, buf, retval }
+    }
+}
+impl DecodeEntry for Fstatfs {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Fstatfs {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if value == 0 {
+            self.buf.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+impl EncodeEntry for Fstatfs {
+    fn encode_entry(&mut self, mut raw: RawSyscall, _pid: i32, _operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.fd.value;
+        raw.args[1] = self.buf.address;
+        Ok(raw)
+    }
+}
@@ -38,11 +38,12 @@ impl Statx {
     }
 }
 impl DecodeEntry for Statx {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.dirfd.decode(pid, operation).unwrap();
-        self.pathname.decode(pid, operation).unwrap();
-        self.flags.decode(pid, operation).unwrap();
-        self.mask.decode(pid, operation).unwrap();
-        self.statxbuf.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.dirfd.decode(pid, operation)?;
+        self.pathname.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        self.mask.decode(pid, operation)?;
+        self.statxbuf.decode(pid, operation)?;
+        Ok(())
     }
 }
\ No newline at end of file
@@ -28,7 +28,8 @@ impl SetTidAddress {
     }
 }
 impl DecodeEntry for SetTidAddress {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.tidptr.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.tidptr.decode(pid, operation)?;
+        Ok(())
     }
 }
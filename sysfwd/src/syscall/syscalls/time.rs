@@ -0,0 +1,293 @@
+/*
+ * Note: glibc usually serves time(), gettimeofday(), clock_gettime() and clock_getres()
+ * from the vDSO, in which case no syscall is made and nothing can be traced.
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, TypedStruct },
+    syscall::structs::{ Timespec, Timeval, Timezone, Tms },
+    syscall::decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+    targets::operation::Operation,
+};
+
+
+
+/* A sleep interrupted by a signal returns -EINTR and fills the remaining time */
+fn is_interrupted(value: usize) -> bool {
+    value as isize == -(nix::libc::EINTR as isize)
+}
+
+
+// time_t time(time_t *tloc);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Time {
+    pub tloc: TypedStruct<u64>,
+    pub retval: Option<Integer>,
+}
+impl Time {
+    pub fn new(raw: RawSyscall) -> Self {
+        let tloc = TypedStruct::new(raw.args[0], Direction::Out);
+        let retval = None;
+        Self { tloc, retval }
+    }
+}
+impl DecodeEntry for Time { }
+impl DecodeExit for Time {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if (value as isize) >= 0 {
+            self.tloc.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+
+// int gettimeofday(struct timeval *tv, struct timezone *tz);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Gettimeofday {
+    pub tv: TypedStruct<Timeval>,
+    pub tz: TypedStruct<Timezone>,
+    pub retval: Option<Integer>,
+}
+impl Gettimeofday {
+    pub fn new(raw: RawSyscall) -> Self {
+        let tv = TypedStruct::new(raw.args[0], Direction::Out);
+        let tz = TypedStruct::new(raw.args[1], Direction::Out);
+        let retval = None;
+        Self { tv, tz, retval }
+    }
+}
+impl DecodeEntry for Gettimeofday { }
+impl DecodeExit for Gettimeofday {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if value == 0 {
+            self.tv.decode(pid, operation)?;
+            self.tz.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+
+// int settimeofday(const struct timeval *tv, const struct timezone *tz);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Settimeofday {
+    pub tv: TypedStruct<Timeval>,
+    pub tz: TypedStruct<Timezone>,
+    pub retval: Option<Integer>,
+}
+impl Settimeofday {
+    pub fn new(raw: RawSyscall) -> Self {
+        let tv = TypedStruct::new(raw.args[0], Direction::In);
+        let tz = TypedStruct::new(raw.args[1], Direction::In);
+        let retval = None;
+        Self { tv, tz, retval }
+    }
+}
+impl DecodeEntry for Settimeofday {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.tv.decode(pid, operation)?;
+        self.tz.decode(pid, operation)?;
+        Ok(())
+    }
+}
+
+
+// clock_t times(struct tms *buf);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Times {
+    pub buf: TypedStruct<Tms>,
+    pub retval: Option<Integer>,
+}
+impl Times {
+    pub fn new(raw: RawSyscall) -> Self {
+        let buf = TypedStruct::new(raw.args[0], Direction::Out);
+        let retval = None;
+        Self { buf, retval }
+    }
+}
+impl DecodeEntry for Times { }
+impl DecodeExit for Times {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if (value as isize) >= 0 {
+            self.buf.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+
+// int nanosleep(const struct timespec *req, struct timespec *rem);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Nanosleep {
+    pub req: TypedStruct<Timespec>,
+    pub rem: TypedStruct<Timespec>,
+    pub retval: Option<Integer>,
+}
+impl Nanosleep {
+    pub fn new(raw: RawSyscall) -> Self {
+        let req = TypedStruct::new(raw.args[0], Direction::In);
+        let rem = TypedStruct::new(raw.args[1], Direction::Out);
+        let retval = None;
+        Self { req, rem, retval }
+    }
+}
+impl DecodeEntry for Nanosleep {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.req.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Nanosleep {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if is_interrupted(value) {
+            self.rem.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+
+// int clock_settime(clockid_t clockid, const struct timespec *tp);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct ClockSettime {
+    pub clockid: Integer,
+    pub tp: TypedStruct<Timespec>,
+    pub retval: Option<Integer>,
+}
+impl ClockSettime {
+    pub fn new(raw: RawSyscall) -> Self {
+        let clockid = Integer::new(raw.args[0]);
+        let tp = TypedStruct::new(raw.args[1], Direction::In);
+        let retval = None;
+        Self { clockid, tp, retval }
+    }
+}
+impl DecodeEntry for ClockSettime {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.clockid.decode(pid, operation)?;
+        self.tp.decode(pid, operation)?;
+        Ok(())
+    }
+}
+
+
+// int clock_gettime(clockid_t clockid, struct timespec *tp);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct ClockGettime {
+    pub clockid: Integer,
+    pub tp: TypedStruct<Timespec>,
+    pub retval: Option<Integer>,
+}
+impl ClockGettime {
+    pub fn new(raw: RawSyscall) -> Self {
+        let clockid = Integer::new(raw.args[0]);
+        let tp = TypedStruct::new(raw.args[1], Direction::Out);
+        let retval = None;
+        Self { clockid, tp, retval }
+    }
+}
+impl DecodeEntry for ClockGettime {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.clockid.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for ClockGettime {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if value == 0 {
+            self.tp.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+
+// int clock_getres(clockid_t clockid, struct timespec *res);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct ClockGetres {
+    pub clockid: Integer,
+    pub res: TypedStruct<Timespec>,
+    pub retval: Option<Integer>,
+}
+impl ClockGetres {
+    pub fn new(raw: RawSyscall) -> Self {
+        let clockid = Integer::new(raw.args[0]);
+        let res = TypedStruct::new(raw.args[1], Direction::Out);
+        let retval = None;
+        Self { clockid, res, retval }
+    }
+}
+impl DecodeEntry for ClockGetres {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.clockid.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for ClockGetres {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if value == 0 {
+            self.res.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+
+// int clock_nanosleep(clockid_t clockid, int flags, const struct timespec *request, struct timespec *remain);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct ClockNanosleep {
+    pub clockid: Integer,
+    pub flags: Integer,
+    pub request: TypedStruct<Timespec>,
+    pub remain: TypedStruct<Timespec>,
+    pub retval: Option<Integer>,
+}
+impl ClockNanosleep {
+    pub fn new(raw: RawSyscall) -> Self {
+        let clockid = Integer::new(raw.args[0]);
+        let flags = Integer::new(raw.args[1]);
+        let request = TypedStruct::new(raw.args[2], Direction::In);
+        let remain = TypedStruct::new(raw.args[3], Direction::Out);
+        let retval = None;
+        Self { clockid, flags, request, remain, retval }
+    }
+}
+impl DecodeEntry for ClockNanosleep {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.clockid.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        self.request.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for ClockNanosleep {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        // the remaining time is not filled for an absolute sleep (TIMER_ABSTIME)
+        if is_interrupted(value) && self.flags.value & 1 == 0 {
+            self.remain.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, TypedStruct },
+    syscall::structs::{ Itimerspec, Itimerval, Sigevent },
+    syscall::decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+    targets::operation::Operation,
+};
+
+
+
+// unsigned int alarm(unsigned int seconds);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Alarm {
+    pub seconds: Integer,
+    pub retval: Option<Integer>,
+}
+impl Alarm {
+    pub fn new(raw: RawSyscall) -> Self {
+        let seconds = Integer::new(raw.args[0]);
+        let retval = None;
+        Self { seconds, retval }
+    }
+}
+impl DecodeEntry for Alarm {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.seconds.decode(pid, operation)?;
+        Ok(())
+    }
+}
+
+
+// int getitimer(int which, struct itimerval *curr_value);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Getitimer {
+    pub which: Integer,
+    pub curr_value: TypedStruct<Itimerval>,
+    pub retval: Option<Integer>,
+}
+impl Getitimer {
+    pub fn new(raw: RawSyscall) -> Self {
+        let which = Integer::new(raw.args[0]);
+        let curr_value = TypedStruct::new(raw.args[1], Direction::Out);
+        let retval = None;
+        Self { which, curr_value, retval }
+    }
+}
+impl DecodeEntry for Getitimer {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.which.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Getitimer {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if value == 0 {
+            self.curr_value.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+
+// int setitimer(int which, const struct itimerval *new_value, struct itimerval *old_value);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Setitimer {
+    pub which: Integer,
+    pub new_value: TypedStruct<Itimerval>,
+    pub old_value: TypedStruct<Itimerval>,
+    pub retval: Option<Integer>,
+}
+impl Setitimer {
+    pub fn new(raw: RawSyscall) -> Self {
+        let which = Integer::new(raw.args[0]);
+        let new_value = TypedStruct::new(raw.args[1], Direction::In);
+        let old_value = TypedStruct::new(raw.args[2], Direction::Out);
+        let retval = None;
+        Self { which, new_value, old_value, retval }
+    }
+}
+impl DecodeEntry for Setitimer {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.which.decode(pid, operation)?;
+        self.new_value.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Setitimer {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if value == 0 {
+            self.old_value.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+
+// int timer_create(clockid_t clockid, struct sigevent *sevp, timer_t *timerid);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct TimerCreate {
+    pub clockid: Integer,
+    pub sevp: TypedStruct<Sigevent>,
+    pub timerid: TypedStruct<u32>,
+    pub retval: Option<Integer>,
+}
+impl TimerCreate {
+    pub fn new(raw: RawSyscall) -> Self {
+        let clockid = Integer::new(raw.args[0]);
+        let sevp = TypedStruct::new(raw.args[1], Direction::In);
+        let timerid = TypedStruct::new(raw.args[2], Direction::Out);
+        let retval = None;
+        Self { clockid, sevp, timerid, retval }
+    }
+}
+impl DecodeEntry for TimerCreate {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.clockid.decode(pid, operation)?;
+        self.sevp.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for TimerCreate {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if value == 0 {
+            self.timerid.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+
+// int timer_settime(timer_t timerid, int flags, const struct itimerspec *new_value, struct itimerspec *old_value);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct TimerSettime {
+    pub timerid: Integer,
+    pub flags: Integer,
+    pub new_value: TypedStruct<Itimerspec>,
+    pub old_value: TypedStruct<Itimerspec>,
+    pub retval: Option<Integer>,
+}
+impl TimerSettime {
+    pub fn new(raw: RawSyscall) -> Self {
+        let timerid = Integer::new(raw.args[0]);
+        let flags = Integer::new(raw.args[1]);
+        let new_value = TypedStruct::new(raw.args[2], Direction::In);
+        let old_value = TypedStruct::new(raw.args[3], Direction::Out);
+        let retval = None;
+        Self { timerid, flags, new_value, old_value, retval }
+    }
+}
+impl DecodeEntry for TimerSettime {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.timerid.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        self.new_value.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for TimerSettime {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if value == 0 {
+            self.old_value.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+
+// int timer_gettime(timer_t timerid, struct itimerspec *curr_value);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct TimerGettime {
+    pub timerid: Integer,
+    pub curr_value: TypedStruct<Itimerspec>,
+    pub retval: Option<Integer>,
+}
+impl TimerGettime {
+    pub fn new(raw: RawSyscall) -> Self {
+        let timerid = Integer::new(raw.args[0]);
+        let curr_value = TypedStruct::new(raw.args[1], Direction::Out);
+        let retval = None;
+        Self { timerid, curr_value, retval }
+    }
+}
+impl DecodeEntry for TimerGettime {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.timerid.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for TimerGettime {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if value == 0 {
+            self.curr_value.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+
+// int timer_getoverrun(timer_t timerid);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct TimerGetoverrun {
+    pub timerid: Integer,
+    pub retval: Option<Integer>,
+}
+impl TimerGetoverrun {
+    pub fn new(raw: RawSyscall) -> Self {
+        let timerid = Integer::new(raw.args[0]);
+        let retval = None;
+        Self { timerid, retval }
+    }
+}
+impl DecodeEntry for TimerGetoverrun {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.timerid.decode(pid, operation)?;
+        Ok(())
+    }
+}
+
+
+// int timer_delete(timer_t timerid);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct TimerDelete {
+    pub timerid: Integer,
+    pub retval: Option<Integer>,
+}
+impl TimerDelete {
+    pub fn new(raw: RawSyscall) -> Self {
+        let timerid = Integer::new(raw.args[0]);
+        let retval = None;
+        Self { timerid, retval }
+    }
+}
+impl DecodeEntry for TimerDelete {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.timerid.decode(pid, operation)?;
+        Ok(())
+    }
+}
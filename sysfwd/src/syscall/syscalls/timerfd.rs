@@ -0,0 +1,110 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, Fd, TypedStruct },
+    syscall::structs::Itimerspec,
+    syscall::decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+    targets::operation::Operation,
+};
+
+
+
+// int timerfd_create(int clockid, int flags);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct TimerfdCreate {
+    pub clockid: Integer,
+    pub flags: Integer,
+    pub retval: Option<Fd>,
+}
+impl TimerfdCreate {
+    pub fn new(raw: RawSyscall) -> Self {
+        let clockid = Integer::new(raw.args[0]);
+        let flags = Integer::new(raw.args[1]);
+        let retval = None;
+        Self { clockid, flags, retval }
+    }
+}
+impl DecodeEntry for TimerfdCreate {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.clockid.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
+    }
+}
+
+// int timerfd_settime(int fd, int flags, const struct itimerspec *new_value, struct itimerspec *_Nullable old_value);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct TimerfdSettime {
+    pub fd: Fd,
+    pub flags: Integer,
+    pub new_value: TypedStruct<Itimerspec>,
+    pub old_value: TypedStruct<Itimerspec>,
+    pub retval: Option<Integer>,
+}
+impl TimerfdSettime {
+    pub fn new(raw: RawSyscall) -> Self {
+        let fd = Fd::new(raw.args[0]);
+        let flags = Integer::new(raw.args[1]);
+        let new_value = TypedStruct::new(raw.args[2], Direction::In);
+        let old_value = TypedStruct::new(raw.args[3], Direction::Out);
+        let retval = None;
+        Self { fd, flags, new_value, old_value, retval }
+    }
+}
+impl DecodeEntry for TimerfdSettime {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        self.new_value.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for TimerfdSettime {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if value == 0 {
+            self.old_value.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
+
+// int timerfd_gettime(int fd, struct itimerspec *curr_value);
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct TimerfdGettime {
+    pub fd: Fd,
+    pub curr_value: TypedStruct<Itimerspec>,
+    pub retval: Option<Integer>,
+}
+impl TimerfdGettime {
+    pub fn new(raw: RawSyscall) -> Self {
+        let fd = Fd::new(raw.args[0]);
+        let curr_value = TypedStruct::new(raw.args[1], Direction::Out);
+        let retval = None;
+        Self { fd, curr_value, retval }
+    }
+}
+impl DecodeEntry for TimerfdGettime {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for TimerfdGettime {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Integer::from(value));
+        if value == 0 {
+            self.curr_value.decode(pid, operation)?;
+        }
+        Ok(())
+    }
+}
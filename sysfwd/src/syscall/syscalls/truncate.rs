@@ -32,9 +32,10 @@ impl Truncate {
 }
 
 impl DecodeEntry for Truncate {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.path.decode(pid, operation).unwrap();
-        self.length.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.path.decode(pid, operation)?;
+        self.length.decode(pid, operation)?;
+        Ok(())
     }
 }
 
@@ -59,8 +60,9 @@ impl Ftruncate {
 }
 
 impl DecodeEntry for Ftruncate {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.fd.decode(pid, operation).unwrap();
-        self.length.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.length.decode(pid, operation)?;
+        Ok(())
     }
 }
\ No newline at end of file
@@ -0,0 +1,82 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, Fd, NullBuffer },
+    syscall::{
+        decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+        encoder::{ EncodeArg, EncodeEntry },
+    },
+    targets::operation::Operation,
+};
+
+
+// int unlink(const char *pathname)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Unlink {
+    pub pathname: NullBuffer,
+    pub retval: Option<Integer>,
+}
+impl Unlink {
+    pub fn new(raw: RawSyscall) -> Self {
+        let pathname = NullBuffer::new(raw.args[0], Direction::In);
+        let retval = None;
+        Self { pathname, retval }
+    }
+}
+impl DecodeEntry for Unlink {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.pathname.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Unlink {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.pathname.address;
+        self.pathname.encode(pid, operation)?;
+        Ok(raw)
+    }
+}
+
+// int unlinkat(int dirfd, const char *pathname, int flags)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Unlinkat {
+    pub dirfd: Fd,
+    pub pathname: NullBuffer,
+    pub flags: Integer,
+    pub retval: Option<Integer>,
+}
+impl Unlinkat {
+    pub fn new(raw: RawSyscall) -> Self {
+        let dirfd = Fd::new(raw.args[0]);
+        let pathname = NullBuffer::new(raw.args[1], Direction::In);
+        let flags = Integer::new(raw.args[2]);
+        let retval = None;
+        Self { dirfd, pathname, flags, retval }
+    }
+}
+impl DecodeEntry for Unlinkat {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.dirfd.decode(pid, operation)?;
+        self.pathname.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Unlinkat {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.dirfd.value;
+        raw.args[1] = self.pathname.address;
+        self.pathname.encode(pid, operation)?;
+        raw.args[2] = self.flags.value;
+        Ok(raw)
+    }
+}
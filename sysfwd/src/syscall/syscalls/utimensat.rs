@@ -0,0 +1,59 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, Fd, NullBuffer, TypedArray },
+    syscall::structs::Timespec,
+    syscall::{
+        decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+        encoder::{ EncodeArg, EncodeEntry },
+    },
+    targets::operation::Operation,
+};
+
+
+// int utimensat(int dirfd, const char *pathname, const struct timespec times[_Nullable 2], int flags)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Utimensat {
+    pub dirfd: Fd,
+    pub pathname: NullBuffer,
+    pub times: TypedArray<Timespec>,
+    pub flags: Integer,
+    pub retval: Option<Integer>,
+}
+impl Utimensat {
+    pub fn new(raw: RawSyscall) -> Self {
+        let dirfd = Fd::new(raw.args[0]);
+        let pathname = NullBuffer::new(raw.args[1], Direction::In);
+        let times = TypedArray::new(raw.args[2], Direction::In, 2);
+        let flags = Integer::new(raw.args[3]);
+        let retval = None;
+        Self { dirfd, pathname, times, flags, retval }
+    }
+}
+impl DecodeEntry for Utimensat {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.dirfd.decode(pid, operation)?;
+        self.pathname.decode(pid, operation)?;
+        self.times.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Utimensat {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.dirfd.value;
+        raw.args[1] = self.pathname.address;
+        self.pathname.encode(pid, operation)?;
+        raw.args[2] = self.times.address;
+        self.times.encode(pid, operation)?;
+        raw.args[3] = self.flags.value;
+        Ok(raw)
+    }
+}
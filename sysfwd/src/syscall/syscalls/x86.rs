@@ -32,9 +32,10 @@ impl ArchPrctl {
     }
 }
 impl DecodeEntry for ArchPrctl {
-    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) {
-        self.code.decode(pid, operation).unwrap();
-        self.addr.decode(pid, operation).unwrap();
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.code.decode(pid, operation)?;
+        self.addr.decode(pid, operation)?;
+        Ok(())
     }
 }
 */
@@ -0,0 +1,552 @@
+/*
+ *
+ */
+use serde::{ Serialize, Deserialize };
+
+use decoding_macro::DecodeExit;
+use crate::{
+    syscall::RawSyscall,
+    syscall::args::{ Direction, Integer, Fd, Size, Buffer, NullBuffer },
+    syscall::{
+        decoder::{ DecodeArg, DecodeEntry, DecodeExit },
+        encoder::{ EncodeArg, EncodeEntry },
+    },
+    targets::operation::Operation,
+};
+
+
+// int setxattr(const char *path, const char *name, const void value[.size], size_t size, int flags)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Setxattr {
+    pub path: NullBuffer,
+    pub name: NullBuffer,
+    pub value: Buffer,
+    pub size: Size,
+    pub flags: Integer,
+    pub retval: Option<Integer>,
+}
+impl Setxattr {
+    pub fn new(raw: RawSyscall) -> Self {
+        let path = NullBuffer::new(raw.args[0], Direction::In);
+        let name = NullBuffer::new(raw.args[1], Direction::In);
+        let value = Buffer::new(raw.args[2], Direction::In, raw.args[3]);
+        let size = Size::new(raw.args[3]);
+        let flags = Integer::new(raw.args[4]);
+        let retval = None;
+        Self { path, name, value, size, flags, retval }
+    }
+}
+impl DecodeEntry for Setxattr {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.path.decode(pid, operation)?;
+        self.name.decode(pid, operation)?;
+        self.value.decode(pid, operation)?;
+        self.size.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Setxattr {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.path.address;
+        self.path.encode(pid, operation)?;
+        raw.args[1] = self.name.address;
+        self.name.encode(pid, operation)?;
+        raw.args[2] = self.value.address;
+        self.value.encode(pid, operation)?;
+        raw.args[3] = self.size.value;
+        raw.args[4] = self.flags.value;
+        Ok(raw)
+    }
+}
+
+// int lsetxattr(const char *path, const char *name, const void value[.size], size_t size, int flags)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Lsetxattr {
+    pub path: NullBuffer,
+    pub name: NullBuffer,
+    pub value: Buffer,
+    pub size: Size,
+    pub flags: Integer,
+    pub retval: Option<Integer>,
+}
+impl Lsetxattr {
+    pub fn new(raw: RawSyscall) -> Self {
+        let path = NullBuffer::new(raw.args[0], Direction::In);
+        let name = NullBuffer::new(raw.args[1], Direction::In);
+        let value = Buffer::new(raw.args[2], Direction::In, raw.args[3]);
+        let size = Size::new(raw.args[3]);
+        let flags = Integer::new(raw.args[4]);
+        let retval = None;
+        Self { path, name, value, size, flags, retval }
+    }
+}
+impl DecodeEntry for Lsetxattr {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.path.decode(pid, operation)?;
+        self.name.decode(pid, operation)?;
+        self.value.decode(pid, operation)?;
+        self.size.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Lsetxattr {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.path.address;
+        self.path.encode(pid, operation)?;
+        raw.args[1] = self.name.address;
+        self.name.encode(pid, operation)?;
+        raw.args[2] = self.value.address;
+        self.value.encode(pid, operation)?;
+        raw.args[3] = self.size.value;
+        raw.args[4] = self.flags.value;
+        Ok(raw)
+    }
+}
+
+// int fsetxattr(int fd, const char *name, const void value[.size], size_t size, int flags)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Fsetxattr {
+    pub fd: Fd,
+    pub name: NullBuffer,
+    pub value: Buffer,
+    pub size: Size,
+    pub flags: Integer,
+    pub retval: Option<Integer>,
+}
+impl Fsetxattr {
+    pub fn new(raw: RawSyscall) -> Self {
+        let fd = Fd::new(raw.args[0]);
+        let name = NullBuffer::new(raw.args[1], Direction::In);
+        let value = Buffer::new(raw.args[2], Direction::In, raw.args[3]);
+        let size = Size::new(raw.args[3]);
+        let flags = Integer::new(raw.args[4]);
+        let retval = None;
+        Self { fd, name, value, size, flags, retval }
+    }
+}
+impl DecodeEntry for Fsetxattr {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.name.decode(pid, operation)?;
+        self.value.decode(pid, operation)?;
+        self.size.decode(pid, operation)?;
+        self.flags.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Fsetxattr {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.fd.value;
+        raw.args[1] = self.name.address;
+        self.name.encode(pid, operation)?;
+        raw.args[2] = self.value.address;
+        self.value.encode(pid, operation)?;
+        raw.args[3] = self.size.value;
+        raw.args[4] = self.flags.value;
+        Ok(raw)
+    }
+}
+
+// ssize_t getxattr(const char *path, const char *name, void value[.size], size_t size)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Getxattr {
+    pub path: NullBuffer,
+    pub name: NullBuffer,
+    pub value: Buffer,
+    pub size: Size,
+    pub retval: Option<Size>,
+}
+impl Getxattr {
+    pub fn new(raw: RawSyscall) -> Self {
+        let path = NullBuffer::new(raw.args[0], Direction::In);
+        let name = NullBuffer::new(raw.args[1], Direction::In);
+        let value = Buffer::new(raw.args[2], Direction::Out, raw.args[3]);
+        let size = Size::new(raw.args[3]);
+        let retval = None;
+        Self { path, name, value, size, retval }
+    }
+}
+impl DecodeEntry for Getxattr {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.path.decode(pid, operation)?;
+        self.name.decode(pid, operation)?;
+        self.size.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Getxattr {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Size::from(value));
+        // With a zero size, the kernel only returns the size needed without filling the buffer
+        if (value as isize) > 0 && self.size.value > 0 {
+            self.value.size = value;
+            self.value.decode(pid, operation)?;
+            self.value.content.truncate(value);
+        }
+        Ok(())
+    }
+}
+impl EncodeEntry for Getxattr {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.path.address;
+        self.path.encode(pid, operation)?;
+        raw.args[1] = self.name.address;
+        self.name.encode(pid, operation)?;
+        raw.args[2] = self.value.address;
+        raw.args[3] = self.size.value;
+        Ok(raw)
+    }
+}
+
+// ssize_t lgetxattr(const char *path, const char *name, void value[.size], size_t size)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Lgetxattr {
+    pub path: NullBuffer,
+    pub name: NullBuffer,
+    pub value: Buffer,
+    pub size: Size,
+    pub retval: Option<Size>,
+}
+impl Lgetxattr {
+    pub fn new(raw: RawSyscall) -> Self {
+        let path = NullBuffer::new(raw.args[0], Direction::In);
+        let name = NullBuffer::new(raw.args[1], Direction::In);
+        let value = Buffer::new(raw.args[2], Direction::Out, raw.args[3]);
+        let size = Size::new(raw.args[3]);
+        let retval = None;
+        Self { path, name, value, size, retval }
+    }
+}
+impl DecodeEntry for Lgetxattr {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.path.decode(pid, operation)?;
+        self.name.decode(pid, operation)?;
+        self.size.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Lgetxattr {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Size::from(value));
+        // With a zero size, the kernel only returns the size needed without filling the buffer
+        if (value as isize) > 0 && self.size.value > 0 {
+            self.value.size = value;
+            self.value.decode(pid, operation)?;
+            self.value.content.truncate(value);
+        }
+        Ok(())
+    }
+}
+impl EncodeEntry for Lgetxattr {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.path.address;
+        self.path.encode(pid, operation)?;
+        raw.args[1] = self.name.address;
+        self.name.encode(pid, operation)?;
+        raw.args[2] = self.value.address;
+        raw.args[3] = self.size.value;
+        Ok(raw)
+    }
+}
+
+// ssize_t fgetxattr(int fd, const char *name, void value[.size], size_t size)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Fgetxattr {
+    pub fd: Fd,
+    pub name: NullBuffer,
+    pub value: Buffer,
+    pub size: Size,
+    pub retval: Option<Size>,
+}
+impl Fgetxattr {
+    pub fn new(raw: RawSyscall) -> Self {
+        let fd = Fd::new(raw.args[0]);
+        let name = NullBuffer::new(raw.args[1], Direction::In);
+        let value = Buffer::new(raw.args[2], Direction::Out, raw.args[3]);
+        let size = Size::new(raw.args[3]);
+        let retval = None;
+        Self { fd, name, value, size, retval }
+    }
+}
+impl DecodeEntry for Fgetxattr {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.name.decode(pid, operation)?;
+        self.size.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Fgetxattr {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Size::from(value));
+        // With a zero size, the kernel only returns the size needed without filling the buffer
+        if (value as isize) > 0 && self.size.value > 0 {
+            self.value.size = value;
+            self.value.decode(pid, operation)?;
+            self.value.content.truncate(value);
+        }
+        Ok(())
+    }
+}
+impl EncodeEntry for Fgetxattr {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.fd.value;
+        raw.args[1] = self.name.address;
+        self.name.encode(pid, operation)?;
+        raw.args[2] = self.value.address;
+        raw.args[3] = self.size.value;
+        Ok(raw)
+    }
+}
+
+// ssize_t listxattr(const char *path, char *_Nullable list, size_t size)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Listxattr {
+    pub path: NullBuffer,
+    pub list: Buffer,
+    pub size: Size,
+    pub retval: Option<Size>,
+}
+impl Listxattr {
+    pub fn new(raw: RawSyscall) -> Self {
+        let path = NullBuffer::new(raw.args[0], Direction::In);
+        let list = Buffer::new(raw.args[1], Direction::Out, raw.args[2]);
+        let size = Size::new(raw.args[2]);
+        let retval = None;
+        Self { path, list, size, retval }
+    }
+}
+impl DecodeEntry for Listxattr {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.path.decode(pid, operation)?;
+        self.size.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Listxattr {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Size::from(value));
+        // With a zero size, the kernel only returns the size needed without filling the buffer
+        if (value as isize) > 0 && self.size.value > 0 {
+            self.list.size = value;
+            self.list.decode(pid, operation)?;
+            self.list.content.truncate(value);
+        }
+        Ok(())
+    }
+}
+impl EncodeEntry for Listxattr {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.path.address;
+        self.path.encode(pid, operation)?;
+        raw.args[1] = self.list.address;
+        raw.args[2] = self.size.value;
+        Ok(raw)
+    }
+}
+
+// ssize_t llistxattr(const char *path, char *_Nullable list, size_t size)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Llistxattr {
+    pub path: NullBuffer,
+    pub list: Buffer,
+    pub size: Size,
+    pub retval: Option<Size>,
+}
+impl Llistxattr {
+    pub fn new(raw: RawSyscall) -> Self {
+        let path = NullBuffer::new(raw.args[0], Direction::In);
+        let list = Buffer::new(raw.args[1], Direction::Out, raw.args[2]);
+        let size = Size::new(raw.args[2]);
+        let retval = None;
+        Self { path, list, size, retval }
+    }
+}
+impl DecodeEntry for Llistxattr {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.path.decode(pid, operation)?;
+        self.size.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Llistxattr {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Size::from(value));
+        // With a zero size, the kernel only returns the size needed without filling the buffer
+        if (value as isize) > 0 && self.size.value > 0 {
+            self.list.size = value;
+            self.list.decode(pid, operation)?;
+            self.list.content.truncate(value);
+        }
+        Ok(())
+    }
+}
+impl EncodeEntry for Llistxattr {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.path.address;
+        self.path.encode(pid, operation)?;
+        raw.args[1] = self.list.address;
+        raw.args[2] = self.size.value;
+        Ok(raw)
+    }
+}
+
+// ssize_t flistxattr(int fd, char *_Nullable list, size_t size)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct Flistxattr {
+    pub fd: Fd,
+    pub list: Buffer,
+    pub size: Size,
+    pub retval: Option<Size>,
+}
+impl Flistxattr {
+    pub fn new(raw: RawSyscall) -> Self {
+        let fd = Fd::new(raw.args[0]);
+        let list = Buffer::new(raw.args[1], Direction::Out, raw.args[2]);
+        let size = Size::new(raw.args[2]);
+        let retval = None;
+        Self { fd, list, size, retval }
+    }
+}
+impl DecodeEntry for Flistxattr {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.size.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl DecodeExit for Flistxattr {
+    fn decode_exit(&mut self, value: usize, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.retval = Some(Size::from(value));
+        // With a zero size, the kernel only returns the size needed without filling the buffer
+        if (value as isize) > 0 && self.size.value > 0 {
+            self.list.size = value;
+            self.list.decode(pid, operation)?;
+            self.list.content.truncate(value);
+        }
+        Ok(())
+    }
+}
+impl EncodeEntry for Flistxattr {
+    fn encode_entry(&mut self, mut raw: RawSyscall, _pid: i32, _operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.fd.value;
+        raw.args[1] = self.list.address;
+        raw.args[2] = self.size.value;
+        Ok(raw)
+    }
+}
+
+// int removexattr(const char *path, const char *name)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Removexattr {
+    pub path: NullBuffer,
+    pub name: NullBuffer,
+    pub retval: Option<Integer>,
+}
+impl Removexattr {
+    pub fn new(raw: RawSyscall) -> Self {
+        let path = NullBuffer::new(raw.args[0], Direction::In);
+        let name = NullBuffer::new(raw.args[1], Direction::In);
+        let retval = None;
+        Self { path, name, retval }
+    }
+}
+impl DecodeEntry for Removexattr {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.path.decode(pid, operation)?;
+        self.name.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Removexattr {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.path.address;
+        self.path.encode(pid, operation)?;
+        raw.args[1] = self.name.address;
+        self.name.encode(pid, operation)?;
+        Ok(raw)
+    }
+}
+
+// int lremovexattr(const char *path, const char *name)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Lremovexattr {
+    pub path: NullBuffer,
+    pub name: NullBuffer,
+    pub retval: Option<Integer>,
+}
+impl Lremovexattr {
+    pub fn new(raw: RawSyscall) -> Self {
+        let path = NullBuffer::new(raw.args[0], Direction::In);
+        let name = NullBuffer::new(raw.args[1], Direction::In);
+        let retval = None;
+        Self { path, name, retval }
+    }
+}
+impl DecodeEntry for Lremovexattr {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.path.decode(pid, operation)?;
+        self.name.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Lremovexattr {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.path.address;
+        self.path.encode(pid, operation)?;
+        raw.args[1] = self.name.address;
+        self.name.encode(pid, operation)?;
+        Ok(raw)
+    }
+}
+
+// int fremovexattr(int fd, const char *name)
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[derive(DecodeExit)]
+pub struct Fremovexattr {
+    pub fd: Fd,
+    pub name: NullBuffer,
+    pub retval: Option<Integer>,
+}
+impl Fremovexattr {
+    pub fn new(raw: RawSyscall) -> Self {
+        let fd = Fd::new(raw.args[0]);
+        let name = NullBuffer::new(raw.args[1], Direction::In);
+        let retval = None;
+        Self { fd, name, retval }
+    }
+}
+impl DecodeEntry for Fremovexattr {
+    fn decode_entry(&mut self, pid: i32, operation: &Box<Operation>) -> Result<(), std::io::Error> {
+        self.fd.decode(pid, operation)?;
+        self.name.decode(pid, operation)?;
+        Ok(())
+    }
+}
+impl EncodeEntry for Fremovexattr {
+    fn encode_entry(&mut self, mut raw: RawSyscall, pid: i32, operation: &Box<Operation>) -> Result<RawSyscall, std::io::Error> {
+        raw.args[0] = self.fd.value;
+        raw.args[1] = self.name.address;
+        self.name.encode(pid, operation)?;
+        Ok(raw)
+    }
+}
@@ -4,4 +4,5 @@
 pub mod operation;
 
 pub mod ptrace;
+pub mod process_vm;
 pub mod qemu;
\ No newline at end of file
@@ -5,13 +5,62 @@
  *
  * Note: we could at some point split the operations into different traits.
  */
-use nix::libc::user_regs_struct;
+use std::{ fmt, io };
 
+use nix::{
+    errno::Errno,
+    libc::user_regs_struct,
+};
+
+
+
+/*
+ * The errors of the operations on the traced process.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OperationError {
+    /*
+     * The memory is not accessible from `address`, the kernel would fail with EFAULT.
+     * `partial` holds the bytes read before the faulting address.
+     */
+    Fault { address: usize, partial: Vec<u8> },
+    /*
+     * The registers of the process cannot be accessed (e.g., the process is not stopped)
+     */
+    Registers { pid: i32, errno: Errno },
+    /*
+     * The kernel cannot tell at which syscall stop the process is
+     */
+    SyscallInfo { pid: i32, errno: Errno },
+}
+
+impl fmt::Display for OperationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OperationError::Fault { address, .. } => write!(f, "bad address {:#x}", address),
+            OperationError::Registers { pid, errno } => write!(f, "cannot access the registers of {}: {}", pid, errno),
+            OperationError::SyscallInfo { pid, errno } => write!(f, "cannot get the syscall stop of {}: {}", pid, errno),
+        }
+    }
+}
+
+impl std::error::Error for OperationError { }
+
+impl From<OperationError> for io::Error {
+    fn from(err: OperationError) -> Self {
+        let errno = match err {
+            OperationError::Fault { .. } => Errno::EFAULT,
+            OperationError::Registers { errno, .. } => errno,
+            OperationError::SyscallInfo { errno, .. } => errno,
+        };
+        io::Error::new(io::Error::from(errno).kind(), err)
+    }
+}
 
 
 pub trait RegisterOperation {
-    fn read_registers(&self, pid: i32) -> Option<user_regs_struct>;
-    fn write_registers(&self, pid: i32, regs: user_regs_struct) -> Result<(), std::io::Error>;
+    fn read_registers(&self, pid: i32) -> Result<user_regs_struct, OperationError>;
+    fn write_registers(&self, pid: i32, regs: user_regs_struct) -> Result<(), OperationError>;
 
     /* 
      * When it's possible to edit registers one by one:
@@ -20,9 +69,26 @@ pub trait RegisterOperation {
     */
 }
 
+/*
+ * The syscall stop of a process, as reported by the kernel instead of guessed by the tracer.
+ * `arch` is the AUDIT_ARCH_* value of the syscall convention used.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SyscallStop {
+    Entry { arch: u32, no: usize, args: [usize; 6] },
+    Exit { arch: u32, retval: usize },
+    /* The syscall entry reported by a SECCOMP_RET_TRACE filter */
+    Seccomp { arch: u32, no: usize, args: [usize; 6] },
+    /* The process is not stopped at a syscall (e.g. a signal-delivery stop) */
+    None,
+}
+
+/*
+ * Reads and writes either transfer the whole memory block or fail with the faulting address.
+ */
 pub trait MemoryOperation {
-    fn read(&self, pid: i32, addr: usize, size: usize) -> Vec<u8>;
-    fn write(&self, pid: i32, addr: usize, mem: Vec<u8>) -> usize;
+    fn read(&self, pid: i32, addr: usize, size: usize) -> Result<Vec<u8>, OperationError>;
+    fn write(&self, pid: i32, addr: usize, mem: Vec<u8>) -> Result<(), OperationError>;
 }
 
 /*
@@ -0,0 +1,324 @@
+/*
+ * A memory backend transferring whole blocks of the tracee memory at once.
+ *
+ * process_vm_readv/writev is tried first, since a syscall per IOV_MAX pages is enough for any size.
+ * It cannot access pages without the read (resp. write) permission, so the remaining bytes are
+ * transferred through /proc/<pid>/mem, and then with ptrace as the last resort.
+ *
+ * The transfers stop precisely at the first inaccessible byte, which is reported as the faulting address.
+ */
+use core::ffi::c_void;
+use std::{
+    fs::OpenOptions,
+    io::{ IoSlice, IoSliceMut },
+    os::unix::fs::FileExt,
+};
+
+use nix::{
+    unistd::Pid,
+    sys::{
+        ptrace,
+        uio::{ process_vm_readv, process_vm_writev, RemoteIoVec },
+    },
+};
+use crate::targets::operation::{ MemoryOperation, OperationError };
+
+
+
+const PAGE_SIZE: usize = 4096;
+const WORD_SIZE: usize = std::mem::size_of::<usize>();
+/* The maximum number of iovecs of a single process_vm_readv/writev call */
+const IOV_MAX: usize = 1024;
+
+
+#[derive(Clone, Debug)]
+pub struct ProcessVm { }
+
+impl MemoryOperation for ProcessVm {
+
+    fn read(&self, pid: i32, addr: usize, size: usize) -> Result<Vec<u8>, OperationError> {
+        // the size is given by the tracee: the memory grows by batches, up to the first fault
+        let accessible = accessible_size(addr, size);
+        let mut mem = Vec::new();
+
+        while mem.len() < accessible {
+            let start = mem.len();
+            let batch = (accessible - start).min(IOV_MAX * PAGE_SIZE);
+            mem.resize(start + batch, 0);
+
+            let mut done = 0;
+            for backend in [read_process_vm, read_proc_mem, read_ptrace] {
+                if done == batch {
+                    break;
+                }
+                done += backend(pid, addr + start + done, &mut mem[start + done..start + batch]);
+            }
+            mem.truncate(start + done);
+            if done < batch {
+                break;
+            }
+        }
+
+        match mem.len() == size {
+            true => Ok(mem),
+            false => Err(OperationError::Fault { address: addr + mem.len(), partial: mem }),
+        }
+    }
+
+    fn write(&self, pid: i32, addr: usize, mem: Vec<u8>) -> Result<(), OperationError> {
+        let mut done = 0;
+        let accessible = accessible_size(addr, mem.len());
+
+        for backend in [write_process_vm, write_proc_mem, write_ptrace] {
+            if done == accessible {
+                break;
+            }
+            done += backend(pid, addr + done, &mem[done..accessible]);
+        }
+
+        match done == mem.len() {
+            true => Ok(()),
+            false => Err(OperationError::Fault { address: addr + done, partial: Vec::new() }),
+        }
+    }
+}
+
+
+/* The bytes past the end of the address space are never accessible, the fault is at its end */
+fn accessible_size(addr: usize, size: usize) -> usize
+{
+    match addr.checked_add(size) {
+        Some(_) => size,
+        None => usize::MAX - addr,
+    }
+}
+
+/*
+ * Split a remote block on page boundaries, up to IOV_MAX pages.
+ * The transfers are partial at the granularity of the remote iovecs, this way they stop at the exact
+ * page which is not accessible.
+ */
+fn remote_pages(addr: usize, size: usize) -> Vec<RemoteIoVec>
+{
+    let mut iovs = Vec::with_capacity((size / PAGE_SIZE + 2).min(IOV_MAX));
+    let mut base = addr;
+    let end = addr.saturating_add(size);
+
+    while base < end && iovs.len() < IOV_MAX {
+        let next_page = (base / PAGE_SIZE + 1).saturating_mul(PAGE_SIZE);
+        let len = next_page.min(end) - base;
+        iovs.push(RemoteIoVec { base, len });
+        base += len;
+    }
+    iovs
+}
+
+
+/* process_vm_readv / process_vm_writev, by batches of IOV_MAX pages */
+
+fn read_process_vm(pid: i32, addr: usize, mem: &mut [u8]) -> usize
+{
+    let mut done = 0;
+    while done < mem.len() {
+        let remote = remote_pages(addr + done, mem.len() - done);
+        let len: usize = remote.iter().map(|iov| iov.len).sum();
+        let mut local = [IoSliceMut::new(&mut mem[done..done + len])];
+        let count = process_vm_readv(Pid::from_raw(pid), &mut local, &remote).unwrap_or(0);
+        done += count;
+        if count < len {
+            break;
+        }
+    }
+    done
+}
+
+fn write_process_vm(pid: i32, addr: usize, mem: &[u8]) -> usize
+{
+    let mut done = 0;
+    while done < mem.len() {
+        let remote = remote_pages(addr + done, mem.len() - done);
+        let len: usize = remote.iter().map(|iov| iov.len).sum();
+        let local = [IoSlice::new(&mem[done..done + len])];
+        let count = process_vm_writev(Pid::from_raw(pid), &local, &remote).unwrap_or(0);
+        done += count;
+        if count < len {
+            break;
+        }
+    }
+    done
+}
+
+
+/* /proc/<pid>/mem, which ignores the page protections like ptrace does */
+
+fn read_proc_mem(pid: i32, addr: usize, mem: &mut [u8]) -> usize
+{
+    let file = match OpenOptions::new().read(true).open(format!("/proc/{}/mem", pid)) {
+        Ok(file) => file,
+        Err(_) => return 0,
+    };
+
+    let mut done = 0;
+    while done < mem.len() {
+        match file.read_at(&mut mem[done..], (addr + done) as u64) {
+            Ok(0) | Err(_) => break,
+            Ok(count) => done += count,
+        }
+    }
+    done
+}
+
+fn write_proc_mem(pid: i32, addr: usize, mem: &[u8]) -> usize
+{
+    let file = match OpenOptions::new().write(true).open(format!("/proc/{}/mem", pid)) {
+        Ok(file) => file,
+        Err(_) => return 0,
+    };
+
+    let mut done = 0;
+    while done < mem.len() {
+        match file.write_at(&mem[done..], (addr + done) as u64) {
+            Ok(0) | Err(_) => break,
+            Ok(count) => done += count,
+        }
+    }
+    done
+}
+
+
+/* ptrace, one word at a time */
+
+fn read_ptrace(pid: i32, addr: usize, mem: &mut [u8]) -> usize
+{
+    let pid = Pid::from_raw(pid);
+    let mut done = 0;
+
+    while done < mem.len() {
+        // the word containing the current address
+        let offset = (addr + done) % WORD_SIZE;
+        let address = (addr + done - offset) as ptrace::AddressType;
+        let word = match ptrace::read(pid, address) {
+            Ok(word) => (word as usize).to_le_bytes(),
+            Err(_) => break,
+        };
+
+        let count = (WORD_SIZE - offset).min(mem.len() - done);
+        mem[done..done + count].copy_from_slice(&word[offset..offset + count]);
+        done += count;
+    }
+    done
+}
+
+fn write_ptrace(pid: i32, addr: usize, mem: &[u8]) -> usize
+{
+    let pid = Pid::from_raw(pid);
+    let mut done = 0;
+
+    while done < mem.len() {
+        let offset = (addr + done) % WORD_SIZE;
+        let address = (addr + done - offset) as ptrace::AddressType;
+        let count = (WORD_SIZE - offset).min(mem.len() - done);
+
+        // keep the bytes of the word which are not overwritten
+        let mut word = match ptrace::read(pid, address) {
+            Ok(word) => (word as usize).to_le_bytes(),
+            Err(_) => break,
+        };
+        word[offset..offset + count].copy_from_slice(&mem[done..done + count]);
+
+        let data = usize::from_le_bytes(word) as *mut c_void;
+        if unsafe { ptrace::write(pid, address, data) }.is_err() {
+            break;
+        }
+        done += count;
+    }
+    done
+}
+
+
+#[cfg(test)]
+mod tests {
+    use nix::sys::mman::{ mmap, munmap, MapFlags, ProtFlags };
+
+    use super::*;
+
+    fn pid() -> i32
+    {
+        std::process::id() as i32
+    }
+
+    #[test]
+    fn blocks_larger_than_iov_max_pages()
+    {
+        let size = (IOV_MAX + 3) * PAGE_SIZE + 123;
+        let data: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+        // not aligned on a page
+        let addr = data.as_ptr() as usize + 1;
+
+        let vm = ProcessVm{ };
+        let mem = vm.read(pid(), addr, size - 1).unwrap();
+        assert_eq!(mem, data[1..]);
+        assert_eq!(read_process_vm(pid(), addr, &mut vec![0; size - 1]), size - 1);
+
+        let mut target = vec![0u8; size];
+        vm.write(pid(), target.as_mut_ptr() as usize, data.clone()).unwrap();
+        assert_eq!(target, data);
+    }
+
+    #[test]
+    fn transfers_stop_at_the_first_unmapped_page()
+    {
+        let vm = ProcessVm{ };
+        let addr = unsafe {
+            mmap(std::ptr::null_mut(), 2 * PAGE_SIZE, ProtFlags::PROT_READ | ProtFlags::PROT_WRITE, MapFlags::MAP_PRIVATE | MapFlags::MAP_ANONYMOUS, -1, 0)
+        }.unwrap() as usize;
+        unsafe { munmap((addr + PAGE_SIZE) as *mut c_void, PAGE_SIZE) }.unwrap();
+
+        match vm.read(pid(), addr + 10, PAGE_SIZE) {
+            Err(OperationError::Fault { address, partial }) => {
+                assert_eq!(address, addr + PAGE_SIZE);
+                assert_eq!(partial.len(), PAGE_SIZE - 10);
+            },
+            result => panic!("unexpected {:?}", result),
+        }
+        match vm.write(pid(), addr + 10, vec![1; PAGE_SIZE]) {
+            Err(OperationError::Fault { address, .. }) => assert_eq!(address, addr + PAGE_SIZE),
+            result => panic!("unexpected {:?}", result),
+        }
+        unsafe { munmap(addr as *mut c_void, PAGE_SIZE) }.unwrap();
+    }
+
+    #[test]
+    fn huge_reads_stop_at_the_end_of_the_mapping()
+    {
+        let vm = ProcessVm{ };
+        let addr = unsafe {
+            mmap(std::ptr::null_mut(), 2 * PAGE_SIZE, ProtFlags::PROT_READ | ProtFlags::PROT_WRITE, MapFlags::MAP_PRIVATE | MapFlags::MAP_ANONYMOUS, -1, 0)
+        }.unwrap() as usize;
+        unsafe { munmap((addr + PAGE_SIZE) as *mut c_void, PAGE_SIZE) }.unwrap();
+
+        // more than the memory of the host
+        match vm.read(pid(), addr, usize::MAX / 2) {
+            Err(OperationError::Fault { address, partial }) => {
+                assert_eq!(address, addr + PAGE_SIZE);
+                assert_eq!(partial.len(), PAGE_SIZE);
+            },
+            result => panic!("unexpected {:?}", result.map(|mem| mem.len())),
+        }
+        unsafe { munmap(addr as *mut c_void, PAGE_SIZE) }.unwrap();
+    }
+
+    #[test]
+    fn blocks_past_the_end_of_the_address_space()
+    {
+        let vm = ProcessVm{ };
+        let addr = usize::MAX - 10;
+        match vm.read(pid(), addr, 100) {
+            Err(OperationError::Fault { address, .. }) => assert_eq!(address, addr),
+            result => panic!("unexpected {:?}", result),
+        }
+        assert!(vm.write(pid(), addr, vec![0; 100]).is_err());
+        assert_eq!(accessible_size(addr, 100), 10);
+        assert_eq!(accessible_size(0x1000, 100), 100);
+    }
+}
@@ -2,34 +2,74 @@
  * The interfaces used by the tracer engine to interact with the tracee process.
  */
 use core::ffi::c_void;
-use std::io;
 
 use nix::{
     unistd::Pid,
-    libc::user_regs_struct,
+    libc::{ self, user_regs_struct },
+    errno::Errno,
     sys::ptrace,
 };
-use crate::targets::operation::{ RegisterOperation, MemoryOperation };
+use crate::targets::operation::{ RegisterOperation, MemoryOperation, OperationError, SyscallStop };
 
 
 
 #[derive(Clone, Debug)]
 pub struct Ptrace { }
 
+impl Ptrace {
+
+    /*
+     * Ask the kernel which syscall stop the process is at (PTRACE_GET_SYSCALL_INFO, Linux 5.3).
+     * The process must be traced with PTRACE_O_TRACESYSGOOD.
+     */
+    pub fn get_syscall_info(&self, pid: i32) -> Result<SyscallStop, OperationError> {
+        let mut info: libc::ptrace_syscall_info = unsafe { std::mem::zeroed() };
+        let size = std::mem::size_of::<libc::ptrace_syscall_info>();
+
+        let ret = unsafe {
+            libc::ptrace(libc::PTRACE_GET_SYSCALL_INFO, pid, size, &mut info as *mut libc::ptrace_syscall_info)
+        };
+        if ret < 0 {
+            return Err(OperationError::SyscallInfo { pid, errno: Errno::last() });
+        }
+
+        let args = |args: [u64; 6]| args.map(|arg| arg as usize);
+        let stop = unsafe {
+            match info.op {
+                libc::PTRACE_SYSCALL_INFO_ENTRY => SyscallStop::Entry {
+                    arch: info.arch,
+                    no: info.u.entry.nr as usize,
+                    args: args(info.u.entry.args),
+                },
+                libc::PTRACE_SYSCALL_INFO_EXIT => SyscallStop::Exit {
+                    arch: info.arch,
+                    retval: info.u.exit.sval as usize,
+                },
+                libc::PTRACE_SYSCALL_INFO_SECCOMP => SyscallStop::Seccomp {
+                    arch: info.arch,
+                    no: info.u.seccomp.nr as usize,
+                    args: args(info.u.seccomp.args),
+                },
+                _ => SyscallStop::None,
+            }
+        };
+        Ok(stop)
+    }
+}
+
 impl RegisterOperation for Ptrace {
 
-    fn read_registers(&self, pid: i32) -> Option<user_regs_struct> {
-        let pid = Pid::from_raw(pid);
-        Some(ptrace::getregs(pid).unwrap())
+    fn read_registers(&self, pid: i32) -> Result<user_regs_struct, OperationError> {
+        ptrace::getregs(Pid::from_raw(pid))
+            .map_err(|errno| OperationError::Registers { pid, errno })
     }
 
-    fn write_registers(&self, pid: i32, regs: user_regs_struct) -> Result<(), io::Error> {
-        let pid = Pid::from_raw(pid);
-        match ptrace::setregs(pid, regs) {
-            Ok(()) => return Ok(()),
-            Err(e) => {
-                eprintln!("[{}] Error setting registers: {}", pid, e);
-                return Err(e.into())
+    fn write_registers(&self, pid: i32, regs: user_regs_struct) -> Result<(), OperationError> {
+        match ptrace::setregs(Pid::from_raw(pid), regs) {
+            Ok(()) => Ok(()),
+            Err(errno) => {
+                eprintln!("[{}] Error setting registers: {}", pid, errno);
+                Err(OperationError::Registers { pid, errno })
             },
         }
     }
@@ -37,9 +77,10 @@ impl RegisterOperation for Ptrace {
 
 impl MemoryOperation for Ptrace {
 
-    fn read(&self, pid: i32, addr: usize, size: usize) -> Vec<u8> {
+    fn read(&self, pid: i32, addr: usize, size: usize) -> Result<Vec<u8>, OperationError> {
         let pid = Pid::from_raw(pid);
         let mut mem: Vec<u8> = Vec::new();
+        let start = addr;
         let mut addr = addr;
         let mut count = size + (4 - size % 4);
         
@@ -69,10 +110,18 @@ impl MemoryOperation for Ptrace {
             count -= 4;
             //println!("Read returns {}, remains {} bytes", word, count);
         }
-        mem
+
+        // the words read may go beyond the requested size
+        match mem.len() >= size {
+            true => {
+                mem.truncate(size);
+                Ok(mem)
+            },
+            false => Err(OperationError::Fault { address: start + mem.len(), partial: mem }),
+        }
     }
 
-    fn write(&self, pid: i32, addr: usize, mem: Vec<u8>) -> usize
+    fn write(&self, pid: i32, addr: usize, mem: Vec<u8>) -> Result<(), OperationError>
     {
         let pid = Pid::from_raw(pid);
         let mut addr = addr;
@@ -84,7 +133,8 @@ impl MemoryOperation for Ptrace {
 
         //println!("[WRITE] {:?}", mem);
 
-        let size = mem.len();
+        let size = mem.len() - pad_bytes;
+        let start = addr;
         let mut count = mem.len();
 
         for chunk in mem.chunks(4) {
@@ -106,7 +156,10 @@ impl MemoryOperation for Ptrace {
             count -= 4;
         }
 
-        size - count
+        match mem.len() - count >= size {
+            true => Ok(()),
+            false => Err(OperationError::Fault { address: start + mem.len() - count, partial: Vec::new() }),
+        }
     }
 
 }
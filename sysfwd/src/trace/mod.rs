@@ -0,0 +1,165 @@
+/*
+ * The traces recorded for an offline analysis.
+ *
+ * A trace file starts with a magic and the version of the format, followed by frames:
+ * a 8-byte big-endian length and a JSON payload, as on the data channel, except that
+ * the byte arrays (e.g. the buffers read or written) are hex strings.
+ * The first frame is the TraceHeader, each following frame a TraceRecord.
+ * The records are only appended, a trace cut by a crash is readable up to its last complete record.
+ *
+ * A trace recorded with the device can be replayed without it, see ReplayTransport.
+ */
+pub mod recorder;
+pub mod reader;
+pub mod replay;
+
+use std::time::{ SystemTime, UNIX_EPOCH };
+
+use serde::{ Serialize, Deserialize };
+use serde_json::{ Map, Value };
+
+use crate::{
+    arch::TargetArch,
+    syscall::{ Syscall, Timestamps },
+};
+
+pub use recorder::TraceRecorder;
+pub use reader::TraceReader;
+pub use replay::{ ReplayTransport, MatchStrictness };
+
+
+
+pub const TRACE_MAGIC: &[u8; 8] = b"SYSFWDTR";
+/*
+ * Bumped on every change of the records, an older trace is rejected.
+ * 2: the request sent to the executor is recorded, and the remote syscall is the reply as received.
+ * 3: the byte arrays are hex strings.
+ */
+pub const TRACE_VERSION: u32 = 3;
+
+const LENGTH_SIZE: usize = 8;
+
+/* The shorter arrays of small integers (e.g. the raw arguments) stay readable */
+const MIN_HEX_LENGTH: usize = 16;
+const HEX_KEY: &str = "$hex";
+
+
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct TraceHeader {
+    pub arch: TargetArch,
+    pub pid: i32,
+    /* The program and its arguments */
+    pub command: Vec<String>,
+    /* Nanoseconds since the Unix epoch */
+    pub start_time: u128,
+    /* The CLOCK_MONOTONIC of the syscall timestamps at the same moment */
+    pub start_monotonic: Option<u64>,
+}
+
+impl TraceHeader {
+    pub fn new(arch: TargetArch, pid: i32, command: Vec<String>) -> Self {
+        let start_time = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos());
+
+        Self { arch, pid, command, start_time, start_monotonic: Timestamps::now() }
+    }
+}
+
+/*
+ * A traced syscall, with the syscall sent to the executor and the one it executed when it was forwarded.
+ * The syscalls sent by the tracer itself (e.g. closing the remote fds on exit) have no timestamps.
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct TraceRecord {
+    pub pid: i32,
+    /* The syscall and the result seen by the tracee */
+    pub syscall: Syscall,
+    /* As sent to the executor, e.g. with the remote fds */
+    pub request: Option<Syscall>,
+    pub remote: Option<Syscall>,
+}
+
+
+/*
+ * Replace each array of bytes by { "$hex": "..." }, a JSON number per byte takes up to 4 bytes.
+ */
+fn compact_bytes(value: &mut Value)
+{
+    match value {
+        Value::Array(items) if items.len() >= MIN_HEX_LENGTH && items.iter().all(|item| item.as_u64().is_some_and(|byte| byte <= 0xff)) => {
+            let hex: String = items.iter()
+                .map(|byte| format!("{:02x}", byte.as_u64().unwrap()))
+                .collect();
+            *value = Value::Object(Map::from_iter([(HEX_KEY.to_string(), Value::String(hex))]));
+        },
+        Value::Array(items) => items.iter_mut().for_each(compact_bytes),
+        Value::Object(fields) => fields.values_mut().for_each(compact_bytes),
+        _ => (),
+    }
+}
+
+/* The reverse of compact_bytes() */
+fn expand_bytes(value: &mut Value) -> Result<(), String>
+{
+    match value {
+        Value::Object(fields) => match fields.get(HEX_KEY).and_then(Value::as_str) {
+            Some(hex) if fields.len() == 1 => {
+                if hex.len() % 2 != 0 {
+                    return Err(format!("odd length hex string of {} digits", hex.len()));
+                }
+                let bytes = (0..hex.len()).step_by(2)
+                    .map(|i| hex.get(i..i + 2)
+                        .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                        .map(Value::from)
+                        .ok_or_else(|| format!("invalid hex digits at {}", i)))
+                    .collect::<Result<Vec<Value>, String>>()?;
+                *value = Value::Array(bytes);
+                Ok(())
+            },
+            _ => fields.values_mut().try_for_each(expand_bytes),
+        },
+        Value::Array(items) => items.iter_mut().try_for_each(expand_bytes),
+        _ => Ok(()),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn byte_arrays_are_hex_strings()
+    {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let original = json!({
+            "content": bytes,
+            "args": [3, 4096, 5, 0, 0, 0],
+            "large": vec![256; MIN_HEX_LENGTH],
+        });
+
+        let mut value = original.clone();
+        compact_bytes(&mut value);
+        assert_eq!(value["content"][HEX_KEY].as_str().unwrap().len(), 512);
+        assert!(value["content"][HEX_KEY].as_str().unwrap().starts_with("000102"));
+        assert_eq!(value["args"], original["args"]);
+        assert_eq!(value["large"], original["large"]);
+
+        expand_bytes(&mut value).unwrap();
+        assert_eq!(value, original);
+    }
+
+    #[test]
+    fn invalid_hex_strings_are_rejected()
+    {
+        assert!(expand_bytes(&mut json!({ HEX_KEY: "abc" })).is_err());
+        assert!(expand_bytes(&mut json!([{ HEX_KEY: "zz" }])).is_err());
+        // only an object with the single hex field is a byte array
+        let mut value = json!({ HEX_KEY: "zz", "other": 1 });
+        assert!(expand_bytes(&mut value).is_ok());
+    }
+}
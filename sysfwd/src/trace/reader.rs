@@ -0,0 +1,214 @@
+/*
+ * Read back a trace file, record by record.
+ */
+use std::{
+    fs::File,
+    io::{ self, BufReader, Read },
+    path::Path,
+};
+
+use serde::de::DeserializeOwned;
+
+use crate::trace::{ expand_bytes, TraceHeader, TraceRecord, TRACE_MAGIC, TRACE_VERSION, LENGTH_SIZE };
+
+
+
+/* Bigger than any syscall, a larger frame means a corrupted file */
+const MAX_FRAME_SIZE: u64 = 1 << 30;
+
+
+pub struct TraceReader {
+    reader: BufReader<File>,
+    header: TraceHeader,
+    /* Set after an error, the next frames cannot be located */
+    broken: bool,
+}
+
+impl TraceReader {
+
+    pub fn open(path: &Path) -> io::Result<Self>
+    {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != TRACE_MAGIC {
+            return Err(invalid("not a trace file".to_string()));
+        }
+        let mut version = [0u8; 4];
+        reader.read_exact(&mut version)?;
+        let version = u32::from_be_bytes(version);
+        if version != TRACE_VERSION {
+            return Err(invalid(format!("unsupported trace version {}, expected {}", version, TRACE_VERSION)));
+        }
+
+        let header = read_frame(&mut reader)?
+            .ok_or_else(|| invalid("trace without header".to_string()))?;
+        Ok(TraceReader { reader, header, broken: false })
+    }
+
+    pub fn header(&self) -> &TraceHeader
+    {
+        &self.header
+    }
+}
+
+/*
+ * The records, from the first traced syscall.
+ * A truncated last record is reported as an error and ends the iteration.
+ */
+impl Iterator for TraceReader {
+    type Item = io::Result<TraceRecord>;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        if self.broken {
+            return None;
+        }
+        let record = read_frame(&mut self.reader).transpose();
+        if let Some(Err(_)) = record {
+            self.broken = true;
+        }
+        record
+    }
+}
+
+
+/* Ok(None) at the end of the file, between two frames */
+fn read_frame<T: DeserializeOwned>(reader: &mut impl Read) -> io::Result<Option<T>>
+{
+    let mut length = [0u8; LENGTH_SIZE];
+    let count = read_full(reader, &mut length)?;
+    if count == 0 {
+        return Ok(None);
+    }
+    if count < LENGTH_SIZE {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "truncated frame length"));
+    }
+
+    let length = u64::from_be_bytes(length);
+    if length > MAX_FRAME_SIZE {
+        return Err(invalid(format!("frame of {} bytes", length)));
+    }
+    let mut payload = vec![0u8; length as usize];
+    reader.read_exact(&mut payload)?;
+
+    let mut value = serde_json::from_slice(&payload)?;
+    expand_bytes(&mut value).map_err(invalid)?;
+    let value = serde_json::from_value(value)?;
+    Ok(Some(value))
+}
+
+/* Like read_exact(), but the number of bytes read before the end of the file is returned */
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize>
+{
+    let mut count = 0;
+    while count < buf.len() {
+        match reader.read(&mut buf[count..]) {
+            Ok(0) => break,
+            Ok(n) => count += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(count)
+}
+
+fn invalid(msg: String) -> io::Error
+{
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{ fs, io::Write, path::PathBuf };
+
+    use super::*;
+    use crate::{
+        arch::TargetArch,
+        syscall::Syscall,
+        trace::TraceRecorder,
+    };
+
+    fn trace_path(name: &str) -> PathBuf
+    {
+        std::env::temp_dir().join(format!("sysfwd-{}-{}.trace", name, std::process::id()))
+    }
+
+    fn syscall(name: &str, no: usize, retval: usize) -> Syscall
+    {
+        let mut syscall = Syscall::new();
+        syscall.name = name.to_string();
+        syscall.raw.no = no;
+        syscall.raw.args = vec![3, 0x1000, 5, 0, 0, 0];
+        syscall.raw.retval = retval;
+        syscall
+    }
+
+    #[test]
+    fn recorded_trace_is_read_back()
+    {
+        let path = trace_path("roundtrip");
+        let header = TraceHeader::new(TargetArch::X86_64, 42, vec!["prog".to_string(), "arg".to_string()]);
+        let local = syscall("read", 0, 5);
+        let request = syscall("read", 0, 0);
+        let mut remote = syscall("read", 0, 5);
+        remote.raw.args[0] = 77;
+
+        let mut recorder = TraceRecorder::create(&path, &header).unwrap();
+        recorder.record(42, &local, Some(&request), Some(&remote)).unwrap();
+        recorder.record(43, &syscall("getpid", 39, 42), None, None).unwrap();
+        assert_eq!(recorder.records(), 2);
+        drop(recorder);
+
+        let reader = TraceReader::open(&path).unwrap();
+        assert_eq!(reader.header().pid, 42);
+        assert_eq!(reader.header().command, ["prog", "arg"]);
+        let records: Vec<TraceRecord> = reader.collect::<io::Result<_>>().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].pid, 42);
+        assert_eq!(records[0].syscall.raw.retval, 5);
+        assert_eq!(records[0].request.as_ref().unwrap().raw.args, request.raw.args);
+        assert_eq!(records[0].remote.as_ref().unwrap().raw.args[0], 77);
+        assert_eq!(records[1].pid, 43);
+        assert_eq!(records[1].syscall.name, "getpid");
+        assert!(records[1].request.is_none() && records[1].remote.is_none());
+    }
+
+    #[test]
+    fn truncated_record_ends_the_trace()
+    {
+        let path = trace_path("truncated");
+        let header = TraceHeader::new(TargetArch::X86_64, 42, Vec::new());
+        let mut recorder = TraceRecorder::create(&path, &header).unwrap();
+        recorder.record(42, &syscall("read", 0, 5), None, None).unwrap();
+        recorder.record(42, &syscall("write", 1, 5), None, None).unwrap();
+        drop(recorder);
+
+        let length = fs::metadata(&path).unwrap().len();
+        fs::OpenOptions::new().write(true).open(&path).unwrap().set_len(length - 4).unwrap();
+
+        let mut reader = TraceReader::open(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(reader.next().unwrap().unwrap().syscall.name, "read");
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn older_version_is_rejected()
+    {
+        let path = trace_path("version");
+        let mut file = fs::File::create(&path).unwrap();
+        file.write_all(TRACE_MAGIC).unwrap();
+        file.write_all(&(TRACE_VERSION - 1).to_be_bytes()).unwrap();
+        drop(file);
+
+        let err = TraceReader::open(&path).err().unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
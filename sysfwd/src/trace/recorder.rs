@@ -0,0 +1,74 @@
+/*
+ * Stream the traced syscalls to a trace file.
+ */
+use std::{
+    fs::File,
+    io::{ self, BufWriter, Write },
+    path::Path,
+};
+
+use serde::Serialize;
+
+use crate::{
+    syscall::Syscall,
+    trace::{ compact_bytes, TraceHeader, TRACE_MAGIC, TRACE_VERSION },
+};
+
+
+
+/* Serialized as a TraceRecord, without cloning the syscalls */
+#[derive(Serialize)]
+struct RecordRef<'a> {
+    pid: i32,
+    syscall: &'a Syscall,
+    request: Option<&'a Syscall>,
+    remote: Option<&'a Syscall>,
+}
+
+
+pub struct TraceRecorder {
+    writer: BufWriter<File>,
+    records: usize,
+}
+
+impl TraceRecorder {
+
+    /* An existing file is truncated */
+    pub fn create(path: &Path, header: &TraceHeader) -> io::Result<Self>
+    {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(TRACE_MAGIC)?;
+        writer.write_all(&TRACE_VERSION.to_be_bytes())?;
+
+        let mut recorder = TraceRecorder { writer, records: 0 };
+        recorder.write_frame(header)?;
+        recorder.writer.flush()?;
+        Ok(recorder)
+    }
+
+    /*
+     * Each record is flushed, so that the trace survives a crash of the tracer.
+     */
+    pub fn record(&mut self, pid: i32, syscall: &Syscall, request: Option<&Syscall>,
+                  remote: Option<&Syscall>) -> io::Result<()>
+    {
+        self.write_frame(&RecordRef { pid, syscall, request, remote })?;
+        self.writer.flush()?;
+        self.records += 1;
+        Ok(())
+    }
+
+    pub fn records(&self) -> usize
+    {
+        self.records
+    }
+
+    fn write_frame<T: Serialize>(&mut self, value: &T) -> io::Result<()>
+    {
+        let mut value = serde_json::to_value(value)?;
+        compact_bytes(&mut value);
+        let payload = serde_json::to_vec(&value)?;
+        self.writer.write_all(&(payload.len() as u64).to_be_bytes())?;
+        self.writer.write_all(&payload)
+    }
+}
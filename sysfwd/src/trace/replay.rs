@@ -0,0 +1,341 @@
+/*
+ * Replay a recorded trace in place of the executor: the forwarded syscalls are answered
+ * with the remote results of the trace, so that the tracee runs again without the device.
+ *
+ * The recorded syscalls are consumed in order. A syscall which does not match the next
+ * recorded one is looked for further in the trace (e.g. the threads are scheduled differently),
+ * and every divergence is reported.
+ */
+use std::{
+    collections::VecDeque,
+    fmt,
+    path::Path,
+};
+
+use serde::{ Serialize, Deserialize };
+use serde_json::Value;
+
+use crate::{
+    error::{ Error, Result },
+    protocol::data::{ NotifySignal, Transport },
+    syscall::Syscall,
+    trace::TraceReader,
+};
+
+
+
+/*
+ * How much a forwarded syscall has to look like the recorded one, as it was sent to the executor.
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchStrictness {
+    /* The same syscall */
+    Name,
+    /*
+     * The same syscall with the same inputs: the addresses, which change between runs (e.g. ASLR),
+     * and the output buffers are ignored. An undecoded syscall, or one recorded without its request,
+     * only needs the same name.
+     */
+    Args,
+    /* The same syscall with the same raw arguments, addresses included */
+    Exact,
+}
+
+#[derive(Clone, Debug)]
+pub enum Divergence {
+    /* The syscall matches a later recorded syscall, the previous ones are kept for later */
+    OutOfOrder { skipped: usize },
+    /* No recorded syscall matches, the tracee gets an error */
+    Unexpected,
+    /* The recorded syscall was never replayed */
+    Missing,
+}
+
+#[derive(Clone, Debug)]
+pub struct DivergenceReport {
+    /* The number of syscalls replayed before */
+    pub position: usize,
+    pub name: String,
+    pub args: Vec<usize>,
+    pub divergence: Divergence,
+}
+
+impl fmt::Display for DivergenceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let args: Vec<String> = self.args.iter().take(6).map(|arg| format!("{:#x}", arg)).collect();
+        write!(f, "#{} {}({}): ", self.position, self.name, args.join(", "))?;
+        match self.divergence {
+            Divergence::OutOfOrder { skipped } => write!(f, "replayed out of order, {} recorded syscalls skipped", skipped),
+            Divergence::Unexpected => write!(f, "not in the trace"),
+            Divergence::Missing => write!(f, "recorded but never replayed"),
+        }
+    }
+}
+
+
+/* A forwarded syscall of the trace */
+struct Recorded {
+    request: Option<Syscall>,
+    remote: Syscall,
+}
+
+impl Recorded {
+    /* The raw values are the ones of the tracee in both */
+    fn sent(&self) -> &Syscall
+    {
+        self.request.as_ref().unwrap_or(&self.remote)
+    }
+}
+
+
+pub struct ReplayTransport {
+    strictness: MatchStrictness,
+    /* The forwarded syscalls of the trace, not replayed yet */
+    pending: VecDeque<Recorded>,
+    replayed: usize,
+    divergences: Vec<DivergenceReport>,
+}
+
+impl ReplayTransport {
+
+    /*
+     * Load the forwarded syscalls of a trace. A trace cut by a crash is replayed up to its last record.
+     */
+    pub fn open(path: &Path, strictness: MatchStrictness) -> Result<Self>
+    {
+        let reader = TraceReader::open(path).map_err(Error::Transport)?;
+
+        let mut pending = VecDeque::new();
+        for record in reader {
+            match record {
+                Ok(record) => if let Some(remote) = record.remote {
+                    pending.push_back(Recorded { request: record.request, remote });
+                },
+                Err(err) => {
+                    eprintln!("The trace {} is truncated: {}", path.display(), err);
+                    break;
+                },
+            }
+        }
+
+        Ok(ReplayTransport {
+            strictness,
+            pending,
+            replayed: 0,
+            divergences: Vec::new(),
+        })
+    }
+
+    pub fn divergences(&self) -> &[DivergenceReport]
+    {
+        &self.divergences
+    }
+
+    fn report(&mut self, syscall: &Syscall, divergence: Divergence)
+    {
+        let report = DivergenceReport {
+            position: self.replayed,
+            name: syscall.name.clone(),
+            args: syscall.raw.args.clone(),
+            divergence,
+        };
+        eprintln!("[REPLAY] Divergence {}", report);
+        self.divergences.push(report);
+    }
+
+    fn matches(&self, syscall: &Syscall, recorded: &Recorded) -> bool
+    {
+        let sent = recorded.sent();
+        if syscall.raw.no != sent.raw.no || syscall.name != sent.name {
+            return false;
+        }
+        match self.strictness {
+            MatchStrictness::Name => true,
+            MatchStrictness::Args => match (&syscall.decoded, recorded.request.as_ref().and_then(|r| r.decoded.as_ref())) {
+                (Some(_), Some(_)) => {
+                    let inputs = |syscall: &Syscall| serde_json::to_value(&syscall.decoded).map(inputs_of).ok();
+                    inputs(syscall) == inputs(sent)
+                },
+                _ => true,
+            },
+            MatchStrictness::Exact => syscall.raw.args == sent.raw.args,
+        }
+    }
+}
+
+impl Transport for ReplayTransport {
+
+    fn send_syscall_entry(&mut self, syscall: &Syscall) -> Result<Syscall>
+    {
+        let index = match self.pending.iter().position(|recorded| self.matches(syscall, recorded)) {
+            Some(index) => index,
+            None => {
+                self.report(syscall, Divergence::Unexpected);
+                return Err(Error::Protocol(format!("replay: {} is not in the trace", syscall.name)));
+            },
+        };
+        if index > 0 {
+            self.report(syscall, Divergence::OutOfOrder { skipped: index });
+        }
+
+        let mut reply = self.pending.remove(index).unwrap().remote;
+        // the timestamps of the tracer are the ones of this run
+        let mut timestamps = syscall.timestamps.clone();
+        timestamps.executor_start = reply.timestamps.executor_start;
+        timestamps.executor_end = reply.timestamps.executor_end;
+        reply.timestamps = timestamps;
+
+        self.replayed += 1;
+        Ok(reply)
+    }
+
+    /* There is no executor to notify */
+    fn notify_signal(&mut self, _signal: &NotifySignal) -> Result<()>
+    {
+        Ok(())
+    }
+
+    fn shutdown(&mut self) -> Result<()>
+    {
+        let missing: Vec<Recorded> = self.pending.drain(..).collect();
+        for recorded in missing.iter() {
+            self.report(recorded.sent(), Divergence::Missing);
+        }
+        println!("[REPLAY] {} syscalls replayed, {} divergences", self.replayed, self.divergences.len());
+        Ok(())
+    }
+}
+
+
+/*
+ * The inputs of a decoded syscall, as serialized: the return value, the addresses
+ * and the content of the output buffers are removed.
+ */
+fn inputs_of(mut decoded: Value) -> Value
+{
+    // DecodedSyscall is serialized as { "Variant": { fields... } }
+    if let Value::Object(variant) = &mut decoded {
+        for fields in variant.values_mut() {
+            if let Value::Object(fields) = fields {
+                fields.remove("retval");
+            }
+        }
+    }
+    strip_outputs(&mut decoded);
+    decoded
+}
+
+fn strip_outputs(value: &mut Value)
+{
+    match value {
+        Value::Object(obj) => {
+            obj.remove("address");
+            obj.remove("fault");
+            // an Address holds a pointer, and the pointed value is often one
+            if obj.contains_key("direction") && obj.get("content").is_some_and(Value::is_number) {
+                obj.remove("value");
+                obj.remove("content");
+            }
+            if obj.get("direction").and_then(Value::as_str) == Some("Out") {
+                obj.remove("content");
+            }
+            obj.values_mut().for_each(strip_outputs);
+        },
+        Value::Array(items) => items.iter_mut().for_each(strip_outputs),
+        _ => (),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syscall::{ decoder::DecodedSyscall, syscalls::io::Read };
+
+    /* A read of the tracee, decoded as on the syscall entry */
+    fn read(fd: usize, buf: usize, count: usize) -> Syscall
+    {
+        let mut syscall = Syscall::new();
+        syscall.name = "read".to_string();
+        syscall.raw.no = 0;
+        syscall.raw.args = vec![fd, buf, count, 0, 0, 0];
+        syscall.decoded = Some(DecodedSyscall::Read(Read::new(syscall.raw.clone())));
+        syscall
+    }
+
+    fn close(fd: usize) -> Syscall
+    {
+        let mut syscall = Syscall::new();
+        syscall.name = "close".to_string();
+        syscall.raw.no = 3;
+        syscall.raw.args = vec![fd, 0, 0, 0, 0, 0];
+        syscall
+    }
+
+    /* A trace in which every syscall returns its position */
+    fn replay(strictness: MatchStrictness, requests: Vec<Syscall>) -> ReplayTransport
+    {
+        let pending = requests.into_iter().enumerate().map(|(position, request)| {
+            let mut remote = request.clone();
+            remote.raw.retval = position;
+            Recorded { request: Some(request), remote }
+        }).collect();
+        ReplayTransport { strictness, pending, replayed: 0, divergences: Vec::new() }
+    }
+
+    #[test]
+    fn name_ignores_the_arguments()
+    {
+        let mut transport = replay(MatchStrictness::Name, vec![read(3, 0x1000, 16)]);
+        assert!(transport.send_syscall_entry(&close(3)).is_err());
+        assert_eq!(transport.send_syscall_entry(&read(4, 0x2000, 32)).unwrap().raw.retval, 0);
+        assert!(matches!(transport.divergences(), [DivergenceReport { divergence: Divergence::Unexpected, .. }]));
+    }
+
+    #[test]
+    fn args_ignores_the_addresses()
+    {
+        let mut transport = replay(MatchStrictness::Args, vec![read(3, 0x1000, 16), read(3, 0x1000, 16)]);
+        assert_eq!(transport.send_syscall_entry(&read(3, 0x7000, 16)).unwrap().raw.retval, 0);
+        assert!(transport.send_syscall_entry(&read(4, 0x1000, 16)).is_err());
+        assert!(transport.send_syscall_entry(&read(3, 0x1000, 32)).is_err());
+        assert_eq!(transport.send_syscall_entry(&read(3, 0x1000, 16)).unwrap().raw.retval, 1);
+    }
+
+    #[test]
+    fn args_of_undecoded_syscalls_are_not_compared()
+    {
+        let mut transport = replay(MatchStrictness::Args, vec![close(3)]);
+        assert_eq!(transport.send_syscall_entry(&close(4)).unwrap().raw.retval, 0);
+        assert!(transport.divergences().is_empty());
+    }
+
+    #[test]
+    fn exact_compares_the_raw_arguments()
+    {
+        let mut transport = replay(MatchStrictness::Exact, vec![read(3, 0x1000, 16)]);
+        assert!(transport.send_syscall_entry(&read(3, 0x7000, 16)).is_err());
+        assert_eq!(transport.send_syscall_entry(&read(3, 0x1000, 16)).unwrap().raw.retval, 0);
+    }
+
+    #[test]
+    fn divergences_are_reported()
+    {
+        let mut transport = replay(MatchStrictness::Exact, vec![close(3), close(4), close(5), close(6)]);
+
+        // close(3) and close(4) are kept for later
+        assert_eq!(transport.send_syscall_entry(&close(5)).unwrap().raw.retval, 2);
+        assert_eq!(transport.send_syscall_entry(&close(3)).unwrap().raw.retval, 0);
+        transport.shutdown().unwrap();
+
+        let divergences: Vec<(usize, usize, &Divergence)> = transport.divergences().iter()
+            .map(|report| (report.position, report.args[0], &report.divergence))
+            .collect();
+        assert!(matches!(divergences[..], [
+            (0, 5, Divergence::OutOfOrder { skipped: 2 }),
+            (2, 4, Divergence::Missing),
+            (2, 6, Divergence::Missing),
+        ]));
+    }
+}
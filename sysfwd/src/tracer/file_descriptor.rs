@@ -2,7 +2,7 @@
  * To manage the file descriptor management and translation between local, remote, user and kernel.
  */
 
-use std::collections::HashSet;
+use std::collections::{ HashMap, HashSet };
 
 
 
@@ -28,10 +28,12 @@ static REMOTE_FD_OFFSET: usize = 4096;
  * A wrapper structure around hashmap for managing file descriptor translation.
  * For now, it is only used to store remote FD.
  */
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct FdTable {
     fd_table: Vec<Option<FdLocation>>,
     available_fd: HashSet<usize>,
+    /* The FDs opened before the tracing started keep their local number */
+    adopted_fd: HashMap<usize, usize>,
 }
 
 impl FdTable {
@@ -41,6 +43,7 @@ impl FdTable {
         Self { 
             fd_table: Vec::new(),
             available_fd: HashSet::new(),
+            adopted_fd: HashMap::new(),
         }
     }
 
@@ -67,6 +70,14 @@ impl FdTable {
         self.insert(fd) + REMOTE_FD_OFFSET
     }
 
+    /*
+     * Associate a FD the tracee opened before being attached with a remote-kernel FD.
+     */
+    pub fn adopt_remote(&mut self, user_fd: usize, kernel_fd: usize)
+    {
+        self.adopted_fd.insert(user_fd, kernel_fd);
+    }
+
     /* The table is not used for local FD.
     pub fn open_local(&mut self, kernel_fd: usize) -> usize
     {
@@ -98,10 +109,10 @@ impl FdTable {
 
     pub fn close_remote(&mut self, user_fd: usize) -> Option<usize>
     {
-        //if user_fd < REMOTE_FD_OFFSET {
-        //    return None;
-        //}
-        //let user_fd = user_fd - REMOTE_FD_OFFSET;
+        if user_fd < REMOTE_FD_OFFSET {
+            return self.adopted_fd.remove(&user_fd);
+        }
+        let user_fd = user_fd - REMOTE_FD_OFFSET;
 
         if let Some(kernel_fd) = self.remove(user_fd) {
             if let FdLocation::Remote(remote_fd) = kernel_fd {
@@ -116,6 +127,51 @@ impl FdTable {
 
     //pub fn close_local(&mut self, user_fd: usize) -> Option<usize>
 
+    /*
+     * The user / remote-kernel FD associations.
+     */
+    pub fn remote_fds(&self) -> Vec<(usize, usize)>
+    {
+        self.fd_table.iter()
+            .enumerate()
+            .filter_map(|(user_fd, fd)| match fd {
+                Some(FdLocation::Remote(fd)) => Some((user_fd + REMOTE_FD_OFFSET, *fd)),
+                _ => None,
+            })
+            .chain(self.adopted_fd.iter().map(|(&user_fd, &fd)| (user_fd, fd)))
+            .collect()
+    }
+
+    /*
+     * Associate a user FD with another remote-kernel FD.
+     * Typically used when the remote FDs are duplicated for a forked process.
+     */
+    pub fn replace_remote(&mut self, user_fd: usize, kernel_fd: usize)
+    {
+        if let Some(fd) = self.adopted_fd.get_mut(&user_fd) {
+            *fd = kernel_fd;
+        } else if let Some(fd) = user_fd.checked_sub(REMOTE_FD_OFFSET).and_then(|user_fd| self.fd_table.get_mut(user_fd)) {
+            *fd = Some(FdLocation::Remote(kernel_fd));
+        }
+    }
+
+    /*
+     * Remove every association and return the remote FDs.
+     * Typically used when the tracee is killed.
+     */
+    pub fn drain_remote(&mut self) -> Vec<usize>
+    {
+        self.available_fd.clear();
+        self.fd_table.drain(..)
+            .flatten()
+            .filter_map(|fd| match fd {
+                FdLocation::Remote(fd) => Some(fd),
+                FdLocation::Local(_) => None,
+            })
+            .chain(self.adopted_fd.drain().map(|(_, fd)| fd))
+            .collect()
+    }
+
     /*
      * Translate a FD used in user space with the corresponding FD used by the remote kernel.
      * Typically used during the entry a read() or write() system call.
@@ -123,7 +179,7 @@ impl FdTable {
     pub fn translate(&self, user_fd: usize) -> Option<usize>
     {
         if user_fd < REMOTE_FD_OFFSET {
-            return None;
+            return self.adopted_fd.get(&user_fd).copied();
         }
         let user_fd = user_fd - REMOTE_FD_OFFSET;
 
@@ -144,3 +200,87 @@ impl FdTable {
 
 
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closed_fds_are_released()
+    {
+        let mut table = FdTable::new();
+        let first = table.open_remote(10);
+        let second = table.open_remote(11);
+        assert_eq!((first, second), (REMOTE_FD_OFFSET, REMOTE_FD_OFFSET + 1));
+
+        assert_eq!(table.close_remote(first), Some(10));
+        assert_eq!(table.translate(first), None);
+        assert_eq!(table.close_remote(first), None);
+        // a local fd is not in the table
+        assert_eq!(table.close_remote(0), None);
+        assert_eq!(table.translate(second), Some(11));
+
+        // the lowest user fd is reused
+        assert_eq!(table.open_remote(12), first);
+        assert_eq!(table.translate(first), Some(12));
+    }
+
+    #[test]
+    fn adopted_fds_keep_their_number()
+    {
+        let mut table = FdTable::new();
+        table.adopt_remote(3, 20);
+        let opened = table.open_remote(21);
+
+        assert_eq!(table.translate(3), Some(20));
+        assert_eq!(table.translate(4), None);
+        let mut fds = table.remote_fds();
+        fds.sort();
+        assert_eq!(fds, [(3, 20), (opened, 21)]);
+
+        assert_eq!(table.close_remote(3), Some(20));
+        assert_eq!(table.translate(3), None);
+        assert_eq!(table.remote_fds(), [(opened, 21)]);
+    }
+
+    #[test]
+    fn replaced_fds_keep_their_user_fd()
+    {
+        let mut table = FdTable::new();
+        table.adopt_remote(3, 20);
+        let opened = table.open_remote(21);
+
+        // e.g. the remote fds duplicated for a forked process
+        table.replace_remote(3, 30);
+        table.replace_remote(opened, 31);
+        assert_eq!(table.translate(3), Some(30));
+        assert_eq!(table.translate(opened), Some(31));
+
+        // an unknown fd is not added
+        table.replace_remote(5, 32);
+        table.replace_remote(opened + 1, 33);
+        assert_eq!(table.translate(5), None);
+        assert_eq!(table.translate(opened + 1), None);
+    }
+
+    #[test]
+    fn drained_table_is_empty()
+    {
+        let mut table = FdTable::new();
+        table.adopt_remote(3, 20);
+        let first = table.open_remote(21);
+        let second = table.open_remote(22);
+        table.close_remote(first);
+
+        let mut fds = table.drain_remote();
+        fds.sort();
+        assert_eq!(fds, [20, 22]);
+        assert!(table.remote_fds().is_empty());
+        assert_eq!(table.translate(3), None);
+        assert_eq!(table.translate(second), None);
+
+        // the user fds start over
+        assert_eq!(table.open_remote(23), REMOTE_FD_OFFSET);
+    }
+}
@@ -5,6 +5,12 @@
  * In the future, it would be nice to support eBPF filter in a similar way as seccomp does.
  * But what would it brings more than the Rule trait?
  */
+use std::collections::HashSet;
+
+use nix::{
+    errno::Errno,
+    sys::signal::Signal,
+};
 use serde::{Serialize, Deserialize};
 use crate::tracer::Syscall;
 
@@ -40,6 +46,81 @@ pub enum Decision {
      * Terminate the process
      */
     Kill        = 5,
+    /*
+     * Execute the syscall locally, then remotly with executor after the local exit.
+     * The rule chooses which result is returned.
+     */
+    ForwardExit = 6,
+}
+
+
+/*
+ * Which execution gives its result to the tracee, with the ForwardExit decision.
+ */
+#[derive(PartialEq)]
+#[derive(Clone, Copy, Debug)]
+pub enum ResultOrigin {
+    Local,
+    Remote,
+}
+
+
+
+/*
+ * The result given to the tracee instead of executing the syscall, with the NoExec decision.
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug, Default)]
+pub struct FakeResult {
+    /* The return value, i.e. -errno on failure */
+    pub retval: usize,
+    /* The out buffers to fill before returning */
+    pub buffers: Vec<FakeBuffer>,
+}
+
+/*
+ * The content written to the memory pointed by a syscall argument,
+ * up to the size given by another argument (e.g. the count of read()).
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+pub struct FakeBuffer {
+    pub arg: usize,
+    #[serde(default)]
+    pub size_arg: Option<usize>,
+    pub content: Vec<u8>,
+}
+
+impl FakeResult {
+
+    pub fn success(retval: usize) -> Self
+    {
+        Self { retval, buffers: Vec::new() }
+    }
+
+    pub fn error(errno: Errno) -> Self
+    {
+        Self { retval: -(errno as isize) as usize, buffers: Vec::new() }
+    }
+
+    /*
+     * Fill the buffer pointed by the argument number `arg` (starting from 0) with `content`.
+     * The buffer is expected to have a fixed size (e.g. a struct stat) at least as large as `content`.
+     */
+    pub fn with_buffer(mut self, arg: usize, content: Vec<u8>) -> Self
+    {
+        self.buffers.push(FakeBuffer { arg, size_arg: None, content });
+        self
+    }
+
+    /*
+     * Like with_buffer(), but `content` is cut to the size of the buffer, given by the argument number `size_arg`.
+     */
+    pub fn with_sized_buffer(mut self, arg: usize, size_arg: usize, content: Vec<u8>) -> Self
+    {
+        self.buffers.push(FakeBuffer { arg, size_arg: Some(size_arg), content });
+        self
+    }
 }
 
 
@@ -48,8 +129,10 @@ pub trait Rule {
 
     /*
      * The filter functions called after the decoder on syscall entry and exit.
+     * On entry, the rule taking the decision may also modify the syscall (e.g., rewrite a path),
+     * it is then encoded back before its execution, locally or forwarded.
      */
-    fn filter_entry(&mut self, syscall: Syscall) -> Result<Decision, std::io::Error>;
+    fn filter_entry(&mut self, syscall: &mut Syscall) -> Result<Decision, std::io::Error>;
     fn filter_exit(&mut self, syscall: Syscall) -> Result<Decision, std::io::Error>;
 
     /*
@@ -57,6 +140,52 @@ pub trait Rule {
      * E.g., to keep track of file descriptors during an execution
      */
     fn on_syscall_exit(&mut self, syscall: Syscall);
+
+    /*
+     * The names of the syscalls the rule needs decoded to take its decisions.
+     * The other syscalls may reach the rule without their decoded arguments.
+     * None means every syscall.
+     */
+    fn syscalls(&self) -> Option<Vec<&'static str>> {
+        None
+    }
+
+    /*
+     * The result of a syscall on which the rule decided NoExec.
+     * By default, the syscall succeeds and returns 0.
+     */
+    fn no_exec(&mut self, _syscall: Syscall) -> FakeResult {
+        FakeResult::default()
+    }
+
+    /*
+     * The signal terminating the tracee when the rule decided Kill.
+     */
+    fn kill_signal(&mut self, _syscall: Syscall) -> Signal {
+        Signal::SIGKILL
+    }
+
+    /*
+     * The complete syscall, with its Out arguments decoded, when the rule decided Inspect.
+     * It is called on syscall exit, before the tracee resumes.
+     */
+    fn inspect(&mut self, _syscall: Syscall) {
+    }
+
+    /*
+     * Adapt the syscall before it is executed remotly, when the rule decided ForwardExit.
+     * E.g., to target a remote device instead of the local file.
+     */
+    fn prepare_forward_exit(&mut self, _syscall: &mut Syscall) {
+    }
+
+    /*
+     * Choose the result returned to the tracee, when the rule decided ForwardExit.
+     * By default, the local behaviour is kept.
+     */
+    fn select_result(&mut self, _local: Syscall, _remote: Syscall) -> ResultOrigin {
+        ResultOrigin::Local
+    }
 }
 
 
@@ -91,26 +220,40 @@ impl Filter {
     /*
      * Return the decision made by the first rule to match,
      * otherwise returns the default decision.
+     *
+     * On syscall entry, the syscall modified by this rule replaces the one given,
+     * and true is returned along with the decision.
+     * The rule is kept in the syscall, as the threads of the tracee interleave their syscalls.
      */
-    pub fn filter(&mut self, insyscall: bool, syscall: &Syscall) -> Decision
+    pub fn filter(&mut self, insyscall: bool, syscall: &mut Syscall) -> (Decision, bool)
     {
         let mut decision: Option<Decision> = None;
         // = self.default_decision;
+        let mut decided_by = None;
+        let mut rewritten = false;
 
-        for rule in self.rules.iter_mut() {
+        for (index, rule) in self.rules.iter_mut().enumerate() {
 
             // We clone to ensure each rule has the correct syscall and has not been modified by the user.
-            let clone_syscall = syscall.clone();
-
-            let result = match insyscall {
-                false => rule.filter_entry(clone_syscall),
-                true => rule.filter_exit(clone_syscall),
+            let (result, filtered) = match insyscall {
+                false => {
+                    let mut clone_syscall = syscall.clone();
+                    (rule.filter_entry(&mut clone_syscall), Some(clone_syscall))
+                },
+                true => (rule.filter_exit(syscall.clone()), None),
             };
 
             match result {
                 Ok(result) => {
                     if result != Decision::Pass {
                         decision = Some(result);
+                        decided_by = Some(index);
+                        // only the changes of the rule taking the decision are kept
+                        if let Some(mut filtered) = filtered.filter(|filtered| is_modified(syscall, filtered)) {
+                            filtered.decision = syscall.decision;
+                            *syscall = filtered;
+                            rewritten = true;
+                        }
                         break;
                     }
                 },
@@ -118,10 +261,85 @@ impl Filter {
             }
         }
 
-        if decision == None {
-            return self.default_decision;
+        // on syscall exit, the rules usually repeat the entry decision
+        if !insyscall {
+            syscall.rule = decided_by;
+        }
+
+        match decision {
+            Some(decision) => (decision, rewritten),
+            None => (self.default_decision, false),
+        }
+    }
+
+    /*
+     * The fake result given by the rule which decided NoExec on syscall entry.
+     */
+    pub fn no_exec(&mut self, syscall: &Syscall) -> FakeResult
+    {
+        match self.deciding_rule(syscall) {
+            Some(rule) => rule.no_exec(syscall.clone()),
+            None => FakeResult::default(),
+        }
+    }
+
+    /*
+     * The signal given by the rule which decided Kill on syscall entry.
+     */
+    pub fn kill_signal(&mut self, syscall: &Syscall) -> Signal
+    {
+        match self.deciding_rule(syscall) {
+            Some(rule) => rule.kill_signal(syscall.clone()),
+            None => Signal::SIGKILL,
+        }
+    }
+
+    /*
+     * Deliver the complete syscall to the rule which decided Inspect on syscall entry.
+     */
+    pub fn inspect(&mut self, syscall: &Syscall)
+    {
+        if let Some(rule) = self.deciding_rule(syscall) {
+            rule.inspect(syscall.clone());
+        }
+    }
+
+    /*
+     * Let the rule which decided ForwardExit on syscall entry adapt the remote syscall.
+     */
+    pub fn prepare_forward_exit(&mut self, syscall: &mut Syscall)
+    {
+        if let Some(rule) = self.deciding_rule(syscall) {
+            rule.prepare_forward_exit(syscall);
+        }
+    }
+
+    /*
+     * The result chosen by the rule which decided ForwardExit on syscall entry.
+     */
+    pub fn select_result(&mut self, local: &Syscall, remote: &Syscall) -> ResultOrigin
+    {
+        match self.deciding_rule(local) {
+            Some(rule) => rule.select_result(local.clone(), remote.clone()),
+            None => ResultOrigin::Local,
+        }
+    }
+
+    fn deciding_rule(&mut self, syscall: &Syscall) -> Option<&mut Box<dyn Rule>>
+    {
+        syscall.rule.and_then(|index| self.rules.get_mut(index))
+    }
+
+    /*
+     * The union of the syscalls needed by the rules, or None if a rule needs every syscall.
+     */
+    pub fn syscalls(&self) -> Option<HashSet<&'static str>>
+    {
+        let mut syscalls = HashSet::new();
+        for rule in self.rules.iter() {
+            syscalls.extend(rule.syscalls()?);
         }
-        return decision.unwrap();
+        Some(syscalls)
     }
 
     /*
@@ -138,3 +356,95 @@ impl Filter {
 
 }
 
+
+/*
+ * Whether a rule modified the syscall, the decoded arguments are compared through their serialization.
+ */
+fn is_modified(original: &Syscall, filtered: &Syscall) -> bool
+{
+    if original.raw.no != filtered.raw.no || original.raw.args != filtered.raw.args {
+        return true;
+    }
+    match (serde_json::to_vec(&original.decoded), serde_json::to_vec(&filtered.decoded)) {
+        (Ok(original), Ok(filtered)) => original != filtered,
+        _ => false,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{ cell::RefCell, rc::Rc };
+
+    use super::*;
+
+    /* Inspect the syscalls of a given name, and keep the ones inspected */
+    struct InspectRule {
+        name: &'static str,
+        inspected: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Rule for InspectRule {
+        fn filter_entry(&mut self, syscall: &mut Syscall) -> Result<Decision, std::io::Error> {
+            match syscall.name == self.name {
+                true => Ok(Decision::Inspect),
+                false => Ok(Decision::Pass),
+            }
+        }
+
+        fn filter_exit(&mut self, syscall: Syscall) -> Result<Decision, std::io::Error> {
+            self.filter_entry(&mut syscall.clone())
+        }
+
+        fn on_syscall_exit(&mut self, _syscall: Syscall) {
+        }
+
+        fn inspect(&mut self, syscall: Syscall) {
+            self.inspected.borrow_mut().push(format!("{}:{}", self.name, syscall.name));
+        }
+    }
+
+    fn syscall(name: &str) -> Syscall
+    {
+        let mut syscall = Syscall::new();
+        syscall.name = name.to_string();
+        syscall
+    }
+
+    #[test]
+    fn exit_callbacks_reach_the_rule_of_each_thread()
+    {
+        let inspected = Rc::new(RefCell::new(Vec::new()));
+        let mut filter = Filter::new(String::from("test"));
+        filter.insert(0, Box::new(InspectRule { name: "read", inspected: inspected.clone() }));
+        filter.insert(1, Box::new(InspectRule { name: "write", inspected: inspected.clone() }));
+
+        // the entries of two threads, then their exits in the same order
+        let mut read = syscall("read");
+        let mut write = syscall("write");
+        assert_eq!(filter.filter(false, &mut read), (Decision::Inspect, false));
+        assert_eq!(filter.filter(false, &mut write), (Decision::Inspect, false));
+
+        assert_eq!(filter.filter(true, &mut read).0, Decision::Inspect);
+        filter.inspect(&read);
+        assert_eq!(filter.filter(true, &mut write).0, Decision::Inspect);
+        filter.inspect(&write);
+
+        assert_eq!(*inspected.borrow(), vec!["read:read", "write:write"]);
+    }
+
+    #[test]
+    fn no_rule_decided_without_a_match()
+    {
+        let inspected = Rc::new(RefCell::new(Vec::new()));
+        let mut filter = Filter::new(String::from("test"));
+        filter.insert(0, Box::new(InspectRule { name: "read", inspected: inspected.clone() }));
+
+        let mut other = syscall("openat");
+        assert_eq!(filter.filter(false, &mut other), (Decision::Continue, false));
+        assert_eq!(other.rule, None);
+        filter.inspect(&other);
+
+        assert!(inspected.borrow().is_empty());
+    }
+}
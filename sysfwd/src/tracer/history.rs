@@ -0,0 +1,285 @@
+/*
+ * The history of the traced syscalls, kept in a bounded ring buffer,
+ * and the statistics, kept as running counters so that they cover the syscalls dropped from the history.
+ */
+use std::{
+    collections::{ hash_map, HashMap, VecDeque },
+    fs::OpenOptions,
+    io::{ self, BufWriter, Write },
+    path::Path,
+};
+
+use crate::syscall::Syscall;
+
+
+
+/* Enough to look back at what led to an error, without growing with the tracee lifetime */
+pub const DEFAULT_HISTORY_CAPACITY: usize = 4096;
+
+
+pub struct SyscallHistory {
+    capacity: usize,
+    /* Only the raw values are kept, without the decoded buffers nor the call stack */
+    raw_only: bool,
+    syscalls: VecDeque<Syscall>,
+    /* The syscalls overwritten since the last flush */
+    dropped: u64,
+}
+
+impl SyscallHistory {
+
+    pub fn new(capacity: usize, raw_only: bool) -> Self
+    {
+        SyscallHistory {
+            capacity,
+            raw_only,
+            syscalls: VecDeque::with_capacity(capacity),
+            dropped: 0,
+        }
+    }
+
+    pub fn push(&mut self, syscall: &Syscall)
+    {
+        if self.capacity == 0 {
+            self.dropped += 1;
+            return;
+        }
+        if self.syscalls.len() == self.capacity {
+            self.syscalls.pop_front();
+            self.dropped += 1;
+        }
+
+        let mut syscall = syscall.clone();
+        if self.raw_only {
+            syscall.decoded = None;
+            syscall.backtrace = None;
+        }
+        self.syscalls.push_back(syscall);
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.syscalls.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.syscalls.is_empty()
+    }
+
+    pub fn dropped(&self) -> u64
+    {
+        self.dropped
+    }
+
+    /* From the oldest to the latest syscall */
+    pub fn iter(&self) -> impl Iterator<Item = &Syscall>
+    {
+        self.syscalls.iter()
+    }
+
+    /*
+     * Append the history to a file, one JSON syscall per line, and empty it.
+     * Return the number of syscalls written.
+     */
+    pub fn flush(&mut self, path: &Path) -> io::Result<usize>
+    {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let mut writer = BufWriter::new(file);
+
+        for syscall in &self.syscalls {
+            serde_json::to_writer(&mut writer, syscall)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+
+        let count = self.syscalls.len();
+        self.syscalls.clear();
+        self.dropped = 0;
+        Ok(count)
+    }
+}
+
+
+/*
+ * The counters of a syscall, the durations are summed in nanoseconds.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct SyscallStats {
+    pub count: u64,
+    pub total: u64,
+    pub forwarded: u64,
+    pub forward: u64,
+    pub executor: u64,
+    pub transport: u64,
+}
+
+impl SyscallStats {
+    /* In microseconds */
+    pub fn average(sum: u64, count: u64) -> Option<u64> {
+        match count {
+            0 => None,
+            _ => Some(sum / count / 1000),
+        }
+    }
+}
+
+
+#[derive(Default)]
+pub struct Statistics {
+    /* By syscall number and name */
+    syscalls: HashMap<(usize, String), SyscallStats>,
+}
+
+impl Statistics {
+
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn record(&mut self, syscall: &Syscall)
+    {
+        let key = (syscall.raw.no, syscall.name.clone());
+        let stats = self.syscalls.entry(key).or_default();
+        let timestamps = &syscall.timestamps;
+
+        stats.count += 1;
+        stats.total += timestamps.total().unwrap_or(0);
+        if let Some(forward) = timestamps.forward() {
+            stats.forwarded += 1;
+            stats.forward += forward;
+            stats.executor += timestamps.executor().unwrap_or(0);
+            stats.transport += timestamps.transport().unwrap_or(0);
+        }
+    }
+
+    pub fn iter(&self) -> hash_map::Iter<'_, (usize, String), SyscallStats>
+    {
+        self.syscalls.iter()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::syscall::{ Timestamps, decoder::DecodedSyscall, syscalls::open::Close };
+
+    fn syscall(no: usize, name: &str) -> Syscall
+    {
+        let mut syscall = Syscall::new();
+        syscall.raw.no = no;
+        syscall.name = name.to_string();
+        syscall
+    }
+
+    fn timed(no: usize, name: &str, total: u64, forward: Option<(u64, u64)>) -> Syscall
+    {
+        let mut syscall = syscall(no, name);
+        syscall.timestamps = Timestamps { entry: Some(1000), exit: Some(1000 + total), ..Default::default() };
+        if let Some((forward, executor)) = forward {
+            syscall.timestamps.forward_sent = Some(2000);
+            syscall.timestamps.reply_received = Some(2000 + forward);
+            syscall.timestamps.executor_start = Some(50);
+            syscall.timestamps.executor_end = Some(50 + executor);
+        }
+        syscall
+    }
+
+    #[test]
+    fn ring_keeps_the_latest_syscalls()
+    {
+        let mut history = SyscallHistory::new(3, false);
+        for no in 0..5 {
+            history.push(&syscall(no, "read"));
+        }
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.dropped(), 2);
+        let kept: Vec<usize> = history.iter().map(|syscall| syscall.raw.no).collect();
+        assert_eq!(kept, [2, 3, 4]);
+
+        history.push(&syscall(5, "read"));
+        let kept: Vec<usize> = history.iter().map(|syscall| syscall.raw.no).collect();
+        assert_eq!(kept, [3, 4, 5]);
+        assert_eq!(history.dropped(), 3);
+    }
+
+    #[test]
+    fn empty_ring_only_counts()
+    {
+        let mut history = SyscallHistory::new(0, false);
+        history.push(&syscall(0, "read"));
+        assert!(history.is_empty());
+        assert_eq!(history.dropped(), 1);
+    }
+
+    #[test]
+    fn raw_only_history_drops_the_decoded_values()
+    {
+        let mut traced = syscall(3, "close");
+        traced.decoded = Some(DecodedSyscall::Close(Close::new(traced.raw.clone())));
+        traced.backtrace = Some(Vec::new());
+
+        let mut history = SyscallHistory::new(2, true);
+        history.push(&traced);
+        assert!(history.iter().all(|syscall| syscall.decoded.is_none() && syscall.backtrace.is_none()));
+
+        let mut history = SyscallHistory::new(2, false);
+        history.push(&traced);
+        assert!(history.iter().all(|syscall| syscall.decoded.is_some() && syscall.backtrace.is_some()));
+    }
+
+    #[test]
+    fn flush_empties_the_ring()
+    {
+        let path = std::env::temp_dir().join(format!("sysfwd-history-{}.jsonl", std::process::id()));
+        let mut history = SyscallHistory::new(2, false);
+        for no in 0..3 {
+            history.push(&syscall(no, "read"));
+        }
+
+        assert_eq!(history.flush(&path).unwrap(), 2);
+        assert!(history.is_empty());
+        assert_eq!(history.dropped(), 0);
+        history.push(&syscall(3, "read"));
+        assert_eq!(history.flush(&path).unwrap(), 1);
+
+        // the flushes are appended
+        let lines: Vec<Syscall> = fs::read_to_string(&path).unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        fs::remove_file(&path).unwrap();
+        let flushed: Vec<usize> = lines.iter().map(|syscall| syscall.raw.no).collect();
+        assert_eq!(flushed, [1, 2, 3]);
+    }
+
+    #[test]
+    fn statistics_by_syscall()
+    {
+        let mut statistics = Statistics::new();
+        statistics.record(&timed(0, "read", 100, None));
+        statistics.record(&timed(0, "read", 300, Some((200, 150))));
+        statistics.record(&timed(1, "write", 50, None));
+        // a syscall of the tracer itself has no timestamps
+        statistics.record(&syscall(1, "write"));
+
+        let stats: HashMap<_, _> = statistics.iter().map(|((no, name), stats)| ((*no, name.as_str()), stats.clone())).collect();
+        assert_eq!(stats.len(), 2);
+
+        let read = &stats[&(0, "read")];
+        assert_eq!((read.count, read.total), (2, 400));
+        assert_eq!((read.forwarded, read.forward, read.executor, read.transport), (1, 200, 150, 50));
+
+        let write = &stats[&(1, "write")];
+        assert_eq!((write.count, write.total, write.forwarded), (2, 50, 0));
+
+        assert_eq!(SyscallStats::average(read.total, read.count), Some(0));
+        assert_eq!(SyscallStats::average(4_000_000, 2), Some(2000));
+        assert_eq!(SyscallStats::average(read.forward, 0), None);
+    }
+}
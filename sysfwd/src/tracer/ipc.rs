@@ -0,0 +1,189 @@
+/*
+ * To keep track of the System V IPC objects (shared memory, semaphores and message queues)
+ * which have been created or opened on the remote side.
+ */
+
+use serde::{ Serialize, Deserialize };
+
+
+
+
+
+/*
+ * IPC identifiers live in a different namespace for each kind of object
+ */
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IpcKind {
+    Shm,
+    Sem,
+    Msg,
+}
+
+/* The key asking for a new private object, it never identifies an existing one */
+pub const IPC_PRIVATE: i32 = 0;
+
+const IPC_RMID: usize = 0;
+const IPC_64: usize = 0x100;
+
+/*
+ * Whether a *ctl() command removes the object, with or without IPC_64.
+ */
+pub fn is_ipc_rmid(cmd: usize) -> bool
+{
+    cmd & !IPC_64 == IPC_RMID
+}
+
+/*
+ * Like file descriptors, the remote IPC identifiers are given to the tracee in another range
+ * than the local ones. A local identifier is the slot index plus 32768 times its sequence number:
+ * it only reaches the offset once a slot has been reused 32768 times.
+ */
+static REMOTE_IPC_ID_OFFSET: usize = 1 << 30;
+
+#[derive(Clone, Copy, Debug)]
+struct RemoteIpc {
+    kind: IpcKind,
+    key: i32,
+    id: usize,
+}
+
+/*
+ * The remote IPC identifiers, indexed by the identifier given to the tracee minus the offset.
+ */
+#[derive(Debug, Default)]
+pub struct IpcTable {
+    ipc_table: Vec<Option<RemoteIpc>>,
+}
+
+impl IpcTable {
+
+    pub fn new() -> Self
+    {
+        Self {
+            ipc_table: Vec::new(),
+        }
+    }
+
+    /*
+     * Record a remote IPC identifier with the key used to obtain it, and return the identifier for the tracee.
+     * An object obtained again (e.g. the same key) keeps its identifier.
+     * Typically used during the exit of a *get() system call.
+     */
+    pub fn open_remote(&mut self, kind: IpcKind, key: i32, id: usize) -> usize
+    {
+        let ipc = RemoteIpc { kind, key, id };
+        let index = match self.ipc_table.iter().position(|entry| matches!(entry, Some(e) if e.kind == kind && e.id == id)) {
+            Some(index) => index,
+            None => match self.ipc_table.iter().position(Option::is_none) {
+                Some(index) => index,
+                None => {
+                    self.ipc_table.push(None);
+                    self.ipc_table.len() - 1
+                },
+            },
+        };
+        self.ipc_table[index] = Some(ipc);
+        index + REMOTE_IPC_ID_OFFSET
+    }
+
+    /*
+     * Forget a remote IPC identifier, and return it.
+     * Typically used during the exit of a *ctl(IPC_RMID) system call.
+     */
+    pub fn close_remote(&mut self, kind: IpcKind, user_id: usize) -> Option<usize>
+    {
+        let id = self.translate(kind, user_id)?;
+        self.ipc_table[user_id - REMOTE_IPC_ID_OFFSET] = None;
+        Some(id)
+    }
+
+    /*
+     * Forget every remote IPC identifier, the IPC objects themselves outlive the tracee.
+     */
+    pub fn clear(&mut self)
+    {
+        self.ipc_table.clear();
+    }
+
+    /*
+     * Translate an identifier used by the tracee with the identifier of the remote object.
+     * Typically used during the entry of a *ctl(), semop() or msgsnd() system call.
+     */
+    pub fn translate(&self, kind: IpcKind, user_id: usize) -> Option<usize>
+    {
+        let index = user_id.checked_sub(REMOTE_IPC_ID_OFFSET)?;
+        match self.ipc_table.get(index) {
+            Some(Some(ipc)) if ipc.kind == kind => Some(ipc.id),
+            _ => None,
+        }
+    }
+
+    pub fn is_id_forwarded(&self, kind: IpcKind, user_id: usize) -> bool
+    {
+        self.translate(kind, user_id).is_some()
+    }
+
+    pub fn is_key_forwarded(&self, kind: IpcKind, key: i32) -> bool
+    {
+        key != IPC_PRIVATE && self.ipc_table.iter().flatten().any(|ipc| ipc.kind == kind && ipc.key == key)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_ids_are_translated()
+    {
+        let mut table = IpcTable::new();
+        let shm = table.open_remote(IpcKind::Shm, 42, 7);
+        let sem = table.open_remote(IpcKind::Sem, 42, 7);
+
+        // the remote identifiers are out of the range of the local ones
+        assert!(shm >= REMOTE_IPC_ID_OFFSET && sem >= REMOTE_IPC_ID_OFFSET);
+        assert_ne!(shm, sem);
+        assert_eq!(table.translate(IpcKind::Shm, shm), Some(7));
+        assert_eq!(table.translate(IpcKind::Sem, sem), Some(7));
+        // each kind has its own namespace
+        assert_eq!(table.translate(IpcKind::Msg, shm), None);
+        assert!(!table.is_id_forwarded(IpcKind::Shm, 7));
+
+        // the same object keeps its identifier
+        assert_eq!(table.open_remote(IpcKind::Shm, 42, 7), shm);
+    }
+
+    #[test]
+    fn remote_ids_lifecycle()
+    {
+        let mut table = IpcTable::new();
+        let first = table.open_remote(IpcKind::Msg, 1, 100);
+        let second = table.open_remote(IpcKind::Msg, IPC_PRIVATE, 101);
+        assert!(table.is_key_forwarded(IpcKind::Msg, 1));
+        assert!(!table.is_key_forwarded(IpcKind::Msg, IPC_PRIVATE));
+        assert!(!table.is_key_forwarded(IpcKind::Shm, 1));
+
+        assert_eq!(table.close_remote(IpcKind::Msg, first), Some(100));
+        assert_eq!(table.close_remote(IpcKind::Msg, first), None);
+        assert!(!table.is_key_forwarded(IpcKind::Msg, 1));
+        assert!(table.is_id_forwarded(IpcKind::Msg, second));
+
+        // the identifier is reused
+        assert_eq!(table.open_remote(IpcKind::Shm, 2, 200), first);
+
+        table.clear();
+        assert!(!table.is_id_forwarded(IpcKind::Msg, second));
+        assert!(!table.is_id_forwarded(IpcKind::Shm, first));
+    }
+
+    #[test]
+    fn rmid_with_or_without_ipc_64()
+    {
+        assert!(is_ipc_rmid(IPC_RMID));
+        assert!(is_ipc_rmid(IPC_RMID | IPC_64));
+        assert!(!is_ipc_rmid(2));
+        assert!(!is_ipc_rmid(2 | IPC_64));
+    }
+}
@@ -3,9 +3,13 @@
  */
 mod tracer_engine;
 pub mod file_descriptor;
+pub mod history;
+pub mod ipc;
 pub mod filtering;
+pub mod seccomp;
 
-pub use tracer_engine::TracerEngine;
+pub use tracer_engine::{ TracerEngine, ENGINE_DECODED_SYSCALLS };
+pub use seccomp::SeccompFilter;
 
 
 use std::io;
@@ -24,6 +28,9 @@ use crate::syscall::Syscall;
 pub trait TracerCallback {
     fn spawn_process(&mut self, program: String, prog_args: Vec<String>) -> Result<Pid, io::Error>;
     fn kill_process(&mut self, pid: Pid) -> Result<(), io::Error>;
+    fn attach_process(&mut self, pid: Pid) -> Result<(), io::Error>;
+    fn detach_process(&mut self, pid: Pid) -> Result<(), io::Error>;
+    fn flush_history(&mut self, pid: Pid, path: String) -> Result<(), io::Error>;
     fn start_tracing(&mut self, pid: Pid) -> Result<(), io::Error>;
     fn cont_tracing(&mut self, pid: Pid, signal: Option<Signal>) -> Result<(), io::Error>;
     fn stop_tracing(&mut self, pid: Pid) -> Result<(), io::Error>;
@@ -0,0 +1,181 @@
+/*
+ * The seccomp-BPF filter installed in the tracee, so that only the syscalls
+ * the rules are interested in stop the tracee (SECCOMP_RET_TRACE).
+ * The other syscalls run at native speed.
+ */
+use std::io;
+
+use nix::libc::{ self, sock_filter, sock_fprog };
+
+use crate::{
+    arch::{ TargetArch, Architecture },
+    error::{ Error, Result },
+};
+
+
+
+/* The offsets of the fields of struct seccomp_data */
+const SECCOMP_DATA_NR: u32 = 0;
+const SECCOMP_DATA_ARCH: u32 = 4;
+
+/*
+ * The exec of the tracee happens after the filter is installed but before the tracer
+ * sets PTRACE_O_TRACESECCOMP: the kernel would fail it with ENOSYS.
+ * It is reported by PTRACE_EVENT_EXEC anyway.
+ */
+const NEVER_TRACED: [&str; 2] = ["execve", "execveat"];
+
+
+#[derive(Clone, Debug)]
+pub struct SeccompFilter {
+    program: Vec<sock_filter>,
+}
+
+impl SeccompFilter {
+
+    /*
+     * Compile the syscalls to trace. The unknown syscall names of the architecture are ignored,
+     * and the syscalls of another convention (e.g. int 0x80) are always traced.
+     */
+    pub fn new<'a>(target_arch: TargetArch, syscalls: impl IntoIterator<Item = &'a str>) -> Result<Self>
+    {
+        let audit_arch = target_arch.audit_arch()
+            .ok_or_else(|| Error::Unsupported("seccomp filter for this architecture".to_string()))?;
+        let arch = Architecture::new(target_arch);
+
+        let mut numbers: Vec<usize> = syscalls.into_iter()
+            .filter(|name| !NEVER_TRACED.contains(name))
+            .filter_map(|name| arch.syscall_table.get_syscall_no(name))
+            .collect();
+        numbers.sort_unstable();
+        numbers.dedup();
+
+        let mut program = vec![
+            bpf_stmt(libc::BPF_LD | libc::BPF_W | libc::BPF_ABS, SECCOMP_DATA_ARCH),
+            bpf_jump(libc::BPF_JMP | libc::BPF_JEQ | libc::BPF_K, audit_arch, 1, 0),
+            bpf_stmt(libc::BPF_RET | libc::BPF_K, libc::SECCOMP_RET_TRACE),
+            bpf_stmt(libc::BPF_LD | libc::BPF_W | libc::BPF_ABS, SECCOMP_DATA_NR),
+        ];
+        for no in numbers {
+            program.push(bpf_jump(libc::BPF_JMP | libc::BPF_JEQ | libc::BPF_K, no as u32, 0, 1));
+            program.push(bpf_stmt(libc::BPF_RET | libc::BPF_K, libc::SECCOMP_RET_TRACE));
+        }
+        program.push(bpf_stmt(libc::BPF_RET | libc::BPF_K, libc::SECCOMP_RET_ALLOW));
+
+        Ok(Self { program })
+    }
+
+    /*
+     * Install the filter in the calling process, typically from the pre_exec of the tracee.
+     * It only makes syscalls, it is async-signal-safe.
+     *
+     * Without CAP_SYS_ADMIN, the kernel only accepts the filter with PR_SET_NO_NEW_PRIVS:
+     * the setuid programs and the file capabilities executed by the tracee do not grant privileges anymore.
+     */
+    pub fn install(&self) -> io::Result<()>
+    {
+        let prog = sock_fprog {
+            len: self.program.len() as u16,
+            filter: self.program.as_ptr() as *mut sock_filter,
+        };
+        let set_filter = || unsafe {
+            libc::syscall(libc::SYS_seccomp, libc::SECCOMP_SET_MODE_FILTER, 0, &prog as *const sock_fprog)
+        };
+
+        if set_filter() == 0 {
+            return Ok(());
+        }
+        if io::Error::last_os_error().raw_os_error() != Some(libc::EACCES) {
+            return Err(io::Error::last_os_error());
+        }
+        unsafe {
+            if libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) < 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        if set_filter() < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+
+fn bpf_stmt(code: u32, k: u32) -> sock_filter
+{
+    sock_filter { code: code as u16, jt: 0, jf: 0, k }
+}
+
+fn bpf_jump(code: u32, k: u32, jt: u8, jf: u8) -> sock_filter
+{
+    sock_filter { code: code as u16, jt, jf, k }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AUDIT_ARCH_X86_64: u32 = 0xc000_003e;
+    const AUDIT_ARCH_I386: u32 = 0x4000_0003;
+
+    /* Run the filter on a struct seccomp_data with the architecture and the syscall number */
+    fn verdict(filter: &SeccompFilter, arch: u32, nr: u32) -> u32
+    {
+        let mut accumulator = 0;
+        let mut pc = 0;
+        loop {
+            let insn = filter.program[pc];
+            pc += 1;
+            match insn.code as u32 {
+                code if code == libc::BPF_LD | libc::BPF_W | libc::BPF_ABS => {
+                    accumulator = match insn.k {
+                        SECCOMP_DATA_NR => nr,
+                        SECCOMP_DATA_ARCH => arch,
+                        k => panic!("load of the offset {}", k),
+                    };
+                },
+                code if code == libc::BPF_JMP | libc::BPF_JEQ | libc::BPF_K => {
+                    pc += match accumulator == insn.k {
+                        true => insn.jt as usize,
+                        false => insn.jf as usize,
+                    };
+                },
+                code if code == libc::BPF_RET | libc::BPF_K => return insn.k,
+                code => panic!("unexpected instruction {:#x}", code),
+            }
+        }
+    }
+
+    #[test]
+    fn only_the_given_syscalls_are_traced()
+    {
+        let filter = SeccompFilter::new(TargetArch::X86_64, ["read", "close", "read", "not_a_syscall"]).unwrap();
+
+        // read and close, once each
+        assert_eq!(filter.program.len(), 4 + 2 * 2 + 1);
+        assert_eq!(verdict(&filter, AUDIT_ARCH_X86_64, 0), libc::SECCOMP_RET_TRACE);
+        assert_eq!(verdict(&filter, AUDIT_ARCH_X86_64, 3), libc::SECCOMP_RET_TRACE);
+        assert_eq!(verdict(&filter, AUDIT_ARCH_X86_64, 1), libc::SECCOMP_RET_ALLOW);
+        assert_eq!(verdict(&filter, AUDIT_ARCH_X86_64, 9), libc::SECCOMP_RET_ALLOW);
+    }
+
+    #[test]
+    fn other_conventions_are_always_traced()
+    {
+        let filter = SeccompFilter::new(TargetArch::X86_64, ["read"]).unwrap();
+        assert_eq!(verdict(&filter, AUDIT_ARCH_I386, 1), libc::SECCOMP_RET_TRACE);
+        assert_eq!(verdict(&filter, AUDIT_ARCH_I386, 0), libc::SECCOMP_RET_TRACE);
+    }
+
+    #[test]
+    fn exec_is_never_traced()
+    {
+        let filter = SeccompFilter::new(TargetArch::X86_64, ["execve", "execveat"]).unwrap();
+        assert_eq!(filter.program.len(), 4 + 1);
+        assert_eq!(verdict(&filter, AUDIT_ARCH_X86_64, 59), libc::SECCOMP_RET_ALLOW);
+
+        let filter = SeccompFilter::new(TargetArch::X86_64, []).unwrap();
+        assert_eq!(verdict(&filter, AUDIT_ARCH_X86_64, 0), libc::SECCOMP_RET_ALLOW);
+    }
+}
@@ -5,38 +5,97 @@
  * The tracer engine takes care of handling syscalls.
  */
 use std::{
-    collections::HashMap,
+    collections::{ HashMap, HashSet },
+    os::unix::ffi::OsStrExt,
+    path::Path,
     sync::Arc,
     io,
 };
-use nix::libc::user_regs_struct;
+use nix::{
+    libc::{ user_regs_struct, AT_FDCWD, EINTR, MAP_ANONYMOUS, O_CREAT, O_EXCL, O_RDONLY, O_TRUNC, PATH_MAX, SEEK_SET },
+    sys::signal::{ kill, Signal },
+    unistd::Pid,
+};
 use serde_json;
 use crate::{
     arch::{ TargetArch, Architecture },
-    protocol::data::Client,
+    error::{ Error, Result },
+    memory::unwind::{ Unwinder, UnwindMethod },
+    protocol::data::{ Client, NotifySignal, SignalEvent, Transport },
     syscall::{
+        RawSyscall,
         Syscall,
-        decoder::{ Decoder, DecodedSyscall },
+        Timestamps,
+        args::{ Direction, Fd },
+        decoder::{ Decoder, DecodedSyscall, DecodeArg, DecodeLevel, DecodePolicy },
+        encoder::{ EncodeArg, EncodeEntry },
+        format::{ self, LogFormat },
+        syscalls::{ chmod, cwd, fsync, link, mkdir, msg, open, sem, shm, statfs, unlink, utimensat, xattr },
+        syscalls::io_uring::{ self, IoUringRing, IOSQE_FIXED_FILE },
     },
     tracer::{
-        filtering::{ Decision, Filter, Rule },
+        filtering::{ Decision, FakeResult, Filter, ResultOrigin, Rule },
         file_descriptor::FdTable,
+        history::{ Statistics, SyscallHistory, SyscallStats, DEFAULT_HISTORY_CAPACITY },
+        ipc::{ self, IpcKind, IpcTable },
     },
-    targets::operation::Operation,
+    targets::operation::{ Operation, SyscallStop },
+    trace::{ TraceHeader, TraceRecorder },
 };
 
 
 /*
-struct TraceeState { 
-    fd_table: HashMap<u16, FdLocation>,
+ * The state of a traced thread, kept aside while another thread is traced.
+ */
+struct TraceeState {
+    tid: i32,
+    tgid: i32,
+    regs: user_regs_struct,
+    syscall: Syscall,
+    remote_syscall: Syscall,
+    request: Option<Syscall>,
+    reply: Option<Syscall>,
+    fake_result: FakeResult,
+    insyscall: bool,
 }
 
 impl TraceeState {
-    pub fn new() -> Self {
-        Self { fd_table: HashMap::new() }
+    fn new(tid: i32, tgid: i32) -> Self {
+        Self {
+            tid,
+            tgid,
+            regs: empty_regs(),
+            syscall: Syscall::new(),
+            remote_syscall: Syscall::new(),
+            request: None,
+            reply: None,
+            fake_result: FakeResult::default(),
+            insyscall: false,
+        }
     }
 }
-*/
+
+/*
+ * The forwarded state of a process, shared by its threads and copied on fork.
+ */
+#[derive(Clone)]
+struct ProcessState {
+    fwd_fd_table: FdTable,
+    uring_table: HashMap<usize, IoUringRing>,
+}
+
+impl ProcessState {
+    fn new() -> Self {
+        Self { fwd_fd_table: FdTable::new(), uring_table: HashMap::new() }
+    }
+}
+
+
+/*
+ * The syscalls the engine instrumentation relies on, even when they are not forwarded:
+ * a seccomp filter must stop the tracee at them too.
+ */
+pub const ENGINE_DECODED_SYSCALLS: [&str; 3] = ["close", "mmap", "io_uring_setup"];
 
 
 pub struct TracerEngine {
@@ -48,18 +107,38 @@ pub struct TracerEngine {
 
     operator: Box<Operation>,
     decoder: Arc<Decoder>,
-    protocol: Client,
+    protocol: Box<dyn Transport>,
 
-    /* Tracee state */
+    /* Tracee state, of the thread currently traced */
+    tgid: i32,
     syscall: Syscall,
     remote_syscall: Syscall,
+    /* The syscall sent to the executor and its reply, before the instrumentation, kept for the trace */
+    request: Option<Syscall>,
+    reply: Option<Syscall>,
+    fake_result: FakeResult,
     insyscall: bool,
+    /* Process state, of the thread currently traced */
     fwd_fd_table: FdTable,
-    //state: TraceeState,
+    uring_table: HashMap<usize, IoUringRing>,
+    /* The IPC identifiers are system-wide */
+    fwd_ipc_table: IpcTable,
 
-    filter: Filter,
+    /* The states of the other threads and processes, by tid and tgid */
+    tracees: HashMap<i32, TraceeState>,
+    processes: HashMap<i32, ProcessState>,
 
-    saved_syscall: Vec<Syscall>,
+    filter: Filter,
+    decode_policy: DecodePolicy,
+    /* Unwind the stack of the tracee at each syscall entry */
+    unwinder: Option<Unwinder>,
+    /* The syscall numbers always fully decoded, None for every syscall */
+    decoded_syscalls: Option<HashSet<usize>>,
+
+    log_format: LogFormat,
+    history: SyscallHistory,
+    stats: Statistics,
+    recorder: Option<TraceRecorder>,
 }
 
 impl TracerEngine {
@@ -80,45 +159,30 @@ impl TracerEngine {
             pid: pid,
             arch: arch,
             //regs: vec![0; 33],
-            regs: user_regs_struct {
-                r15: 0,
-                r14: 0,
-                r13: 0,
-                r12: 0,
-                rbp: 0,
-                rbx: 0,
-                r11: 0,
-                r10: 0,
-                r9: 0,
-                r8: 0,
-                rax: 0,
-                rcx: 0,
-                rdx: 0,
-                rsi: 0,
-                rdi: 0,
-                orig_rax: 0,
-                rip: 0,
-                cs: 0,
-                eflags: 0,
-                rsp: 0,
-                ss: 0,
-                fs_base: 0,
-                gs_base: 0,
-                ds: 0,
-                es: 0,
-                fs: 0,
-                gs: 0,
-            },
+            regs: empty_regs(),
             operator: operator,
             decoder: decoder,
-            protocol: Client::new(ipv4_address, tracer_port, executor_port),
+            protocol: Box::new(Client::new(ipv4_address, tracer_port, executor_port)),
+            tgid: pid,      // Hypothesis: the main thread is traced first
             syscall: Syscall::new(),
             remote_syscall: Syscall::new(),
+            request: None,
+            reply: None,
+            fake_result: FakeResult::default(),
             insyscall: false,   // Hypothesis: we do the tracing from the start!
             fwd_fd_table: FdTable::new(),
-            //state: TraceeState::new(),
+            uring_table: HashMap::new(),
+            fwd_ipc_table: IpcTable::new(),
+            tracees: HashMap::new(),
+            processes: HashMap::new(),
             filter: Filter::new(String::from("filtername")),
-            saved_syscall: Vec::new(),
+            decode_policy: DecodePolicy::default(),
+            unwinder: None,
+            decoded_syscalls: None,
+            log_format: LogFormat::Strace,
+            history: SyscallHistory::new(DEFAULT_HISTORY_CAPACITY, false),
+            stats: Statistics::new(),
+            recorder: None,
         }
     }
 
@@ -131,25 +195,87 @@ impl TracerEngine {
         self.regs = regs.clone();
     }
 
-    pub fn trace(&mut self) -> Result<(), io::Error>
+    /*
+     * The syscall entry and exit are always accounted for, even when an error is returned,
+     * so that the tracing can go on with the next syscall.
+     */
+    pub fn trace(&mut self) -> Result<()>
     {
         match self.insyscall {
             false    => {
                 self.sync_entry();
-                self.trace_entry();
+                let result = self.trace_entry();
+                self.insyscall = true;
+                result
             },
 
             true   => {
                 self.sync_exit();
-                self.trace_exit();
+                let result = self.trace_exit();
+                self.insyscall = false;
+                result
             },
         }
-        Ok(())
+    }
+
+    /*
+     * Trace the syscall stop reported by the kernel (e.g. PTRACE_GET_SYSCALL_INFO),
+     * rather than guessing it from the previous stop.
+     * A seccomp stop is the syscall entry unless it follows the syscall-entry stop.
+     */
+    pub fn trace_stop(&mut self, stop: SyscallStop) -> Result<()>
+    {
+        match stop {
+            SyscallStop::Seccomp { .. } if self.insyscall => Ok(()),
+
+            SyscallStop::Entry { arch, no, args } | SyscallStop::Seccomp { arch, no, args } => {
+                self.insyscall = false;
+                self.syscall = Syscall::new();
+                self.remote_syscall = Syscall::new();
+                self.request = None;
+                self.reply = None;
+                self.set_syscall_entry(no, args[0], args[1], args[2], args[3], args[4], args[5], 0);
+
+                let result = self.check_arch(arch).and_then(|()| self.trace_entry());
+                self.insyscall = true;
+                result
+            },
+
+            SyscallStop::Exit { arch, retval } => {
+                if !self.insyscall {
+                    return Err(Error::Unsupported(format!("[{}] syscall exit without an entry", self.pid)));
+                }
+                self.insyscall = false;
+                self.check_arch(arch)?;
+
+                // Only for x86_64
+                self.set_syscall_exit(retval, self.regs.rdx as usize);
+                self.trace_exit()
+            },
+
+            SyscallStop::None => Ok(()),
+        }
+    }
+
+    /*
+     * A syscall of another convention (e.g. int 0x80 from a x86_64 process) would be misdecoded,
+     * it is left to the kernel.
+     */
+    fn check_arch(&self, arch: u32) -> Result<()>
+    {
+        match self.arch.name.audit_arch() {
+            Some(expected) if expected != arch => {
+                Err(Error::Unsupported(format!("[{}] syscall {} of arch {:#x}", self.pid, self.syscall.raw.no, arch)))
+            },
+            _ => Ok(()),
+        }
     }
 
     fn sync_entry(&mut self) {
         self.syscall = Syscall::new();
         self.remote_syscall = Syscall::new();
+        self.request = None;
+        self.reply = None;
 
         // Only for x86_64
         self.set_syscall_entry(self.regs.orig_rax as usize,
@@ -174,7 +300,6 @@ impl TracerEngine {
     pub fn set_syscall_entry(&mut self, scno: usize, arg1: usize, 
                              arg2: usize, arg3: usize, arg4: usize,
                              arg5: usize, arg6: usize, arg7: usize) {
-        // TODO: what about seccomp (see strace & PTRACE_GET_SYSCALL_INFO)
         self.syscall.raw.no = scno;
         self.syscall.raw.args[0] = arg1;
         self.syscall.raw.args[1] = arg2;
@@ -190,49 +315,387 @@ impl TracerEngine {
         self.syscall.raw.errno = errno;
     }
 
-    pub fn shutdown(&mut self) -> Result<(), io::Error>
+    pub fn shutdown(&mut self) -> io::Result<()>
+    {
+        // Print syscall statistics
+        // syscall number | how many? | timings | name
+        self.print_stats();
+        self.protocol.shutdown().map_err(io::Error::other)
+    }
+
+    /*
+     * Replace the executor, e.g. by a replay of a recorded trace.
+     */
+    pub fn set_transport(&mut self, transport: Box<dyn Transport>)
+    {
+        self.protocol = transport;
+    }
+
+    /* Threads and processes */
+
+    /*
+     * Make `tid` the traced thread, before synchronizing its registers.
+     * The state of the previous thread is kept aside, and an unknown thread is added on the fly.
+     */
+    pub fn select_tracee(&mut self, tid: i32)
     {
-        // Calculate & print syscall statistics
-        // syscall number | how many? | is_decoded? | name
-        match self.calculate_stats() {
-            Ok(syscall_stats) => {
-                self.print_stats(syscall_stats);
+        if tid == self.pid {
+            return;
+        }
+        if !self.is_traced(tid) {
+            let parent = read_status_field(tid, "PPid").unwrap_or(self.pid);
+            self.add_tracee(tid, parent);
+        }
+        let mut state = match self.tracees.remove(&tid) {
+            Some(state) => state,
+            None => return,
+        };
+
+        let previous_tgid = self.tgid;
+        self.swap_tracee(&mut state);
+        if state.tid != 0 {
+            self.tracees.insert(state.tid, state);
+        }
+
+        if self.tgid != previous_tgid {
+            let mut process = self.processes.remove(&self.tgid).unwrap_or_else(ProcessState::new);
+            self.swap_process(&mut process);
+            if previous_tgid != 0 {
+                self.processes.insert(previous_tgid, process);
+            }
+        }
+    }
+
+    /*
+     * Start tracing a thread or a process created by the tracee `parent`.
+     * A thread shares the forwarded state of its process, a process gets a copy of it.
+     */
+    pub fn add_tracee(&mut self, tid: i32, parent: i32)
+    {
+        if self.is_traced(tid) {
+            return;
+        }
+        let tgid = read_status_field(tid, "Tgid").unwrap_or(tid);
+
+        if tgid != self.tgid && !self.processes.contains_key(&tgid) {
+            let parent_tgid = match self.tracees.get(&parent) {
+                Some(state) => state.tgid,
+                None if parent == self.pid => self.tgid,
+                None => read_status_field(parent, "Tgid").unwrap_or(parent),
+            };
+            let mut process = match parent_tgid == self.tgid {
+                true => ProcessState { fwd_fd_table: self.fwd_fd_table.clone(), uring_table: self.uring_table.clone() },
+                false => self.processes.get(&parent_tgid).cloned().unwrap_or_else(ProcessState::new),
+            };
+            self.duplicate_remote_fds(&mut process.fwd_fd_table);
+            self.processes.insert(tgid, process);
+        }
+
+        self.tracees.insert(tid, TraceeState::new(tid, tgid));
+    }
+
+    /*
+     * Stop tracing an exited thread. The last thread of a process closes its forwarded fds.
+     */
+    pub fn remove_tracee(&mut self, tid: i32)
+    {
+        let tgid = match tid == self.pid {
+            true => {
+                self.pid = 0;
+                self.tgid
             },
-            Err(err) => {
-                println!("Oops, something happens when calculating syscall statistics: {}", err);
+            false => match self.tracees.remove(&tid) {
+                Some(state) => state.tgid,
+                None => return,
+            },
+        };
+
+        let alive = (self.pid != 0 && self.tgid == tgid) || self.tracees.values().any(|state| state.tgid == tgid);
+        if alive {
+            return;
+        }
+        if tgid == self.tgid {
+            let fds = self.fwd_fd_table.drain_remote();
+            self.close_remote_fds(fds);
+            self.uring_table.clear();
+            self.tgid = 0;
+        } else if let Some(mut process) = self.processes.remove(&tgid) {
+            let fds = process.fwd_fd_table.drain_remote();
+            self.close_remote_fds(fds);
+        }
+    }
+
+    /*
+     * A thread called execve(): the other threads of its process are gone,
+     * and the calling thread takes the pid of the process.
+     */
+    pub fn exec_tracee(&mut self, tid: i32, former_tid: i32)
+    {
+        self.select_tracee(former_tid);
+        let tgid = self.tgid;
+        self.tracees.retain(|_, state| state.tgid != tgid);
+        self.pid = tid;
+
+        // the io_uring fds are always close-on-exec
+        self.uring_table.clear();
+        if let Some(unwinder) = self.unwinder.as_mut() {
+            unwinder.invalidate(self.tgid);
+        }
+    }
+
+    /*
+     * Rebuild the forwarded state of an attached process: the files it already opened
+     * are reopened by the executor when a rule would forward their open().
+     * The process must be stopped. Return the number of fds adopted.
+     */
+    pub fn adopt_open_fds(&mut self) -> Result<usize>
+    {
+        let openat_no = self.decoder.get_syscall_no("openat")
+            .ok_or_else(|| Error::Unsupported("openat is not a syscall of the architecture".to_string()))?;
+
+        // like the syscalls of the tracee, the pathname is written at an address of the tracee, below its stack
+        let regs = self.operator.register.read_registers(self.pid)?;
+        let pathname_address = (regs.rsp as usize - 128 - 2 * PATH_MAX as usize) & !0xf;
+
+        let mut fds: Vec<usize> = std::fs::read_dir(format!("/proc/{}/fd", self.pid))
+            .map_err(Error::Target)?
+            .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+            .collect();
+        fds.sort_unstable();
+
+        let mut adopted = 0;
+        for fd in fds {
+            let path = match std::fs::read_link(format!("/proc/{}/fd/{}", self.pid, fd)) {
+                Ok(path) if path.is_absolute() => path,
+                // pipes, sockets, anonymous inodes cannot be reopened
+                _ => continue,
+            };
+            let (flags, pos) = read_fdinfo(self.pid, fd).unwrap_or((O_RDONLY as usize, 0));
+
+            let mut openat = Syscall::new();
+            openat.name = String::from("openat");
+            openat.raw.no = openat_no;
+            openat.raw.args[0] = AT_FDCWD as usize;
+            openat.raw.args[1] = pathname_address;
+            openat.raw.args[2] = flags & !((O_CREAT | O_EXCL | O_TRUNC) as usize);
+            let mut decoded = open::Openat::new(openat.raw.clone());
+            decoded.pathname.content = path.as_os_str().as_bytes().to_vec();
+            decoded.pathname.size = decoded.pathname.content.len();
+            openat.decoded = Some(DecodedSyscall::Openat(decoded));
+
+            let (decision, _) = self.filter.filter(false, &mut openat);
+            if decision != Decision::Forward {
+                continue;
+            }
+            openat.decision = Some(decision);
+
+            let kernel_fd = match self.send_engine_syscall(&openat) {
+                Ok(remote) if (remote.raw.retval as isize) >= 0 => remote.raw.retval,
+                Ok(remote) => {
+                    eprintln!("[{}] Unable to reopen {:?} remotely: {}", self.pid, path, remote.raw.retval as isize);
+                    continue;
+                },
+                Err(err) => {
+                    eprintln!("[{}] Unable to reopen {:?} remotely: {}", self.pid, path, err);
+                    continue;
+                },
+            };
+            if pos > 0 {
+                self.seek_remote_fd(kernel_fd, pos);
+            }
+            self.fwd_fd_table.adopt_remote(fd, kernel_fd);
+
+            // the rules see the open() returning the fd already used by the tracee
+            openat.raw.retval = fd;
+            if let Some(DecodedSyscall::Openat(decoded)) = openat.decoded.as_mut() {
+                decoded.retval = Some(Fd::new(fd));
+            }
+            self.filter.on_syscall_exit(&openat);
+            adopted += 1;
+        }
+        Ok(adopted)
+    }
+
+    fn seek_remote_fd(&mut self, kernel_fd: usize, pos: usize)
+    {
+        let lseek_no = match self.decoder.get_syscall_no("lseek") {
+            Some(no) => no,
+            None => return,
+        };
+
+        let mut lseek = Syscall::new();
+        lseek.name = String::from("lseek");
+        lseek.raw.no = lseek_no;
+        lseek.raw.args[0] = kernel_fd;
+        lseek.raw.args[1] = pos;
+        lseek.raw.args[2] = SEEK_SET as usize;
+        lseek.decision = Some(Decision::Forward);
+
+        if let Err(err) = self.send_engine_syscall(&lseek) {
+            eprintln!("[{}] Unable to seek the remote fd {}: {}", self.pid, kernel_fd, err);
+        }
+    }
+
+    /* Every traced thread */
+    pub fn tracees(&self) -> Vec<i32>
+    {
+        let mut tids: Vec<i32> = self.tracees.keys().copied().collect();
+        if self.pid != 0 {
+            tids.push(self.pid);
+        }
+        tids
+    }
+
+    /*
+     * Stop tracing every thread: the forwarded state is released, as the tracee cannot use it anymore.
+     * Return the threads to detach.
+     */
+    pub fn detach(&mut self) -> Vec<i32>
+    {
+        let tids = self.tracees();
+        for tid in tids.iter() {
+            self.remove_tracee(*tid);
+        }
+        self.fwd_ipc_table.clear();
+        tids
+    }
+
+    /*
+     * Report a signal event of a tracee to the executor.
+     */
+    pub fn notify_signal(&mut self, tid: i32, signal: Signal, event: SignalEvent) -> Result<()>
+    {
+        let signal = NotifySignal { signo: signal as i32, pid: tid, event };
+        self.protocol.notify_signal(&signal)
+    }
+
+    /*
+     * Whether the traced thread stopped between the entry and the exit of a syscall.
+     * With a seccomp filter, the tracer only needs the syscall-exit stop then.
+     */
+    pub fn in_syscall(&self) -> bool
+    {
+        self.insyscall
+    }
+
+    pub fn is_traced(&self, tid: i32) -> bool
+    {
+        tid == self.pid || self.tracees.contains_key(&tid)
+    }
+
+    fn swap_tracee(&mut self, state: &mut TraceeState)
+    {
+        std::mem::swap(&mut self.pid, &mut state.tid);
+        std::mem::swap(&mut self.tgid, &mut state.tgid);
+        std::mem::swap(&mut self.regs, &mut state.regs);
+        std::mem::swap(&mut self.syscall, &mut state.syscall);
+        std::mem::swap(&mut self.remote_syscall, &mut state.remote_syscall);
+        std::mem::swap(&mut self.request, &mut state.request);
+        std::mem::swap(&mut self.reply, &mut state.reply);
+        std::mem::swap(&mut self.fake_result, &mut state.fake_result);
+        std::mem::swap(&mut self.insyscall, &mut state.insyscall);
+    }
+
+    fn swap_process(&mut self, state: &mut ProcessState)
+    {
+        std::mem::swap(&mut self.fwd_fd_table, &mut state.fwd_fd_table);
+        std::mem::swap(&mut self.uring_table, &mut state.uring_table);
+    }
+
+    /*
+     * After fork, the child closing a forwarded fd must not close it for its parent:
+     * each remote fd is duplicated on the executor.
+     */
+    fn duplicate_remote_fds(&mut self, fd_table: &mut FdTable)
+    {
+        let dup_no = match self.decoder.get_syscall_no("dup") {
+            Some(no) => no,
+            None => return,
+        };
+
+        for (user_fd, kernel_fd) in fd_table.remote_fds() {
+            let mut dup = Syscall::new();
+            dup.name = String::from("dup");
+            dup.raw.no = dup_no;
+            dup.raw.args[0] = kernel_fd;
+            dup.decision = Some(Decision::Forward);
+
+            match self.send_engine_syscall(&dup) {
+                Ok(dup) if (dup.raw.retval as isize) >= 0 => fd_table.replace_remote(user_fd, dup.raw.retval),
+                Ok(dup) => eprintln!("[{}] Unable to duplicate the remote fd {}: {}", self.pid, kernel_fd, dup.raw.retval as isize),
+                Err(err) => eprintln!("[{}] Unable to duplicate the remote fd {}: {}", self.pid, kernel_fd, err),
             }
         }
-        Ok(())
     }
 
     /* Tracing */
 
-    fn trace_entry(&mut self) {
+    fn trace_entry(&mut self) -> Result<()> {
         //self._log_raw_entry();
+        self.syscall.timestamps.entry = Timestamps::now();
+
+        // a syscall which cannot be decoded is still traced, as if it was not decoded
+        let decoded = match self.decode_level() {
+            DecodeLevel::Raw => Ok(()),
+            DecodeLevel::Name => {
+                self.decoder.decode_name(&mut self.syscall);
+                Ok(())
+            },
+            DecodeLevel::Full => self.decoder.decode_entry(&mut self.syscall, self.pid, &self.operator),
+        };
+        if decoded.is_err() {
+            self.syscall.decoded = None;
+        }
+        self.decode_io_uring_entry();
+        self.syscall.timestamps.decoded = Timestamps::now();
 
-        // TODO: Add an option to decode only certain syscalls to increase speed.
-        self.decoder.decode_entry(&mut self.syscall, self.pid, &self.operator);
+        if let Some(unwinder) = self.unwinder.as_mut() {
+            let frames = unwinder.unwind(self.pid, self.tgid, &self.regs, self.operator.memory.as_ref());
+            self.syscall.backtrace = Some(frames);
+        }
 
-        self.filter_entry();
+        let rewritten = self.filter_entry();
         self.log_entry();
 
         // Note: When should the decoded syscall be sync with the RawSyscall/tracee?
-        self.carry_out_entry_decision();
+        self.carry_out_entry_decision(rewritten)?;
 
-        self.insyscall = true;
+        decoded
     }
 
-    fn trace_exit(&mut self) {
+    fn trace_exit(&mut self) -> Result<()> {
         //self._log_raw_exit();
+        self.syscall.timestamps.exit = Timestamps::now();
 
-        self.decoder.decode_exit(&mut self.syscall, self.pid, &self.operator);
+        // the result must be in place before the exit is decoded and logged
+        match self.syscall.decision {
+            Some(Decision::NoExec) => self.no_exec_exit()?,
+            // the signal did not terminate the tracee yet, the syscall is interrupted
+            Some(Decision::Kill) => self.kill_exit()?,
+            _ => (),
+        }
+        /*
+         * An inspected syscall is decoded again, whatever the decode policy:
+         * the arguments now contain what the kernel wrote in the Out arguments.
+         */
+        let mut decoded = Ok(());
+        if self.syscall.decision == Some(Decision::Inspect) {
+            decoded = self.decoder.decode_entry(&mut self.syscall, self.pid, &self.operator);
+            if decoded.is_err() {
+                self.syscall.decoded = None;
+            }
+        }
 
-        self.filter_exit();
-        self.log_exit();
+        let decoded = decoded.and(self.decoder.decode_exit(&mut self.syscall, self.pid, &self.operator));
+        self.decode_io_uring_exit();
 
-        self.carry_out_exit_decision();
+        self.filter_exit();
+        // logged once the result is known, e.g. the remote one of ForwardExit
+        let carried_out = self.carry_out_exit_decision();
+        self.log_exit(carried_out.as_ref().ok().copied().flatten());
+        carried_out?;
 
-        self.insyscall = false;
+        decoded
     }
 
 
@@ -246,150 +709,494 @@ impl TracerEngine {
                  self.pid, self.syscall.raw.retval as usize)
     }
 
+    pub fn set_log_format(&mut self, log_format: LogFormat) {
+        self.log_format = log_format;
+    }
+
     fn log_entry(&self) {
-        let json = serde_json::to_string(&self.syscall).unwrap();
-        println!("[{}] LOCAL: {}", self.pid, json)
+        // the strace format only prints complete syscalls
+        if self.log_format == LogFormat::Json {
+            let json = serde_json::to_string(&self.syscall).unwrap();
+            println!("[{}] LOCAL: {}", self.pid, json)
+        }
     }
 
-    fn log_exit(&mut self) {
-        let json = serde_json::to_string(&self.syscall).unwrap();
-        println!("[{}] LOCAL: {}", self.pid, json);
-        println!("");
+    /* The origin of the result, with ForwardExit */
+    fn log_exit(&mut self, origin: Option<ResultOrigin>) {
+        match self.log_format {
+            LogFormat::Json => {
+                let json = serde_json::to_string(&self.syscall).unwrap();
+                println!("[{}] LOCAL: {}", self.pid, json);
+                println!("");
+            },
+            LogFormat::Strace => {
+                // a forwarded syscall is displayed as executed by the executor
+                let line = match (self.syscall.decision, origin) {
+                    (Some(Decision::Forward), _) | (_, Some(ResultOrigin::Remote)) => format::format_strace(&self.remote_syscall, true),
+                    _ => format::format_strace(&self.syscall, false),
+                };
+                println!("[{}] {}{}", self.pid, line, format::format_timings(&self.syscall.timestamps));
+                if let Some(frames) = &self.syscall.backtrace {
+                    for frame in frames {
+                        println!("[{}]  > {}", self.pid, format::format_frame(frame));
+                    }
+                }
+            },
+        }
 
-        self.saved_syscall.push(self.syscall.clone());
+        self.stats.record(&self.syscall);
+        self.history.push(&self.syscall);
+        self.record_syscall();
     }
 
     /* Filtering */
 
-    fn filter_entry(&mut self) -> Option<Decision> {
-        self.syscall.decision = Some(self.filter.filter(self.insyscall, &self.syscall));
-        self.syscall.decision
+    /* When the syscall has been rewritten by the rule, return the raw syscall before */
+    fn filter_entry(&mut self) -> Option<RawSyscall> {
+        let entry = self.syscall.raw.clone();
+        let (decision, rewritten) = self.filter.filter(self.insyscall, &mut self.syscall);
+        self.syscall.decision = Some(decision);
+        rewritten.then_some(entry)
     }
 
     fn filter_exit(&mut self) -> Option<Decision> {
-        self.syscall.decision = Some(self.filter.filter(self.insyscall, &self.syscall));
+        let (decision, _) = self.filter.filter(self.insyscall, &mut self.syscall);
+        self.syscall.decision = Some(decision);
         self.syscall.decision
     }
 
-    fn carry_out_entry_decision(&mut self)
+    fn carry_out_entry_decision(&mut self, rewritten: Option<RawSyscall>) -> Result<()>
     {
         //TODO: finish implementing the decisions
+        // a forwarded syscall is rewritten by the executor
+        let local = matches!(self.syscall.decision,
+                             Some(Decision::Continue) | Some(Decision::Inspect) | Some(Decision::ForwardExit));
+        if let Some(entry) = rewritten.filter(|_| local) {
+            self.encode_entry(&entry)?;
+        }
+
         match self.syscall.decision {
-            Some(Decision::Continue) => {
-                self.continue_entry().unwrap();
-            },
-            Some(Decision::Forward) => {
-                self.forward_entry().unwrap();
-            },
-            _ => panic!("Decision not implemented")
+            Some(Decision::Continue) => self.continue_entry(),
+            Some(Decision::Forward) => self.forward_entry(),
+            Some(Decision::NoExec) => self.no_exec_entry(),
+            Some(Decision::Inspect) => self.continue_entry(),
+            Some(Decision::Kill) => self.kill_entry(),
+            Some(Decision::ForwardExit) => self.continue_entry(),
+            decision => Err(Error::Unsupported(format!("decision {:?}", decision))),
         }
     }
 
-    fn carry_out_exit_decision(&mut self)
+    /* With ForwardExit, return which result the tracee got */
+    fn carry_out_exit_decision(&mut self) -> Result<Option<ResultOrigin>>
     {
         // TODO: finish implementing the decisions
         // first the instrumentation, then the filter callback
         match self.syscall.decision {
             Some(Decision::Continue) => {
-                self.continue_exit().unwrap();
+                self.continue_exit()?;
                 self.filter.on_syscall_exit(&self.syscall);
             },
             Some(Decision::Forward) => {
-                self.forward_exit().unwrap();
+                self.forward_exit()?;
                 self.filter.on_syscall_exit(&self.remote_syscall);
             },
-            _ => panic!("Decision not implemented")
+            Some(Decision::NoExec) => {
+                // the fake result is already given to the tracee
+                self.filter.on_syscall_exit(&self.syscall);
+            },
+            Some(Decision::Inspect) => {
+                self.continue_exit()?;
+                self.filter.inspect(&self.syscall);
+                self.filter.on_syscall_exit(&self.syscall);
+            },
+            Some(Decision::Kill) => (),
+            Some(Decision::ForwardExit) => {
+                self.continue_exit()?;
+                let origin = self.forward_after_exit()?;
+                match origin {
+                    ResultOrigin::Local => self.filter.on_syscall_exit(&self.syscall),
+                    ResultOrigin::Remote => self.filter.on_syscall_exit(&self.remote_syscall),
+                }
+                return Ok(Some(origin));
+            },
+            decision => return Err(Error::Unsupported(format!("decision {:?}", decision))),
         }
+        Ok(None)
+    }
 
+    fn continue_entry(&mut self) -> Result<()>
+    {
+        // the rings of a forwarded io_uring live on the executor, an anonymous mapping stands for them
+        let fd = self.syscall.raw.args[4];
+        if self.syscall.name == "mmap" && self.uring_table.contains_key(&fd) && self.fwd_fd_table.translate(fd).is_some() {
+            let mut regs = self.operator.register.read_registers(self.pid)?;
+            regs.r10 |= MAP_ANONYMOUS as u64;
+            regs.r8 = -1_i64 as u64;
+            regs.r9 = 0;
+            self.operator.register.write_registers(self.pid, regs)?;
+        }
+        Ok(())
     }
 
-    fn continue_entry(&mut self) -> Result<(), io::Error>
+    fn continue_exit(&mut self) -> Result<()>
     {
-        // TODO
+        let retval = self.syscall.raw.retval;
+        if (retval as isize) < 0 {
+            return Ok(());
+        }
+
+        match self.syscall.decoded.as_ref() {
+            Some(DecodedSyscall::IoUringSetup(sc)) => {
+                if let Some(params) = sc.params.content {
+                    self.uring_table.insert(retval, IoUringRing::new(params));
+                }
+            },
+            Some(DecodedSyscall::Mmap(_)) => {
+                let (fd, offset) = (self.syscall.raw.args[4], self.syscall.raw.args[5]);
+                if let Some(ring) = self.uring_table.get_mut(&fd) {
+                    ring.map(offset, retval);
+                    if self.fwd_fd_table.translate(fd).is_some() && ring.is_mapped() {
+                        ring.init_header(self.pid, &self.operator).map_err(Error::Target)?;
+                    }
+                }
+            },
+            Some(DecodedSyscall::Close(_)) => {
+                self.uring_table.remove(&self.syscall.raw.args[0]);
+            },
+            _ => (),
+        };
         Ok(())
     }
 
-    fn continue_exit(&mut self) -> Result<(), io::Error>
+    /*
+     * Encode the syscall modified by a rule into the tracee registers and memory.
+     * The pointed data is written at the address of the arguments: a rule growing them
+     * (e.g., a longer path) must also point them to a memory area large enough.
+     * The raw arguments modified by the rule are kept over the decoded ones.
+     */
+    fn encode_entry(&mut self, entry: &RawSyscall) -> Result<()>
     {
-        // TODO
+        if let Some(decoded) = self.syscall.decoded.as_mut() {
+            let raw = self.syscall.raw.clone();
+            let encoded = decoded.encode_entry(raw.clone(), self.pid, &self.operator)
+                .map_err(|source| Error::Encode { syscall: self.syscall.name.clone(), source })?;
+            self.syscall.raw = keep_raw_changes(entry, &raw, encoded);
+        }
+
+        // Only for x86_64
+        let raw = &self.syscall.raw;
+        let mut regs = self.operator.register.read_registers(self.pid)?;
+        regs.orig_rax = raw.no as u64;
+        regs.rdi = raw.args[0] as u64;
+        regs.rsi = raw.args[1] as u64;
+        regs.rdx = raw.args[2] as u64;
+        regs.r10 = raw.args[3] as u64;
+        regs.r8 = raw.args[4] as u64;
+        regs.r9 = raw.args[5] as u64;
+        self.operator.register.write_registers(self.pid, regs)?;
+        Ok(())
+    }
+
+    /*
+     * Replace the local syscall with a dummy one, its return value is overwritten on exit.
+     */
+    fn replace_with_dummy(&mut self) -> Result<()>
+    {
+        // note: it would be more clean to modify self.syscall.raw values and synchronized once we return to the program execution.
+        // for now on x86-64, replace with getpid()
+        let mut regs = self.operator.register.read_registers(self.pid)?;
+        regs.orig_rax = 39_u64;  // getpid() in x86_64
+        self.operator.register.write_registers(self.pid, regs)?;
+        Ok(())
+    }
+
+    /* NoExec */
+
+    fn no_exec_entry(&mut self) -> Result<()>
+    {
+        self.fake_result = self.filter.no_exec(&self.syscall);
+        self.replace_with_dummy()
+    }
+
+    fn no_exec_exit(&mut self) -> Result<()>
+    {
+        let fake_result = std::mem::take(&mut self.fake_result);
+
+        for mut buffer in fake_result.buffers {
+            // the tracee's buffer is never overflowed
+            if let Some(&size) = buffer.size_arg.and_then(|arg| self.syscall.raw.args.get(arg)) {
+                buffer.content.truncate(size);
+            }
+            // a NULL pointer stays untouched, like the kernel would not fill it
+            if let Some(&address) = self.syscall.raw.args.get(buffer.arg).filter(|&&address| address != 0) {
+                self.operator.memory.write(self.pid, address, buffer.content)?;
+            }
+        }
+
+        let mut regs = self.operator.register.read_registers(self.pid)?;
+        regs.rax = fake_result.retval as u64;
+        self.operator.register.write_registers(self.pid, regs)?;
+        self.syscall.raw.retval = fake_result.retval;
+
         Ok(())
     }
 
+    /* Kill */
+
+    fn kill_entry(&mut self) -> Result<()>
+    {
+        let signal = self.filter.kill_signal(&self.syscall);
+        self.replace_with_dummy()?;
+        self.release_forwarded_state();
+
+        kill(Pid::from_raw(self.pid), signal)
+            .map_err(|errno| Error::Target(io::Error::from(errno)))
+    }
+
+    fn kill_exit(&mut self) -> Result<()>
+    {
+        let mut regs = self.operator.register.read_registers(self.pid)?;
+        regs.rax = -EINTR as u64;
+        self.operator.register.write_registers(self.pid, regs)?;
+        self.syscall.raw.retval = regs.rax as usize;
+        Ok(())
+    }
+
+    /*
+     * Close the fds forwarded to the executor and forget the forwarded state of the tracee.
+     * The failures are only reported, the tracee is terminated anyway.
+     * The remote IPC objects are system-wide and outlive the tracee: the other tracees keep using them.
+     */
+    fn release_forwarded_state(&mut self)
+    {
+        let fds = self.fwd_fd_table.drain_remote();
+        self.close_remote_fds(fds);
+        self.uring_table.clear();
+    }
+
+    fn close_remote_fds(&mut self, fds: Vec<usize>)
+    {
+        let close_no = match self.decoder.get_syscall_no("close") {
+            Some(no) => no,
+            None => return,
+        };
+
+        for kernel_fd in fds {
+            let mut close = Syscall::new();
+            close.name = String::from("close");
+            close.raw.no = close_no;
+            close.raw.args[0] = kernel_fd;
+            close.decoded = Some(DecodedSyscall::Close(open::Close::new(close.raw.clone())));
+            close.decision = Some(Decision::Forward);
+
+            if let Err(err) = self.send_engine_syscall(&close) {
+                eprintln!("[{}] Unable to close the remote fd {}: {}", self.pid, kernel_fd, err);
+            }
+        }
+    }
+
     /* Forwarding */
 
-    fn forward_entry(&mut self) -> Result<(), io::Error>
+    /*
+     * Send a syscall of the engine itself (e.g. closing the remote fds on exit).
+     * It is recorded as well, so that a replay of the trace answers it.
+     */
+    fn send_engine_syscall(&mut self, syscall: &Syscall) -> Result<Syscall>
+    {
+        let reply = self.protocol.send_syscall_entry(syscall);
+        if let Some(recorder) = self.recorder.as_mut() {
+            if let Err(err) = recorder.record(self.pid, syscall, Some(syscall), reply.as_ref().ok()) {
+                eprintln!("[{}] Unable to record the syscall, the recording stops: {}", self.pid, err);
+                self.recorder = None;
+            }
+        }
+        reply
+    }
+
+    fn forward_entry(&mut self) -> Result<()>
     {
         /* Pre-forward instrumentation */
-        self.instr_pre_forward().unwrap();
+        self.instr_pre_forward()?;
 
         /* Forward */
-        self.remote_syscall = self.protocol.send_syscall_entry(&self.remote_syscall).unwrap();
+        if self.recorder.is_some() {
+            self.request = Some(self.remote_syscall.clone());
+        }
+        self.syscall.timestamps.forward_sent = Timestamps::now();
+        let reply = self.protocol.send_syscall_entry(&self.remote_syscall);
+        self.stamp_reply(&reply);
+        self.remote_syscall = match reply {
+            Ok(remote_syscall) => remote_syscall,
+            Err(err) => {
+                // the local syscall has already been replaced, the tracee gets an I/O error
+                self.remote_syscall.decoded = None;
+                self.remote_syscall.raw.retval = -(nix::libc::EIO as isize) as usize;
+                return Err(err);
+            },
+        };
         //println!("[{}] remote syscall retval: {:#x}", self.pid, self.remote_syscall.raw.retval as usize);
-        let json = serde_json::to_string(&self.remote_syscall).unwrap();
-        println!("[{}] REMOTE: {}", self.pid, json);
+        if self.log_format == LogFormat::Json {
+            let json = serde_json::to_string(&self.remote_syscall).unwrap();
+            println!("[{}] REMOTE: {}", self.pid, json);
+        }
 
         /* Post-forward instrumentation */
-        self.instr_post_forward().unwrap();
+        self.instr_post_forward()?;
 
         Ok(())
     }
 
-    fn instr_pre_forward(&mut self) -> Result<(), io::Error>
+    /* The executor's timings are kept with the local syscall, which is the one saved */
+    fn stamp_reply(&mut self, reply: &Result<Syscall>)
+    {
+        self.syscall.timestamps.reply_received = Timestamps::now();
+        if let Ok(remote_syscall) = reply {
+            self.syscall.timestamps.executor_start = remote_syscall.timestamps.executor_start;
+            self.syscall.timestamps.executor_end = remote_syscall.timestamps.executor_end;
+            // a replay answers with the reply as received, e.g. with the remote fd
+            if self.recorder.is_some() {
+                self.reply = Some(remote_syscall.clone());
+            }
+        }
+    }
+
+    fn instr_pre_forward(&mut self) -> Result<()>
     {
         /* Syscall specific instrumentation */
         self.remote_syscall = self.syscall.clone();
         match self.remote_syscall.name.as_str() {
             "close" => {
                 // translate the fd with the remote fd
-                if let DecodedSyscall::Close(remote_syscall) = self.remote_syscall.decoded.as_mut().unwrap() {
+                if let Some(DecodedSyscall::Close(remote_syscall)) = self.remote_syscall.decoded.as_mut() {
                     let user_fd = remote_syscall.fd.value;
-                    let kernel_fd = self.fwd_fd_table.translate(user_fd).unwrap();
+                    let kernel_fd = self.fwd_fd_table.translate(user_fd).ok_or(Error::FdTranslation { fd: user_fd })?;
                     remote_syscall.fd.value = kernel_fd;
                 }
             },
             "read" => {
                 // translate the fd with the remote fd
-                if let DecodedSyscall::Read(remote_syscall) = self.remote_syscall.decoded.as_mut().unwrap() {
+                if let Some(DecodedSyscall::Read(remote_syscall)) = self.remote_syscall.decoded.as_mut() {
                     let user_fd = remote_syscall.fd.value;
-                    let kernel_fd = self.fwd_fd_table.translate(user_fd).unwrap(); // BUG=> la conversion ne s'est pas bien passe avec openat
+                    let kernel_fd = self.fwd_fd_table.translate(user_fd).ok_or(Error::FdTranslation { fd: user_fd })?; // BUG=> la conversion ne s'est pas bien passe avec openat
                     remote_syscall.fd.value = kernel_fd;
                 }
             },
             "write" => {
                 // translate the fd with the remote fd
-                if let DecodedSyscall::Write(remote_syscall) = self.remote_syscall.decoded.as_mut().unwrap() {
+                if let Some(DecodedSyscall::Write(remote_syscall)) = self.remote_syscall.decoded.as_mut() {
                     let user_fd = remote_syscall.fd.value;
-                    let kernel_fd = self.fwd_fd_table.translate(user_fd).unwrap();
+                    let kernel_fd = self.fwd_fd_table.translate(user_fd).ok_or(Error::FdTranslation { fd: user_fd })?;
                     remote_syscall.fd.value = kernel_fd;
                 }
             },
             "lseek" => {
                 // translate the fd with the remote fd
-                if let DecodedSyscall::Lseek(remote_syscall) = self.remote_syscall.decoded.as_mut().unwrap() {
+                if let Some(DecodedSyscall::Lseek(remote_syscall)) = self.remote_syscall.decoded.as_mut() {
                     let user_fd = remote_syscall.fd.value;
-                    let kernel_fd = self.fwd_fd_table.translate(user_fd).unwrap(); // BUG=> la conversion ne s'est pas bien passe avec openat
+                    let kernel_fd = self.fwd_fd_table.translate(user_fd).ok_or(Error::FdTranslation { fd: user_fd })?; // BUG=> la conversion ne s'est pas bien passe avec openat
                     remote_syscall.fd.value = kernel_fd;
                 }
             }
+            "fchmod" | "fchown" | "fchdir" | "fstatfs" | "fsync" | "fdatasync"
+            | "fsetxattr" | "fgetxattr" | "flistxattr" | "fremovexattr" => {
+                // translate the fd with the remote fd
+                match self.remote_syscall.decoded.as_mut().ok_or_else(|| not_decoded(&self.remote_syscall.name))? {
+                    DecodedSyscall::Fchmod(chmod::Fchmod { fd, .. })
+                    | DecodedSyscall::Fchown(chmod::Fchown { fd, .. })
+                    | DecodedSyscall::Fchdir(cwd::Fchdir { fd, .. })
+                    | DecodedSyscall::Fstatfs(statfs::Fstatfs { fd, .. })
+                    | DecodedSyscall::Fsync(fsync::Fsync { fd, .. })
+                    | DecodedSyscall::Fdatasync(fsync::Fdatasync { fd, .. })
+                    | DecodedSyscall::Fsetxattr(xattr::Fsetxattr { fd, .. })
+                    | DecodedSyscall::Fgetxattr(xattr::Fgetxattr { fd, .. })
+                    | DecodedSyscall::Flistxattr(xattr::Flistxattr { fd, .. })
+                    | DecodedSyscall::Fremovexattr(xattr::Fremovexattr { fd, .. }) => {
+                        fd.value = self.fwd_fd_table.translate(fd.value).ok_or(Error::FdTranslation { fd: fd.value })?;
+                    },
+                    _ => (),
+                }
+            },
+            "io_uring_enter" | "io_uring_register" => {
+                match self.remote_syscall.decoded.as_mut().ok_or_else(|| not_decoded(&self.remote_syscall.name))? {
+                    DecodedSyscall::IoUringEnter(remote_syscall) => {
+                        remote_syscall.fd.value = self.fwd_fd_table.translate(remote_syscall.fd.value)
+                            .ok_or(Error::FdTranslation { fd: remote_syscall.fd.value })?;
+                        // only the SQEs read from the local ring are pushed to the mirrored ring
+                        remote_syscall.to_submit.value = remote_syscall.sqes.len();
+                        remote_syscall.buffers.clear();
+                        for sqe in remote_syscall.sqes.iter_mut() {
+                            if let Some(mut buffer) = io_uring::sqe_buffer(sqe) {
+                                if matches!(buffer.direction, Direction::In) {
+                                    buffer.decode(self.pid, &self.operator).map_err(Error::Target)?;
+                                    remote_syscall.buffers.push(buffer);
+                                }
+                            }
+                            if sqe.flags & IOSQE_FIXED_FILE == 0 {
+                                if let Some(kernel_fd) = self.fwd_fd_table.translate(sqe.fd as usize) {
+                                    sqe.fd = kernel_fd as i32;
+                                }
+                            }
+                        }
+                    },
+                    DecodedSyscall::IoUringRegister(remote_syscall) => {
+                        remote_syscall.fd.value = self.fwd_fd_table.translate(remote_syscall.fd.value)
+                            .ok_or(Error::FdTranslation { fd: remote_syscall.fd.value })?;
+                    },
+                    _ => (),
+                }
+            },
+            "mkdirat" | "unlinkat" | "linkat" | "symlinkat" | "readlinkat"
+            | "fchmodat" | "fchownat" | "utimensat" => {
+                // only a directory opened on the remote side needs to be translated, AT_FDCWD stays untouched
+                match self.remote_syscall.decoded.as_mut().ok_or_else(|| not_decoded(&self.remote_syscall.name))? {
+                    DecodedSyscall::Mkdirat(mkdir::Mkdirat { dirfd, .. })
+                    | DecodedSyscall::Unlinkat(unlink::Unlinkat { dirfd, .. })
+                    | DecodedSyscall::Readlinkat(link::Readlinkat { dirfd, .. })
+                    | DecodedSyscall::Fchmodat(chmod::Fchmodat { dirfd, .. })
+                    | DecodedSyscall::Fchownat(chmod::Fchownat { dirfd, .. })
+                    | DecodedSyscall::Utimensat(utimensat::Utimensat { dirfd, .. })
+                    | DecodedSyscall::Symlinkat(link::Symlinkat { newdirfd: dirfd, .. }) => {
+                        if let Some(kernel_fd) = self.fwd_fd_table.translate(dirfd.value) {
+                            dirfd.value = kernel_fd;
+                        }
+                    },
+                    DecodedSyscall::Linkat(remote_syscall) => {
+                        if let Some(kernel_fd) = self.fwd_fd_table.translate(remote_syscall.olddirfd.value) {
+                            remote_syscall.olddirfd.value = kernel_fd;
+                        }
+                        if let Some(kernel_fd) = self.fwd_fd_table.translate(remote_syscall.newdirfd.value) {
+                            remote_syscall.newdirfd.value = kernel_fd;
+                        }
+                    },
+                    _ => (),
+                }
+            },
+            "shmctl" | "semop" | "semtimedop" | "semctl" | "msgsnd" | "msgrcv" | "msgctl" => {
+                // translate the IPC identifier with the remote one
+                let (kind, id) = match self.remote_syscall.decoded.as_mut().ok_or_else(|| not_decoded(&self.remote_syscall.name))? {
+                    DecodedSyscall::Shmctl(shm::Shmctl { shmid: id, .. }) => (IpcKind::Shm, id),
+                    DecodedSyscall::Semop(sem::Semop { semid: id, .. })
+                    | DecodedSyscall::Semtimedop(sem::Semtimedop { semid: id, .. })
+                    | DecodedSyscall::Semctl(sem::Semctl { semid: id, .. }) => (IpcKind::Sem, id),
+                    DecodedSyscall::Msgsnd(msg::Msgsnd { msqid: id, .. })
+                    | DecodedSyscall::Msgrcv(msg::Msgrcv { msqid: id, .. })
+                    | DecodedSyscall::Msgctl(msg::Msgctl { msqid: id, .. }) => (IpcKind::Msg, id),
+                    _ => return Err(not_decoded(&self.remote_syscall.name)),
+                };
+                id.value = self.fwd_ipc_table.translate(kind, id.value).ok_or(Error::IpcTranslation { id: id.value })?;
+            },
             _ => (),
         };
 
-        /* Replace local syscall with a dummy one */
-        // note: it would be more clean to modify self.syscall.raw values and synchronized once we return to the program execution.
-        // for now on x86-64, replace with getpid()
-        let mut regs = self.operator.register.read_registers(self.pid).unwrap();
-        regs.orig_rax = 39 as u64;  // getpid() in x86_64
-        self.operator.register.write_registers(self.pid, regs).unwrap();
-
-        Ok(())
+        self.replace_with_dummy()
     }
 
-    fn instr_post_forward(&mut self) -> Result<(), io::Error>
+    fn instr_post_forward(&mut self) -> Result<()>
     {
         match self.remote_syscall.name.as_str() {
             "open"  => {
                 // a bit ugly but we replace the return value with the remote fd to not overlap with local fd space.
-                if let DecodedSyscall::Open(remote_syscall) = self.remote_syscall.decoded.as_mut().unwrap() {
+                if let Some(DecodedSyscall::Open(remote_syscall)) = self.remote_syscall.decoded.as_mut() {
                     let retval = remote_syscall.retval.as_ref().unwrap().value;
                     if retval as i64 >= 0 {
                         let user_fd = self.fwd_fd_table.open_remote(retval);
@@ -400,7 +1207,7 @@ impl TracerEngine {
             },
             "openat" => {
                 // a bit ugly but we replace the return value with the remote fd to not overlap with local fd space.
-                if let DecodedSyscall::Openat(remote_syscall) = self.remote_syscall.decoded.as_mut().unwrap() {
+                if let Some(DecodedSyscall::Openat(remote_syscall)) = self.remote_syscall.decoded.as_mut() {
                     let retval = remote_syscall.retval.as_ref().unwrap().value;
                     if retval as i64 >= 0 {
                         let user_fd = self.fwd_fd_table.open_remote(retval);
@@ -411,20 +1218,98 @@ impl TracerEngine {
             }
             "close" => {
                 // on successful close, remove the fd from the table
-                if let DecodedSyscall::Close(remote_syscall) = self.remote_syscall.decoded.as_mut().unwrap() {
+                if let Some(DecodedSyscall::Close(remote_syscall)) = self.remote_syscall.decoded.as_mut() {
                     let retval = remote_syscall.retval.as_ref().unwrap().value;
-                    let user_fd = remote_syscall.fd.value;
+                    // the fd of the remote syscall is already translated
+                    let user_fd = self.syscall.raw.args[0];
                     if retval as i64 >= 0 {
                         let _kernel_fd = self.fwd_fd_table.close_remote(user_fd);
+                        self.uring_table.remove(&self.syscall.raw.args[0]);
                     }
                 }
             },
+            "io_uring_setup" => {
+                // like open(), the remote fd is moved to the forwarded fd space and its rings are tracked
+                if let Some(DecodedSyscall::IoUringSetup(remote_syscall)) = self.remote_syscall.decoded.as_mut() {
+                    let retval = remote_syscall.retval.as_ref().unwrap().value;
+                    if retval as i64 >= 0 {
+                        let user_fd = self.fwd_fd_table.open_remote(retval);
+                        remote_syscall.retval.as_mut().unwrap().value = user_fd;
+                        self.remote_syscall.raw.retval = user_fd;
+                        if let Some(params) = remote_syscall.params.content {
+                            self.uring_table.insert(user_fd, IoUringRing::new(params));
+                        }
+                    }
+                }
+            },
+            "shmget" | "semget" | "msgget" => {
+                // like open(), the remote IPC identifier is moved to the forwarded identifier space
+                let (kind, key, retval) = match self.remote_syscall.decoded.as_mut() {
+                    Some(DecodedSyscall::Shmget(remote_syscall)) => (IpcKind::Shm, remote_syscall.key.value, &mut remote_syscall.retval),
+                    Some(DecodedSyscall::Semget(remote_syscall)) => (IpcKind::Sem, remote_syscall.key.value, &mut remote_syscall.retval),
+                    Some(DecodedSyscall::Msgget(remote_syscall)) => (IpcKind::Msg, remote_syscall.key.value, &mut remote_syscall.retval),
+                    _ => return Ok(()),
+                };
+                let id = self.remote_syscall.raw.retval;
+                if id as i64 >= 0 {
+                    let user_id = self.fwd_ipc_table.open_remote(kind, key as i32, id);
+                    if let Some(retval) = retval.as_mut() {
+                        retval.value = user_id;
+                    }
+                    self.remote_syscall.raw.retval = user_id;
+                }
+            },
+            "shmctl" | "semctl" | "msgctl" => {
+                // on successful IPC_RMID, remove the identifier from the table
+                let retval = self.remote_syscall.raw.retval;
+                let (kind, cmd) = match self.remote_syscall.decoded.as_ref() {
+                    Some(DecodedSyscall::Shmctl(remote_syscall)) => (IpcKind::Shm, remote_syscall.cmd.value),
+                    Some(DecodedSyscall::Semctl(remote_syscall)) => (IpcKind::Sem, remote_syscall.cmd.value),
+                    Some(DecodedSyscall::Msgctl(remote_syscall)) => (IpcKind::Msg, remote_syscall.cmd.value),
+                    _ => return Ok(()),
+                };
+                // the identifier of the remote syscall is already translated
+                let user_id = self.syscall.raw.args[0];
+                if retval == 0 && ipc::is_ipc_rmid(cmd) {
+                    self.fwd_ipc_table.close_remote(kind, user_id);
+                }
+            },
             _ => (),
         };
         Ok(())
     }
 
-    fn forward_exit(&mut self) -> Result<(), io::Error>
+    /*
+     * Execute the syscall on the executor once the local execution is over,
+     * then give the tracee the result chosen by the rule.
+     */
+    fn forward_after_exit(&mut self) -> Result<ResultOrigin>
+    {
+        let mut remote_syscall = self.syscall.clone();
+        self.filter.prepare_forward_exit(&mut remote_syscall);
+
+        if self.recorder.is_some() {
+            self.request = Some(remote_syscall.clone());
+        }
+        self.syscall.timestamps.forward_sent = Timestamps::now();
+        let reply = self.protocol.send_syscall_entry(&remote_syscall);
+        self.stamp_reply(&reply);
+        self.remote_syscall = reply?;
+        if self.log_format == LogFormat::Json {
+            let json = serde_json::to_string(&self.remote_syscall).unwrap();
+            println!("[{}] REMOTE: {}", self.pid, json);
+        }
+
+        let origin = self.filter.select_result(&self.syscall, &self.remote_syscall);
+        if origin == ResultOrigin::Remote {
+            // the remote result is handled as a forwarded one, e.g. a remote fd joins the forwarded fds
+            self.instr_post_forward()?;
+            self.forward_exit()?;
+        }
+        Ok(origin)
+    }
+
+    fn forward_exit(&mut self) -> Result<()>
     {
         /* 
          * On local syscall exit, this is usually the moment when the forwarded syscall synchronizes 
@@ -433,30 +1318,101 @@ impl TracerEngine {
         // TODO
         //self.write_syscall_ret(self.remote_syscall.raw.retval, self.remote_syscall.raw.errno)?;
 
+        // a failed syscall has no memory to synchronize, and the return value is written back anyway
+        let synced = match (self.remote_syscall.raw.retval as isize) < 0 {
+            true => Ok(()),
+            false => self.sync_forwarded_memory(),
+        };
+
+        /* Syncrhonize back the return value and errno */
+        let mut regs = self.operator.register.read_registers(self.pid)?;
+        regs.rax = self.remote_syscall.raw.retval as u64;
+        regs.rdx = self.remote_syscall.raw.errno as u64;
+        self.operator.register.write_registers(self.pid, regs)?;
+
+        // verify the register write...
+        if self.log_format == LogFormat::Json {
+            let regs = self.operator.register.read_registers(self.pid)?;
+            println!("fwd exit regs: {:?}", regs);
+        }
+
+        synced
+    }
+
+    fn sync_forwarded_memory(&mut self) -> Result<()>
+    {
         match self.remote_syscall.name.as_str() {
             "read" => {
                 // sync the memory buffer
                 // TODO: should be automated by passing over all arguments
-                if let DecodedSyscall::Read(remote_syscall) = self.remote_syscall.decoded.as_ref().unwrap() {
+                if let Some(DecodedSyscall::Read(remote_syscall)) = self.remote_syscall.decoded.as_ref() {
                     let size = remote_syscall.buf.size;
                     let mem = &remote_syscall.buf.content[0..size];
                     let addr = remote_syscall.buf.address;
-                    self.operator.memory.write(self.pid, addr, mem.to_vec());
+                    self.operator.memory.write(self.pid, addr, mem.to_vec())?;
+                }
+            }
+            "readlink" | "readlinkat" | "getcwd" | "statfs" | "fstatfs"
+            | "getxattr" | "lgetxattr" | "fgetxattr" | "listxattr" | "llistxattr" | "flistxattr" => {
+                // sync the memory filled by the remote kernel
+                match self.remote_syscall.decoded.as_mut().ok_or_else(|| not_decoded(&self.remote_syscall.name))? {
+                    DecodedSyscall::Readlink(link::Readlink { buf, .. })
+                    | DecodedSyscall::Readlinkat(link::Readlinkat { buf, .. })
+                    | DecodedSyscall::Getcwd(cwd::Getcwd { buf, .. })
+                    | DecodedSyscall::Getxattr(xattr::Getxattr { value: buf, .. })
+                    | DecodedSyscall::Lgetxattr(xattr::Lgetxattr { value: buf, .. })
+                    | DecodedSyscall::Fgetxattr(xattr::Fgetxattr { value: buf, .. })
+                    | DecodedSyscall::Listxattr(xattr::Listxattr { list: buf, .. })
+                    | DecodedSyscall::Llistxattr(xattr::Llistxattr { list: buf, .. })
+                    | DecodedSyscall::Flistxattr(xattr::Flistxattr { list: buf, .. }) if !buf.content.is_empty() => {
+                        buf.encode(self.pid, &self.operator).map_err(Error::Target)?;
+                    },
+                    DecodedSyscall::Statfs(statfs::Statfs { buf, .. })
+                    | DecodedSyscall::Fstatfs(statfs::Fstatfs { buf, .. }) => {
+                        buf.encode(self.pid, &self.operator).map_err(Error::Target)?;
+                    },
+                    _ => (),
+                }
+            }
+            "io_uring_setup" => {
+                // sync the ring sizes and offsets given by the remote kernel
+                if let Some(DecodedSyscall::IoUringSetup(remote_syscall)) = self.remote_syscall.decoded.as_mut() {
+                    remote_syscall.params.encode(self.pid, &self.operator).map_err(Error::Target)?;
+                }
+            }
+            "io_uring_enter" => {
+                // sync the buffers filled by the completed operations, then the local rings
+                if let Some(DecodedSyscall::IoUringEnter(remote_syscall)) = self.remote_syscall.decoded.as_mut() {
+                    for buffer in remote_syscall.buffers.iter_mut().filter(|b| matches!(b.direction, Direction::Out)) {
+                        buffer.encode(self.pid, &self.operator).map_err(Error::Target)?;
+                    }
+                    if let Some(ring) = self.uring_table.get(&self.syscall.raw.args[0]) {
+                        let submitted = remote_syscall.retval.as_ref().unwrap().value;
+                        if (submitted as isize) > 0 {
+                            ring.consume_submissions(self.pid, &self.operator, submitted).map_err(Error::Target)?;
+                        }
+                        ring.push_completions(self.pid, &self.operator, &remote_syscall.cqes).map_err(Error::Target)?;
+                    }
+                }
+            }
+            "msgrcv" | "shmctl" | "msgctl" => {
+                // sync the memory filled by the remote kernel
+                match self.remote_syscall.decoded.as_mut().ok_or_else(|| not_decoded(&self.remote_syscall.name))? {
+                    DecodedSyscall::Msgrcv(msg::Msgrcv { msgp, .. }) => {
+                        msgp.encode(self.pid, &self.operator).map_err(Error::Target)?;
+                    },
+                    DecodedSyscall::Shmctl(shm::Shmctl { buf: Some(buf), .. }) => {
+                        buf.encode(self.pid, &self.operator).map_err(Error::Target)?;
+                    },
+                    DecodedSyscall::Msgctl(msg::Msgctl { buf: Some(buf), .. }) => {
+                        buf.encode(self.pid, &self.operator).map_err(Error::Target)?;
+                    },
+                    _ => (),
                 }
             }
             _ => (),
         };
 
-        /* Syncrhonize back the return value and errno */
-        let mut regs = self.operator.register.read_registers(self.pid).unwrap();
-        regs.rax = self.remote_syscall.raw.retval as u64;
-        regs.rdx = self.remote_syscall.raw.errno as u64;
-        self.operator.register.write_registers(self.pid, regs).unwrap();
-
-        // verify the register write...
-        let regs = self.operator.register.read_registers(self.pid).unwrap();
-        println!("fwd exit regs: {:?}", regs);
-
         Ok(())
     }
 
@@ -474,48 +1430,192 @@ impl TracerEngine {
     }
     */
 
+    /* io_uring */
+
+    /*
+     * The SQEs are not syscall arguments, they are read from the rings known by the engine.
+     */
+    fn decode_io_uring_entry(&mut self)
+    {
+        if let Some(DecodedSyscall::IoUringEnter(sc)) = self.syscall.decoded.as_mut() {
+            if let Some(ring) = self.uring_table.get(&sc.fd.value) {
+                match ring.read_submissions(self.pid, &self.operator, sc.to_submit.value) {
+                    Ok(sqes) => sc.sqes = sqes,
+                    Err(err) => eprintln!("[{}] Unable to read the io_uring submission queue: {}", self.pid, err),
+                }
+            }
+        }
+    }
+
+    /*
+     * The CQEs pending after a local io_uring_enter(), a forwarded one gets them from the executor.
+     */
+    fn decode_io_uring_exit(&mut self)
+    {
+        if self.syscall.decision == Some(Decision::Forward) {
+            return;
+        }
+        if let Some(DecodedSyscall::IoUringEnter(sc)) = self.syscall.decoded.as_mut() {
+            if let Some(ring) = self.uring_table.get(&sc.fd.value) {
+                match ring.read_completions(self.pid, &self.operator) {
+                    Ok(cqes) => sc.cqes = cqes,
+                    Err(err) => eprintln!("[{}] Unable to read the io_uring completion queue: {}", self.pid, err),
+                }
+            }
+        }
+    }
+
     /* Filtering management */
 
     pub fn load_rule(&mut self, index: usize, rule: Box<dyn Rule>)
     {
-        self.filter.insert(index, rule)
+        self.filter.insert(index, rule);
+        self.update_decoded_syscalls();
     }
 
     pub fn unload_rule(&mut self, index: usize) -> Box<dyn Rule>
     {
-        self.filter.remove(index)
+        let rule = self.filter.remove(index);
+        self.update_decoded_syscalls();
+        rule
+    }
+
+    /* Decoding */
+
+    /*
+     * Choose how much of the syscalls are decoded.
+     * The syscalls needed by the rules and by the engine are always fully decoded.
+     */
+    pub fn set_decode_policy(&mut self, policy: DecodePolicy)
+    {
+        self.decode_policy = policy;
+    }
+
+    /*
+     * Recover the call stack of each syscall, at its entry. It is attached to the syscall.
+     */
+    pub fn set_unwind_method(&mut self, method: Option<UnwindMethod>)
+    {
+        self.unwinder = method.map(Unwinder::new);
+    }
+
+    fn update_decoded_syscalls(&mut self)
+    {
+        self.decoded_syscalls = self.filter.syscalls().map(|names| {
+            names.iter()
+                .chain(ENGINE_DECODED_SYSCALLS.iter())
+                .filter_map(|name| self.decoder.get_syscall_no(name))
+                .collect()
+        });
+    }
+
+    fn decode_level(&self) -> DecodeLevel
+    {
+        match &self.decoded_syscalls {
+            Some(syscalls) if !syscalls.contains(&self.syscall.raw.no) => self.decode_policy.level(self.syscall.raw.no),
+            _ => DecodeLevel::Full,
+        }
+    }
+
+    /* Forwarded state */
+
+    /*
+     * Tell whether a System V IPC key has been obtained on the remote side.
+     * The identifiers returned by *get() are checked with is_ipc_id_forwarded().
+     */
+    pub fn is_ipc_key_forwarded(&self, kind: IpcKind, key: i32) -> bool
+    {
+        self.fwd_ipc_table.is_key_forwarded(kind, key)
+    }
+
+    pub fn is_ipc_id_forwarded(&self, kind: IpcKind, id: usize) -> bool
+    {
+        self.fwd_ipc_table.is_id_forwarded(kind, id)
+    }
+
+    /* History and statistics */
+
+    /*
+     * Keep the last `capacity` syscalls, only with their raw values if `raw_only`.
+     * The syscalls already saved are dropped, the statistics are kept.
+     */
+    pub fn set_history(&mut self, capacity: usize, raw_only: bool)
+    {
+        self.history = SyscallHistory::new(capacity, raw_only);
     }
 
-    /* Statistics */
+    pub fn history(&self) -> &SyscallHistory
+    {
+        &self.history
+    }
+
+    /*
+     * Record every syscall traced from now on to a trace file.
+     * The command is the program and the arguments of the tracee, for the trace header.
+     */
+    pub fn start_recording(&mut self, path: &Path, command: Vec<String>) -> io::Result<()>
+    {
+        let header = TraceHeader::new(self.arch.name, self.pid, command);
+        self.recorder = Some(TraceRecorder::create(path, &header)?);
+        Ok(())
+    }
 
-    fn calculate_stats(&self) -> Result<HashMap<(usize, String), i32>, io::Error>
+    /* Return the number of syscalls recorded */
+    pub fn stop_recording(&mut self) -> usize
     {
-        let mut syscall_stats: HashMap<(usize, String), i32> = HashMap::new();
+        self.recorder.take().map_or(0, |recorder| recorder.records())
+    }
 
-        for syscall in &self.saved_syscall {
-            let key = (syscall.raw.no.clone(), syscall.name.clone());
-            let count = syscall_stats.entry(key).or_insert(0);
-            *count += 1;
+    /*
+     * A syscall was forwarded when it was sent to the executor.
+     * Without a reply, a forwarded syscall failed with EIO, and ForwardExit kept the local result.
+     */
+    fn record_syscall(&mut self)
+    {
+        let recorder = match self.recorder.as_mut() {
+            Some(recorder) => recorder,
+            None => return,
+        };
+        let failed = self.syscall.timestamps.forward_sent.is_some() && self.syscall.decision == Some(Decision::Forward);
+        let remote = self.reply.as_ref()
+            .or(failed.then_some(&self.remote_syscall));
+        if let Err(err) = recorder.record(self.pid, &self.syscall, self.request.as_ref(), remote) {
+            eprintln!("[{}] Unable to record the syscall, the recording stops: {}", self.pid, err);
+            self.recorder = None;
         }
-        Ok(syscall_stats)
     }
 
-    fn print_stats(&self, syscall_stats: HashMap<(usize, String), i32>)
+    /* Append the history to a file and empty it, return the number of syscalls written */
+    pub fn flush_history(&mut self, path: &Path) -> io::Result<usize>
     {
-        //println!("{:?}", syscall_stats);
+        self.history.flush(path)
+    }
 
-        println!("+-----+------------------+--------+");
-        println!("| No  |       Name       | Number |");
-        println!("+-----+------------------+--------+");
+    /* The times are averages in microseconds, the forwarding ones only over the forwarded syscalls */
+    fn print_stats(&self)
+    {
+        let average = |sum, count| match SyscallStats::average(sum, count) {
+            Some(average) => average.to_string(),
+            None => String::from("-"),
+        };
+
+        println!("+-----+------------------+--------+----------+-----------+----------+----------+-----------+");
+        println!("| No  |       Name       | Number | Avg (us) | Forwarded | Fwd (us) | Exe (us) | Net (us)  |");
+        println!("+-----+------------------+--------+----------+-----------+----------+----------+-----------+");
 
-        for ((no, name), number) in syscall_stats {
-            let name_str = if name.is_empty() { "<empty>" } else { &name };
+        for ((no, name), stats) in self.stats.iter() {
+            let name_str = if name.is_empty() { "<empty>" } else { name };
             println!(
-                "| {:<3} | {:<16} | {:<6} |",
-                no, name_str, number
+                "| {:<3} | {:<16} | {:<6} | {:<8} | {:<9} | {:<8} | {:<8} | {:<9} |",
+                no, name_str, stats.count,
+                average(stats.total, stats.count),
+                stats.forwarded,
+                average(stats.forward, stats.forwarded),
+                average(stats.executor, stats.forwarded),
+                average(stats.transport, stats.forwarded),
             );
         }
-        println!("+-----+------------------+--------+");
+        println!("+-----+------------------+--------+----------+-----------+----------+----------+-----------+");
     }
 
 
@@ -557,4 +1657,129 @@ impl TracerEngine {
         self.interceptor.write_syscall_ret(self.pid, retval, errno)
     }
     */
-}
\ No newline at end of file
+}
+
+
+/* The arguments of the rewritten raw syscall which differ from the entry ones replace the encoded ones */
+fn keep_raw_changes(entry: &RawSyscall, rewritten: &RawSyscall, mut encoded: RawSyscall) -> RawSyscall
+{
+    for (index, arg) in rewritten.args.iter().enumerate() {
+        if entry.args.get(index) != Some(arg) {
+            encoded.args[index] = *arg;
+        }
+    }
+    encoded
+}
+
+/* A forwarded syscall must be decoded to be translated and synchronized */
+fn not_decoded(name: &str) -> Error
+{
+    Error::Unsupported(format!("{} is forwarded without being decoded", name))
+}
+
+
+fn empty_regs() -> user_regs_struct
+{
+    user_regs_struct {
+        r15: 0,
+        r14: 0,
+        r13: 0,
+        r12: 0,
+        rbp: 0,
+        rbx: 0,
+        r11: 0,
+        r10: 0,
+        r9: 0,
+        r8: 0,
+        rax: 0,
+        rcx: 0,
+        rdx: 0,
+        rsi: 0,
+        rdi: 0,
+        orig_rax: 0,
+        rip: 0,
+        cs: 0,
+        eflags: 0,
+        rsp: 0,
+        ss: 0,
+        fs_base: 0,
+        gs_base: 0,
+        ds: 0,
+        es: 0,
+        fs: 0,
+        gs: 0,
+    }
+}
+
+
+/* Read the open flags and the offset of a fd from /proc/<pid>/fdinfo/<fd> */
+fn read_fdinfo(pid: i32, fd: usize) -> Option<(usize, usize)>
+{
+    let fdinfo = std::fs::read_to_string(format!("/proc/{}/fdinfo/{}", pid, fd)).ok()?;
+    let field = |name: &str, radix: u32| {
+        fdinfo.lines()
+            .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+            .and_then(|value| usize::from_str_radix(value.trim(), radix).ok())
+    };
+    Some((field("flags", 8)?, field("pos", 10)?))
+}
+
+
+/* Read a field of /proc/<tid>/status, e.g. Tgid or PPid */
+fn read_status_field(tid: i32, field: &str) -> Option<i32>
+{
+    let status = std::fs::read_to_string(format!("/proc/{}/status", tid)).ok()?;
+    status.lines()
+        .find_map(|line| line.strip_prefix(field)?.strip_prefix(':'))
+        .and_then(|value| value.trim().parse().ok())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        syscall::syscalls::open::Close,
+        targets::{ process_vm::ProcessVm, ptrace::Ptrace },
+    };
+
+    use super::*;
+
+    /* Rewrite the fd of close() in the raw arguments only */
+    struct RawRewriteRule { }
+
+    impl Rule for RawRewriteRule {
+        fn filter_entry(&mut self, syscall: &mut Syscall) -> std::result::Result<Decision, std::io::Error> {
+            syscall.raw.args[0] = 4;
+            Ok(Decision::Continue)
+        }
+
+        fn filter_exit(&mut self, syscall: Syscall) -> std::result::Result<Decision, std::io::Error> {
+            Ok(syscall.decision.unwrap())
+        }
+
+        fn on_syscall_exit(&mut self, _syscall: Syscall) {
+        }
+    }
+
+    #[test]
+    fn raw_arguments_rewritten_by_a_rule_are_encoded()
+    {
+        let mut syscall = Syscall::new();
+        syscall.name = "close".to_string();
+        syscall.raw.no = 3;
+        syscall.raw.args = vec![3, 0, 0, 0, 0, 0];
+        syscall.decoded = Some(DecodedSyscall::Close(Close::new(syscall.raw.clone())));
+        let entry = syscall.raw.clone();
+
+        let mut filter = Filter::new(String::from("test"));
+        filter.insert(0, Box::new(RawRewriteRule { }));
+        assert_eq!(filter.filter(false, &mut syscall), (Decision::Continue, true));
+
+        // the decoded fd is still the one of the entry
+        let operation: Box<Operation> = Box::new(Operation { register: Box::new(Ptrace { }), memory: Box::new(ProcessVm { }) });
+        let pid = std::process::id() as i32;
+        let encoded = syscall.decoded.as_mut().unwrap().encode_entry(syscall.raw.clone(), pid, &operation).unwrap();
+        assert_eq!(encoded.args[0], 3);
+        assert_eq!(keep_raw_changes(&entry, &syscall.raw, encoded).args, vec![4, 0, 0, 0, 0, 0]);
+    }
+}
@@ -28,13 +28,13 @@ impl ForwardFileRule {
 
 impl Rule for ForwardFileRule {
 
-    fn filter_entry(&mut self, syscall: Syscall) -> Result<Decision, std::io::Error>
+    fn filter_entry(&mut self, syscall: &mut Syscall) -> Result<Decision, std::io::Error>
     {
         let mut decision = Decision::Pass;
         self.trigger_on_entry = false;
 
         //println!("Syscall: {:?}", syscall);
-        if let Some(decoded_syscall) = syscall.decoded {
+        if let Some(decoded_syscall) = &syscall.decoded {
 
             match decoded_syscall {
 
@@ -149,8 +149,8 @@ impl Rule for ForwardFileRule {
             return;
         }
 
-        if self.trigger_on_entry {
-            let decoded_syscall = syscall.decoded.as_ref().unwrap();
+        // a failed forward (e.g. a syscall missing from a replayed trace) has no decoded result
+        if let (true, Some(decoded_syscall)) = (self.trigger_on_entry, syscall.decoded.as_ref()) {
 
             match decoded_syscall {
 
@@ -189,4 +189,9 @@ impl Rule for ForwardFileRule {
 
         self.trigger_on_entry = false;
     }
-}
\ No newline at end of file
+
+    fn syscalls(&self) -> Option<Vec<&'static str>>
+    {
+        Some(vec!["open", "creat", "openat", "openat2", "close", "read", "write", "lseek"])
+    }
+}
@@ -0,0 +1,94 @@
+/*
+ *
+ */
+use std::collections::HashSet;
+
+use sysfwd::{
+    syscall::{
+        Syscall,
+        decoder::DecodedSyscall,
+    },
+    tracer::filtering::{ Decision, Rule },
+};
+
+
+
+/*
+ * Forward every io_uring instance, so that its operations run on the executor side.
+ * The engine mirrors the rings on the executor and translates the SQEs referencing forwarded fds.
+ */
+pub struct ForwardIoUringRule {
+    fds: HashSet<usize>,
+    trigger_on_entry: bool,     // to avoid having to recheck during the callback
+    closing_fd: Option<usize>,  // the fd seen on exit is the remote one
+}
+
+impl ForwardIoUringRule {
+
+    pub fn new() -> Self
+    {
+        ForwardIoUringRule { fds: HashSet::new(), trigger_on_entry: false, closing_fd: None }
+    }
+
+}
+
+impl Default for ForwardIoUringRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rule for ForwardIoUringRule {
+
+    fn filter_entry(&mut self, syscall: &mut Syscall) -> Result<Decision, std::io::Error>
+    {
+        self.closing_fd = None;
+        self.trigger_on_entry = match &syscall.decoded {
+            Some(DecodedSyscall::IoUringSetup(_)) => true,
+            Some(DecodedSyscall::IoUringEnter(sc)) => self.fds.contains(&sc.fd.value),
+            Some(DecodedSyscall::IoUringRegister(sc)) => self.fds.contains(&sc.fd.value),
+            Some(DecodedSyscall::Close(sc)) if self.fds.contains(&sc.fd.value) => {
+                self.closing_fd = Some(sc.fd.value);
+                true
+            },
+            /* Note: mmap() of the rings stays local, the engine replaces it with an anonymous mapping */
+            _ => false,
+        };
+
+        match self.trigger_on_entry {
+            true => Ok(Decision::Forward),
+            false => Ok(Decision::Pass),
+        }
+    }
+
+    fn filter_exit(&mut self, syscall: Syscall) -> Result<Decision, std::io::Error>
+    {
+        Ok(syscall.decision.unwrap())
+    }
+
+    fn on_syscall_exit(&mut self, syscall: Syscall)
+    {
+        if !self.trigger_on_entry || syscall.decision != Some(Decision::Forward) {
+            return;
+        }
+        self.trigger_on_entry = false;
+
+        let retval = syscall.raw.retval;
+        match syscall.decoded {
+            Some(DecodedSyscall::IoUringSetup(_)) if retval as i64 >= 0 => {
+                self.fds.insert(retval);
+            },
+            Some(DecodedSyscall::Close(_)) if retval == 0 => {
+                if let Some(fd) = self.closing_fd.take() {
+                    self.fds.remove(&fd);
+                }
+            },
+            _ => (),
+        }
+    }
+
+    fn syscalls(&self) -> Option<Vec<&'static str>>
+    {
+        Some(vec!["io_uring_setup", "io_uring_enter", "io_uring_register", "close"])
+    }
+}
@@ -0,0 +1,199 @@
+/*
+ *
+ */
+use std::collections::HashSet;
+
+use sysfwd::{
+    syscall::{
+        Syscall,
+        decoder::DecodedSyscall,
+    },
+    tracer::{
+        filtering::{ Decision, Rule },
+        ipc::{ self, IpcKind },
+    },
+};
+
+
+
+/*
+ * Forward every System V IPC object of a given kind obtained with a given key,
+ * and every operation on the identifiers returned for it.
+ */
+pub struct ForwardIpcRule {
+    pub kind: IpcKind,
+    pub key: i32,
+    ids: HashSet<usize>,
+    trigger_on_entry: bool,     // to avoid having to recheck during the callback
+    removing_id: Option<usize>, // the identifier seen on exit is the remote one
+}
+
+impl ForwardIpcRule {
+
+    pub fn new(kind: IpcKind, key: i32) -> Self
+    {
+        ForwardIpcRule { kind, key, ids: HashSet::new(), trigger_on_entry: false, removing_id: None }
+    }
+
+    /* Whether the key currently designates an object living on the remote side */
+    pub fn is_forwarded(&self) -> bool
+    {
+        !self.ids.is_empty()
+    }
+
+    fn is_forwarded_id(&self, kind: IpcKind, id: usize) -> bool
+    {
+        kind == self.kind && self.ids.contains(&id)
+    }
+
+    /* Like is_forwarded_id(), keeping the identifier removed by IPC_RMID until the exit */
+    fn is_forwarded_ctl(&mut self, kind: IpcKind, id: usize, cmd: usize) -> bool
+    {
+        let forwarded = self.is_forwarded_id(kind, id);
+        if forwarded && ipc::is_ipc_rmid(cmd) {
+            self.removing_id = Some(id);
+        }
+        forwarded
+    }
+
+}
+
+impl Rule for ForwardIpcRule {
+
+    fn filter_entry(&mut self, syscall: &mut Syscall) -> Result<Decision, std::io::Error>
+    {
+        self.removing_id = None;
+        self.trigger_on_entry = match &syscall.decoded {
+            /* Get class syscalls */
+            Some(DecodedSyscall::Shmget(sc)) => self.kind == IpcKind::Shm && sc.key.value as i32 == self.key,
+            Some(DecodedSyscall::Semget(sc)) => self.kind == IpcKind::Sem && sc.key.value as i32 == self.key,
+            Some(DecodedSyscall::Msgget(sc)) => self.kind == IpcKind::Msg && sc.key.value as i32 == self.key,
+
+            /* Operations on an identifier */
+            Some(DecodedSyscall::Shmctl(sc)) => self.is_forwarded_ctl(IpcKind::Shm, sc.shmid.value, sc.cmd.value),
+            Some(DecodedSyscall::Semop(sc)) => self.is_forwarded_id(IpcKind::Sem, sc.semid.value),
+            Some(DecodedSyscall::Semtimedop(sc)) => self.is_forwarded_id(IpcKind::Sem, sc.semid.value),
+            Some(DecodedSyscall::Semctl(sc)) => self.is_forwarded_ctl(IpcKind::Sem, sc.semid.value, sc.cmd.value),
+            Some(DecodedSyscall::Msgsnd(sc)) => self.is_forwarded_id(IpcKind::Msg, sc.msqid.value),
+            Some(DecodedSyscall::Msgrcv(sc)) => self.is_forwarded_id(IpcKind::Msg, sc.msqid.value),
+            Some(DecodedSyscall::Msgctl(sc)) => self.is_forwarded_ctl(IpcKind::Msg, sc.msqid.value, sc.cmd.value),
+
+            /* Note: shmat() cannot be forwarded since the segment would be mapped in the executor */
+            _ => false,
+        };
+
+        match self.trigger_on_entry {
+            true => Ok(Decision::Forward),
+            false => Ok(Decision::Pass),
+        }
+    }
+
+    fn filter_exit(&mut self, syscall: Syscall) -> Result<Decision, std::io::Error>
+    {
+        Ok(syscall.decision.unwrap())
+    }
+
+    fn on_syscall_exit(&mut self, syscall: Syscall)
+    {
+        if !self.trigger_on_entry || syscall.decision != Some(Decision::Forward) {
+            return;
+        }
+        self.trigger_on_entry = false;
+
+        let retval = syscall.raw.retval;
+        match syscall.decoded {
+            Some(DecodedSyscall::Shmget(_))
+            | Some(DecodedSyscall::Semget(_))
+            | Some(DecodedSyscall::Msgget(_)) if retval as i64 >= 0 => {
+                self.ids.insert(retval);
+            },
+
+            Some(DecodedSyscall::Shmctl(_))
+            | Some(DecodedSyscall::Semctl(_))
+            | Some(DecodedSyscall::Msgctl(_)) if retval == 0 && self.removing_id.is_some() => {
+                self.ids.remove(&self.removing_id.unwrap());
+            },
+
+            _ => (),
+        }
+    }
+
+    fn syscalls(&self) -> Option<Vec<&'static str>>
+    {
+        Some(vec!["shmget", "semget", "msgget", "shmctl", "semop", "semtimedop", "semctl", "msgsnd", "msgrcv", "msgctl"])
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use sysfwd::syscall::{ RawSyscall, syscalls::shm::{ Shmctl, Shmget } };
+
+    use super::*;
+
+    const KEY: usize = 0x1234;
+    const IPC_RMID: usize = 0;
+    const USER_ID: usize = 1 << 30;
+    const REMOTE_ID: usize = 7;
+
+    fn raw(no: usize, args: [usize; 3], retval: usize) -> RawSyscall
+    {
+        let mut raw = RawSyscall::new();
+        raw.no = no;
+        raw.args = vec![args[0], args[1], args[2], 0, 0, 0];
+        raw.retval = retval;
+        raw
+    }
+
+    /* The syscall as seen by the rule, on entry with the values of the tracee, and on exit */
+    fn shmget(retval: usize) -> Syscall
+    {
+        let mut syscall = Syscall::new();
+        syscall.name = "shmget".to_string();
+        syscall.raw = raw(29, [KEY, 4096, 0], retval);
+        syscall.decoded = Some(DecodedSyscall::Shmget(Shmget::new(syscall.raw.clone())));
+        syscall
+    }
+
+    fn shmctl(id: usize, cmd: usize) -> Syscall
+    {
+        let mut syscall = Syscall::new();
+        syscall.name = "shmctl".to_string();
+        syscall.raw = raw(31, [id, cmd, 0], 0);
+        syscall.decoded = Some(DecodedSyscall::Shmctl(Shmctl::new(syscall.raw.clone())));
+        syscall
+    }
+
+    fn forward(rule: &mut ForwardIpcRule, mut entry: Syscall, mut exit: Syscall)
+    {
+        assert_eq!(rule.filter_entry(&mut entry).unwrap(), Decision::Forward);
+        exit.decision = Some(Decision::Forward);
+        rule.on_syscall_exit(exit);
+    }
+
+    #[test]
+    fn removed_objects_are_not_forwarded()
+    {
+        let mut rule = ForwardIpcRule::new(IpcKind::Shm, KEY as i32);
+        forward(&mut rule, shmget(0), shmget(USER_ID));
+        assert!(rule.is_forwarded());
+
+        // the identifier is translated on exit
+        forward(&mut rule, shmctl(USER_ID, IPC_RMID), shmctl(REMOTE_ID, IPC_RMID));
+        assert!(!rule.is_forwarded());
+
+        let mut syscall = shmctl(USER_ID, IPC_RMID);
+        assert_eq!(rule.filter_entry(&mut syscall).unwrap(), Decision::Pass);
+    }
+
+    #[test]
+    fn other_commands_keep_the_object_forwarded()
+    {
+        const IPC_STAT: usize = 2;
+
+        let mut rule = ForwardIpcRule::new(IpcKind::Shm, KEY as i32);
+        forward(&mut rule, shmget(0), shmget(USER_ID));
+        forward(&mut rule, shmctl(USER_ID, IPC_STAT), shmctl(REMOTE_ID, IPC_STAT));
+        assert!(rule.is_forwarded());
+    }
+}
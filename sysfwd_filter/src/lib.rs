@@ -4,8 +4,14 @@
 
 /* Each filter is contained in a module */
 mod forward_file;
+mod forward_ipc;
+mod forward_io_uring;
+mod no_exec;
 
 
 
 /* List of exported filters */
 pub use forward_file::ForwardFileRule;
+pub use forward_ipc::ForwardIpcRule;
+pub use forward_io_uring::ForwardIoUringRule;
+pub use no_exec::NoExecRule;
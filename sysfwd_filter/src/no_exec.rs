@@ -0,0 +1,74 @@
+/*
+ * A rule stubbing out syscalls: they are never executed, locally or remotely,
+ * and the tracee gets the result configured for each of them.
+ */
+use std::collections::HashMap;
+
+use sysfwd::{
+    syscall::Syscall,
+    tracer::filtering::{ Decision, FakeResult, Rule },
+};
+
+
+
+/*
+ * Do not execute the given syscalls, the tracee gets a fake result instead.
+ * E.g., to stub out reboot() or the ioctls checking a hardware device.
+ */
+pub struct NoExecRule {
+    results: HashMap<&'static str, FakeResult>,
+}
+
+impl NoExecRule {
+
+    pub fn new() -> Self
+    {
+        NoExecRule { results: HashMap::new() }
+    }
+
+    /*
+     * Stub the syscall `name` with the result given.
+     * The buffers of a variable size, e.g. the one of read(), should be given with FakeResult::with_sized_buffer().
+     */
+    pub fn stub(&mut self, name: &'static str, result: FakeResult)
+    {
+        self.results.insert(name, result);
+    }
+
+}
+
+impl Default for NoExecRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rule for NoExecRule {
+
+    fn filter_entry(&mut self, syscall: &mut Syscall) -> Result<Decision, std::io::Error>
+    {
+        match self.results.contains_key(syscall.name.as_str()) {
+            true => Ok(Decision::NoExec),
+            false => Ok(Decision::Pass),
+        }
+    }
+
+    fn filter_exit(&mut self, syscall: Syscall) -> Result<Decision, std::io::Error>
+    {
+        Ok(syscall.decision.unwrap())
+    }
+
+    fn on_syscall_exit(&mut self, _syscall: Syscall)
+    {
+    }
+
+    fn syscalls(&self) -> Option<Vec<&'static str>>
+    {
+        Some(self.results.keys().copied().collect())
+    }
+
+    fn no_exec(&mut self, syscall: Syscall) -> FakeResult
+    {
+        self.results.get(syscall.name.as_str()).cloned().unwrap_or_default()
+    }
+}